@@ -0,0 +1,79 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use peacher_sdk::tippytappy::*;
+
+/// Roughly 1 MB of serialized bill text.
+fn large_document(edit: &str) -> DocumentView {
+    let mut builder = DocumentView::builder();
+    for section in 0..1_000 {
+        builder = builder
+            .heading(2, |h| h.text(format!("Section {section}")))
+            .paragraph(|p| {
+                p.text("The department shall, in consultation with ")
+                    .member(section % 150, format!("@member{}", section % 150))
+                    .text(", submit a report on the implementation of ")
+                    .legislation(section, format!("HB {section}"))
+                    .text(" to the appropriate committees of the legislature.")
+            })
+            .ordered_list(1, |l| {
+                l.item_text("the number of applications received;")
+                    .item_text("the number of applications approved; and")
+                    .item_text("any recommendations for legislation.")
+            });
+    }
+    builder.text(edit).build().unwrap()
+}
+
+fn compile(c: &mut Criterion) {
+    let original = large_document("original");
+    let edited = large_document("edited");
+
+    let mut group = c.benchmark_group("compile");
+    group.sample_size(20);
+    group.bench_function("full", |b| {
+        // the editor keeps its copy of the document, so the clone is part of the cost.
+        b.iter(|| black_box(edited.clone().compile()))
+    });
+    group.bench_function("incremental", |b| {
+        let mut compiler = IncrementalCompiler::new();
+        compiler.compile(&original);
+        let mut documents = [&original, &edited].into_iter().cycle();
+        b.iter(|| {
+            black_box(compiler.compile(documents.next().unwrap()));
+        })
+    });
+    group.finish();
+}
+
+fn decompile(c: &mut Criterion) {
+    let original = large_document("original").compile();
+    let edited = large_document("edited").compile();
+
+    let mut group = c.benchmark_group("decompile");
+    group.sample_size(20);
+    group.bench_function("full", |b| {
+        b.iter(|| {
+            let mut relationships = edited.relationships.clone();
+            black_box(
+                edited
+                    .document
+                    .clone()
+                    .visit_and_decompile(&mut relationships),
+            )
+        })
+    });
+    group.bench_function("incremental", |b| {
+        let mut compiler = IncrementalCompiler::new();
+        compiler.decompile(&original.document, &original.relationships);
+        let mut documents = [&original, &edited].into_iter().cycle();
+        b.iter(|| {
+            let result = documents.next().unwrap();
+            black_box(compiler.decompile(&result.document, &result.relationships));
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, compile, decompile);
+criterion_main!(benches);
@@ -0,0 +1,97 @@
+use anyhow::Result;
+use clap::Subcommand;
+
+use crate::{
+    cli::resource::Config,
+    client::PeacherClient,
+    sync::{ApiSync, ExternalClient},
+};
+
+/// Subcommands of your own, added next to the built-in ones.
+///
+/// Derive [`Subcommand`] on an enum, implement this for it and start the CLI with
+/// [`cli_with_commands`](crate::cli::cli_with_commands). Names must not clash with the
+/// built-in commands.
+///
+/// ```ignore
+/// #[derive(Subcommand, Debug, Clone)]
+/// enum ScraperCmd {
+///     /// Drops the cached pages
+///     RefreshCache,
+/// }
+///
+/// impl CustomCommand<Scraper> for ScraperCmd {
+///     async fn run(self, mut context: CliContext<'_, Scraper>) -> anyhow::Result<()> {
+///         match self {
+///             ScraperCmd::RefreshCache => context.external().clear_cache().await,
+///         }
+///     }
+/// }
+///
+/// peacher_sdk::cli::cli_with_commands::<_, ScraperCmd>(Scraper::new(), Default::default()).await
+/// ```
+pub trait CustomCommand<E: ExternalClient>: Subcommand {
+    fn run(self, context: CliContext<'_, E>) -> impl Future<Output = Result<()>>;
+}
+
+/// No custom commands, only the built-in ones
+#[derive(Subcommand, Debug, Clone, Copy)]
+pub enum NoCommands {}
+
+impl<E: ExternalClient> CustomCommand<E> for NoCommands {
+    async fn run(self, _: CliContext<'_, E>) -> Result<()> {
+        match self {}
+    }
+}
+
+/// What a custom command has to work with
+pub struct CliContext<'p, E> {
+    /// The config as loaded from disk, without command line or environment overrides
+    pub config: Config,
+    /// The profile in use
+    pub profile: String,
+    pub sync: ApiSync<'p, E, PeacherClient>,
+}
+
+impl<'p, E: ExternalClient> CliContext<'p, E> {
+    /// The client for the Peacher API, with every override applied
+    pub fn peacher(&self) -> &'p PeacherClient {
+        self.sync.peacher()
+    }
+
+    pub fn external(&self) -> &E {
+        self.sync.external()
+    }
+}
+
+#[cfg(test)]
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+enum ScraperCmd {
+    RefreshCache,
+    Scrape { bill: String },
+}
+
+#[test]
+fn custom_commands_parse_next_to_built_ins() {
+    use crate::cli::{Args, resource::Resource};
+    use clap::{CommandFactory, Parser};
+
+    Args::<ScraperCmd>::command().debug_assert();
+
+    let args = Args::<ScraperCmd>::try_parse_from(["peacher", "scrape", "HB 12"]).unwrap();
+    assert!(matches!(
+        args.resource,
+        Resource::Custom(ScraperCmd::Scrape { bill }) if bill == "HB 12"
+    ));
+
+    let args =
+        Args::<ScraperCmd>::try_parse_from(["peacher", "-p", "staging", "refresh-cache"]).unwrap();
+    assert_eq!(args.profile.as_deref(), Some("staging"));
+    assert!(matches!(
+        args.resource,
+        Resource::Custom(ScraperCmd::RefreshCache)
+    ));
+
+    let args = Args::<ScraperCmd>::try_parse_from(["peacher", "members", "show", "@jo"]).unwrap();
+    assert!(matches!(args.resource, Resource::Members { .. }));
+}
@@ -1,3 +1,6 @@
+mod output;
+pub use output::*;
+
 mod utils;
 pub use utils::*;
 
@@ -10,14 +13,16 @@ use tabled::{
     settings::{Alignment, Margin, Modify, Width, style::Style as TableStyle},
 };
 
+use crate::cli::resource::{Config, DaemonStatus, StageRun};
 use crate::prelude::*;
 use crate::sync::{
     JurisdictionAndChambersSyncResult, LegislationSyncResult, MembersSyncResult,
     SessionsSyncResult, VotesSyncResult,
 };
+use serde::Serialize;
 
 pub trait AsTable {
-    type TableRow<'a>: Tabled
+    type TableRow<'a>: Tabled + Serialize
     where
         Self: 'a;
     const NAME: &str;
@@ -26,23 +31,72 @@ pub trait AsTable {
         0
     }
     fn print(&self) {
-        let rows = self.to_table_row();
+        print_table(self);
+    }
+}
 
-        let spacing = " ".repeat(self.nest());
+fn print_table<T: AsTable + ?Sized>(value: &T) {
+    let rows = value.to_table_row();
+    if output() != OutputFormat::Table {
+        return print_rows(rows);
+    }
 
-        if rows.len() == 0 {
-            print!("{}{}", spacing, dim(format!("No {} found", Self::NAME)));
-        }
+    let spacing = " ".repeat(value.nest());
+
+    if rows.len() == 0 {
+        print!("{}{}", spacing, dim(format!("No {} found", T::NAME)));
+    }
+
+    let mut table = Table::new(rows);
+    table
+        .with(TableStyle::rounded())
+        .with(Margin::new(value.nest(), 0, 0, 0));
+    println!("{}", table);
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() > max {
+        format!("{}...", text.chars().take(max - 3).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
 
-        let mut table = Table::new(rows);
-        table
-            .with(TableStyle::rounded())
-            .with(Margin::new(self.nest(), 0, 0, 0));
-        println!("{}", table);
+fn external_id(external_id: Option<&ExternalId>) -> String {
+    external_id.map(|e| e.val_str()).unwrap_or("-").to_string()
+}
+
+impl<T> AsTable for Paginated<T>
+where
+    Vec<T>: AsTable,
+{
+    type TableRow<'a>
+        = <Vec<T> as AsTable>::TableRow<'a>
+    where
+        Self: 'a;
+    const NAME: &str = <Vec<T> as AsTable>::NAME;
+    fn to_table_row<'a>(&'a self) -> impl ExactSizeIterator<Item = Self::TableRow<'a>> {
+        self.data.to_table_row()
+    }
+    fn print(&self) {
+        print_table(self);
+        if output() != OutputFormat::Table {
+            return;
+        }
+        println!(
+            "{}",
+            dim(format!(
+                "Page {} of {} ({} {})",
+                self.page + 1,
+                self.num_pages.max(1),
+                self.num_items,
+                Self::NAME
+            ))
+        );
     }
 }
 
-#[derive(Tabled)]
+#[derive(Tabled, Serialize)]
 pub struct SessionRow {
     #[tabled(rename = "ID")]
     pub id: i32,
@@ -124,99 +178,103 @@ impl AsTable for Vec<SessionView> {
 
 impl SessionView {
     pub fn print(&self) {
-        let current_marker = if self.current {
-            format!(" {}", green("● Current"))
-        } else {
-            String::new()
-        };
-
-        let ext_id = self
-            .external_id
-            .as_ref()
-            .map(|e| e.val_str())
-            .unwrap_or("-");
+        print_value(self, || {
+            let current_marker = if self.current {
+                format!(" {}", green("● Current"))
+            } else {
+                String::new()
+            };
 
-        println!(
-            "{}{} {} {}",
-            bold(&self.name),
-            current_marker,
-            dim(&format!("(ID: {})", self.id)),
-            cyan(&format!("[{}]", ext_id))
-        );
+            let ext_id = self
+                .external_id
+                .as_ref()
+                .map(|e| e.val_str())
+                .unwrap_or("-");
+
+            println!(
+                "{}{} {} {}",
+                bold(&self.name),
+                current_marker,
+                dim(&format!("(ID: {})", self.id)),
+                cyan(&format!("[{}]", ext_id))
+            );
 
-        println!("  {} {}", dim("Jurisdiction:"), &self.jurisdiction_id);
+            println!("  {} {}", dim("Jurisdiction:"), &self.jurisdiction_id);
 
-        let starts = self
-            .starts_at
-            .map(|d| d.to_string())
-            .unwrap_or_else(|| "-".to_string());
-        let ends = self
-            .ends_at
-            .map(|d| d.to_string())
-            .unwrap_or_else(|| "-".to_string());
-        println!("  {} {} to {}", dim("Dates:"), starts, ends);
+            let starts = self
+                .starts_at
+                .map(|d| d.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            let ends = self
+                .ends_at
+                .map(|d| d.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            println!("  {} {} to {}", dim("Dates:"), starts, ends);
+        });
     }
 }
 
 impl ListSessionView {
     pub fn print(&self) {
-        let current_marker = if self.current {
-            format!(" {}", green("● Current"))
-        } else {
-            String::new()
-        };
-
-        let ext_id = self
-            .external_id
-            .as_ref()
-            .map(|e| e.val_str())
-            .unwrap_or("-");
+        print_value(self, || {
+            let current_marker = if self.current {
+                format!(" {}", green("● Current"))
+            } else {
+                String::new()
+            };
 
-        println!(
-            "{}{} {} {}",
-            bold(&self.name),
-            current_marker,
-            dim(&format!("(ID: {})", self.id)),
-            cyan(&format!("[{}]", ext_id))
-        );
+            let ext_id = self
+                .external_id
+                .as_ref()
+                .map(|e| e.val_str())
+                .unwrap_or("-");
 
-        println!("  {} {}", dim("Jurisdiction:"), &self.jurisdiction.name);
-
-        let starts = self
-            .starts_at
-            .map(|d| d.to_string())
-            .unwrap_or_else(|| "-".to_string());
-        let ends = self
-            .ends_at
-            .map(|d| d.to_string())
-            .unwrap_or_else(|| "-".to_string());
-        println!("  {} {} to {}", dim("Dates:"), starts, ends);
-
-        if self.chambers.is_empty() {
-            println!("  {}", dim("No chambers"));
-        } else {
-            println!();
-            for chamber in &self.chambers {
-                let chamber_ext = chamber
-                    .external_id
-                    .as_ref()
-                    .map(|e| e.val_str())
-                    .unwrap_or("-");
+            println!(
+                "{}{} {} {}",
+                bold(&self.name),
+                current_marker,
+                dim(&format!("(ID: {})", self.id)),
+                cyan(&format!("[{}]", ext_id))
+            );
 
-                println!(
-                    "  {} {} {}",
-                    bold(&chamber.name),
-                    dim(&format!("(ID: {})", chamber.id)),
-                    cyan(&format!("[{}]", chamber_ext))
-                );
+            println!("  {} {}", dim("Jurisdiction:"), &self.jurisdiction.name);
+
+            let starts = self
+                .starts_at
+                .map(|d| d.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            let ends = self
+                .ends_at
+                .map(|d| d.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            println!("  {} {} to {}", dim("Dates:"), starts, ends);
 
+            if self.chambers.is_empty() {
+                println!("  {}", dim("No chambers"));
+            } else {
                 println!();
+                for chamber in &self.chambers {
+                    let chamber_ext = chamber
+                        .external_id
+                        .as_ref()
+                        .map(|e| e.val_str())
+                        .unwrap_or("-");
+
+                    println!(
+                        "  {} {} {}",
+                        bold(&chamber.name),
+                        dim(&format!("(ID: {})", chamber.id)),
+                        cyan(&format!("[{}]", chamber_ext))
+                    );
+
+                    println!();
+                }
             }
-        }
+        });
     }
 }
 
-#[derive(Tabled)]
+#[derive(Tabled, Serialize)]
 pub struct ChamberRow {
     #[tabled(rename = "ID")]
     pub id: i32,
@@ -249,7 +307,7 @@ impl AsTable for Vec<GetChamberView> {
     }
 }
 
-#[derive(Tabled)]
+#[derive(Tabled, Serialize)]
 pub struct JurisdictionRow {
     #[tabled(rename = "ID")]
     pub id: i32,
@@ -261,7 +319,7 @@ pub struct JurisdictionRow {
     pub chamber_count: usize,
 }
 
-#[derive(Tabled)]
+#[derive(Tabled, Serialize)]
 pub struct MemberRow {
     #[tabled(rename = "ID")]
     pub id: i32,
@@ -273,7 +331,7 @@ pub struct MemberRow {
     pub party: String,
 }
 
-#[derive(Tabled)]
+#[derive(Tabled, Serialize)]
 pub struct LegislationRow {
     #[tabled(rename = "ID")]
     pub id: i32,
@@ -316,6 +374,24 @@ impl AsTable for Vec<LegislationView> {
     }
 }
 
+impl AsTable for Vec<DetailedLegislationView> {
+    type TableRow<'a>
+        = LegislationRow
+    where
+        Self: 'a;
+    const NAME: &str = "legislation";
+    fn to_table_row<'a>(&'a self) -> impl ExactSizeIterator<Item = Self::TableRow<'a>> {
+        self.iter().map(|l| LegislationRow {
+            id: l.id,
+            name_id: l.name_id.clone(),
+            title: truncate(&l.title, 50),
+            legislation_type: format!("{:?}", l.legislation_type),
+            status: l.status_text.clone(),
+            external_id: external_id(l.external_id.as_ref()),
+        })
+    }
+}
+
 impl AsTable for Vec<Arc<MemberWithPartyView>> {
     type TableRow<'a>
         = MemberRow
@@ -403,6 +479,10 @@ impl AsTable for Vec<GetJurisdictionView> {
     }
 
     fn print(&self) {
+        if output() != OutputFormat::Table {
+            return print_table(self);
+        }
+
         if self.is_empty() {
             println!("{}", dim("No jurisdictions found"));
             return;
@@ -430,146 +510,650 @@ impl AsTable for Vec<GetJurisdictionView> {
 
 impl JurisdictionAndChambersSyncResult {
     pub fn print(&self) {
-        println!();
-        if self.jurisdiction_created {
-            println!(
-                "{} Created jurisdiction {} {}",
-                green("✓"),
-                bold(&self.jurisdiction_name),
-                dim(&format!("(ID: {})", self.jurisdiction_id))
-            );
-        } else {
-            println!(
-                "{} Jurisdiction {} already exists {}",
-                green("✓"),
-                bold(&self.jurisdiction_name),
-                dim(&format!("(ID: {})", self.jurisdiction_id))
-            );
-        }
-
-        if !self.chambers_created.is_empty() {
+        print_value(self, || {
             println!();
-            println!(
-                "{} Created {} chamber(s):",
-                green("✓"),
-                self.chambers_created.len()
-            );
-            self.chambers_created.print();
-        }
+            if self.jurisdiction_created {
+                println!(
+                    "{} Created jurisdiction {} {}",
+                    green("✓"),
+                    bold(&self.jurisdiction_name),
+                    dim(&format!("(ID: {})", self.jurisdiction_id))
+                );
+            } else {
+                println!(
+                    "{} Jurisdiction {} already exists {}",
+                    green("✓"),
+                    bold(&self.jurisdiction_name),
+                    dim(&format!("(ID: {})", self.jurisdiction_id))
+                );
+            }
 
-        if !self.chambers_updated.is_empty() {
-            println!();
-            println!(
-                "{} {} existing chamber(s)",
-                dim("●"),
-                self.chambers_updated.len()
-            );
-        }
+            if !self.chambers_created.is_empty() {
+                println!();
+                println!(
+                    "{} Created {} chamber(s):",
+                    green("✓"),
+                    self.chambers_created.len()
+                );
+                self.chambers_created.print();
+            }
 
-        if self.chambers_created.is_empty() && self.chambers_updated.is_empty() {
-            println!("{}", dim("No chamber changes"));
-        }
+            if !self.chambers_updated.is_empty() {
+                println!();
+                println!(
+                    "{} {} existing chamber(s)",
+                    dim("●"),
+                    self.chambers_updated.len()
+                );
+            }
+
+            if self.chambers_created.is_empty() && self.chambers_updated.is_empty() {
+                println!("{}", dim("No chamber changes"));
+            }
+        });
     }
 }
 
 impl SessionsSyncResult {
     pub fn print(&self) {
-        println!();
-        println!("{} Sessions synced!", green("✓"));
-
-        if !self.created.is_empty() {
+        print_value(self, || {
             println!();
-            println!("Created {} session(s):", self.created.len());
-            self.created.print();
-        }
+            println!("{} Sessions synced!", green("✓"));
 
-        if !self.updated.is_empty() {
-            println!();
-            println!("Existing {} session(s):", self.updated.len());
-            self.updated.print();
-        }
+            if !self.created.is_empty() {
+                println!();
+                println!("Created {} session(s):", self.created.len());
+                self.created.print();
+            }
 
-        if self.created.is_empty() && self.updated.is_empty() {
-            println!("{}", dim("No changes."));
-        }
+            if !self.updated.is_empty() {
+                println!();
+                println!("Existing {} session(s):", self.updated.len());
+                self.updated.print();
+            }
+
+            if self.created.is_empty() && self.updated.is_empty() {
+                println!("{}", dim("No changes."));
+            }
+        });
     }
 }
 
 impl MembersSyncResult {
     pub fn print(&self) {
-        println!();
-        println!("{} Members synced!", green("✓"));
-
-        if !self.created.is_empty() {
+        print_value(self, || {
             println!();
-            println!("Created {} member(s):", self.created.len());
-            self.created.print();
-        }
+            println!("{} Members synced!", green("✓"));
 
-        if !self.updated.is_empty() {
-            println!();
-            println!("Existing {} member(s):", self.updated.len());
-            self.updated.print();
-        }
+            if !self.created.is_empty() {
+                println!();
+                println!("Created {} member(s):", self.created.len());
+                self.created.print();
+            }
 
-        if self.created.is_empty() && self.updated.is_empty() {
-            println!("{}", dim("No changes."));
-        }
+            if !self.updated.is_empty() {
+                println!();
+                println!("Existing {} member(s):", self.updated.len());
+                self.updated.print();
+            }
+
+            if self.created.is_empty() && self.updated.is_empty() {
+                println!("{}", dim("No changes."));
+            }
+        });
     }
 }
 
 impl LegislationSyncResult {
     pub fn print(&self) {
-        println!();
-        println!("{} Legislation synced!", green("✓"));
+        print_value(self, || {
+            println!();
+            println!("{} Legislation synced!", green("✓"));
 
-        if self.stopped_early {
-            println!("{}", dim("(Stopped early - reached known items)"));
-        }
+            if self.stopped_early {
+                println!("{}", dim("(Stopped early - reached known items)"));
+            }
+
+            if !self.created.is_empty() {
+                println!();
+                println!("Created {} legislation(s):", self.created.len());
+                self.created.print();
+            }
+
+            if !self.updated.is_empty() {
+                println!();
+                println!("Existing {} legislation(s):", self.updated.len());
+                self.updated.print();
+            }
+
+            if self.created.is_empty() && self.updated.is_empty() {
+                println!("{}", dim("No changes."));
+            }
+        });
+    }
+}
 
-        if !self.created.is_empty() {
+impl VotesSyncResult {
+    pub fn print(&self) {
+        print_value(self, || {
             println!();
-            println!("Created {} legislation(s):", self.created.len());
-            self.created.print();
-        }
+            println!("{} Votes synced!", green("✓"));
+
+            if !self.created.is_empty() {
+                println!();
+                println!(
+                    "Created {} vote(s): {}",
+                    self.created.len(),
+                    dim(&format!("{:?}", self.created))
+                );
+            }
+
+            if !self.updated.is_empty() {
+                println!();
+                println!(
+                    "Existing {} vote(s): {}",
+                    self.updated.len(),
+                    dim(&format!("{:?}", self.updated))
+                );
+            }
+
+            if self.created.is_empty() && self.updated.is_empty() {
+                println!("{}", dim("No changes."));
+            }
+        });
+    }
+}
+
+impl GetMemberDetailsResponse {
+    pub fn print(&self) {
+        print_value(self, || {
+            println!(
+                "{} {} {}",
+                bold(&self.display_name),
+                dim(&format!("(ID: {})", self.id)),
+                cyan(&format!("[{}]", external_id(self.external_id.as_ref())))
+            );
+
+            println!("  {} @{}", dim("Handle:"), self.handle);
+            if let Some(full_name) = &self.full_name {
+                println!("  {} {}", dim("Full name:"), full_name);
+            }
+            println!(
+                "  {} {}",
+                dim("Party:"),
+                self.party
+                    .as_ref()
+                    .map(|p| p.name.as_str())
+                    .unwrap_or("Unaffiliated")
+            );
+            if let Some(url) = &self.external_url {
+                println!("  {} {}", dim("Source:"), url);
+            }
+            if let Some(ban) = &self.ban {
+                println!(
+                    "  {} {} ({})",
+                    yellow("Banned:"),
+                    ban.ban_reason,
+                    ban.ban_date
+                );
+            }
+            if !self.bio.is_empty() {
+                println!();
+                println!("  {}", self.bio);
+            }
+        });
+    }
+}
+
+#[derive(Tabled, Serialize)]
+pub struct SponsorRow {
+    #[tabled(rename = "ID")]
+    pub id: i32,
+    #[tabled(rename = "Name")]
+    pub display_name: String,
+    #[tabled(rename = "Party")]
+    pub party: String,
+    #[tabled(rename = "Sponsorship")]
+    pub sponsor_type: SponsorshipType,
+}
+
+impl AsTable for Vec<SponsorInfo> {
+    type TableRow<'a>
+        = SponsorRow
+    where
+        Self: 'a;
+    const NAME: &str = "sponsors";
+    fn to_table_row<'a>(&'a self) -> impl ExactSizeIterator<Item = Self::TableRow<'a>> {
+        self.iter().map(|s| SponsorRow {
+            id: s.member.id,
+            display_name: s.member.display_name.clone(),
+            party: s
+                .member
+                .party
+                .as_ref()
+                .map(|p| p.name.as_str())
+                .unwrap_or("Unaffiliated")
+                .to_string(),
+            sponsor_type: s.sponsor_type,
+        })
+    }
+    fn nest(&self) -> usize {
+        2
+    }
+}
+
+impl LegislationDetailsResponse {
+    pub fn print(&self) {
+        print_value(self, || {
+            println!(
+                "{} {} {} {}",
+                bold(&self.name_id),
+                &self.title,
+                dim(&format!("(ID: {})", self.id)),
+                cyan(&format!("[{}]", external_id(self.external_id.as_ref())))
+            );
+
+            println!("  {} {:?}", dim("Type:"), self.legislation_type);
+            println!(
+                "  {} {} {}",
+                dim("Status:"),
+                self.status_text,
+                dim(&format!("(updated {})", self.status_updated_at))
+            );
+            if let Some(session) = &self.session {
+                println!("  {} {}", dim("Session:"), session.name);
+            }
+            if let Some(chamber) = &self.chamber {
+                println!("  {} {}", dim("Chamber:"), chamber.name);
+            }
+            if let Some(url) = &self.external_url {
+                println!("  {} {}", dim("Source:"), url);
+            }
 
-        if !self.updated.is_empty() {
             println!();
-            println!("Existing {} legislation(s):", self.updated.len());
-            self.updated.print();
-        }
+            self.sponsors.print();
+        });
+    }
+}
 
-        if self.created.is_empty() && self.updated.is_empty() {
-            println!("{}", dim("No changes."));
-        }
+#[derive(Tabled, Serialize)]
+pub struct VoteRow {
+    #[tabled(rename = "ID")]
+    pub id: i32,
+    #[tabled(rename = "Name")]
+    pub name: String,
+    #[tabled(rename = "Date")]
+    pub occurred_at: String,
+    #[tabled(rename = "Chamber")]
+    pub chamber: String,
+    #[tabled(rename = "Result")]
+    pub result: String,
+    #[tabled(rename = "Yes")]
+    pub yes: i32,
+    #[tabled(rename = "No")]
+    pub no: i32,
+    #[tabled(rename = "Absent")]
+    pub absent: i32,
+    #[tabled(rename = "Not voting")]
+    pub not_voting: i32,
+}
+
+fn vote_result(succeeded: bool) -> String {
+    if succeeded {
+        green("Passed")
+    } else {
+        yellow("Failed")
     }
 }
 
-impl VotesSyncResult {
+impl AsTable for Vec<LegislationVoteView> {
+    type TableRow<'a>
+        = VoteRow
+    where
+        Self: 'a;
+    const NAME: &str = "votes";
+    fn to_table_row<'a>(&'a self) -> impl ExactSizeIterator<Item = Self::TableRow<'a>> {
+        self.iter().map(|v| VoteRow {
+            id: v.id,
+            name: truncate(&v.name, 50),
+            occurred_at: v
+                .occurred_at
+                .map(|d| d.date_naive().to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            chamber: v.chamber.name.clone(),
+            result: vote_result(v.succeeded),
+            yes: v.yes_count,
+            no: v.no_count,
+            absent: v.absent_count,
+            not_voting: v.not_voting_count,
+        })
+    }
+}
+
+impl LegislationVotesResponse {
+    pub fn print(&self) {
+        print_value(self, || {
+            println!(
+                "{} {} {}",
+                bold(&self.legislation.name_id),
+                &self.legislation.title,
+                dim(&format!("(ID: {})", self.legislation.id))
+            );
+            self.votes.print();
+        });
+    }
+}
+
+#[derive(Tabled, Serialize)]
+pub struct MemberVoteRow {
+    #[tabled(rename = "ID")]
+    pub id: i32,
+    #[tabled(rename = "Name")]
+    pub display_name: String,
+    #[tabled(rename = "Party")]
+    pub party: String,
+    #[tabled(rename = "Vote")]
+    pub vote: Vote,
+}
+
+impl AsTable for Vec<MemberVoteValue> {
+    type TableRow<'a>
+        = MemberVoteRow
+    where
+        Self: 'a;
+    const NAME: &str = "member votes";
+    fn to_table_row<'a>(&'a self) -> impl ExactSizeIterator<Item = Self::TableRow<'a>> {
+        self.iter().map(|v| MemberVoteRow {
+            id: v.member.id,
+            display_name: v.member.display_name.clone(),
+            party: v
+                .member
+                .party
+                .as_ref()
+                .map(|p| p.name.as_str())
+                .unwrap_or("Unaffiliated")
+                .to_string(),
+            vote: v.vote,
+        })
+    }
+    fn nest(&self) -> usize {
+        2
+    }
+}
+
+impl LegislationVoteDetailsResponse {
     pub fn print(&self) {
-        println!();
-        println!("{} Votes synced!", green("✓"));
+        print_value(self, || {
+            println!(
+                "{} {} {}",
+                bold(&self.vote_name),
+                vote_result(self.succeeded),
+                dim(&format!("(ID: {})", self.vote_id))
+            );
+            println!(
+                "  {} {} {}",
+                dim("Legislation:"),
+                self.legislation.name_id,
+                self.legislation.title
+            );
+            println!("  {} {}", dim("Chamber:"), self.chamber.name);
+            if let Some(occurred_at) = self.occurred_at {
+                println!("  {} {}", dim("Date:"), occurred_at);
+            }
+            println!(
+                "  {} {} yes, {} no, {} absent, {} not voting of {}",
+                dim("Tally:"),
+                self.summary.yes_count,
+                self.summary.no_count,
+                self.summary.absent_count,
+                self.summary.not_voting_count,
+                self.summary.total
+            );
 
-        if !self.created.is_empty() {
             println!();
+            self.member_votes.print();
+        });
+    }
+}
+
+#[derive(Tabled, Serialize)]
+pub struct MapRow {
+    #[tabled(rename = "ID")]
+    pub id: i32,
+    #[tabled(rename = "Name")]
+    pub name: String,
+    #[tabled(rename = "Updated")]
+    pub updated_at: String,
+    #[tabled(rename = "External ID")]
+    pub external_id: String,
+}
+
+impl AsTable for Vec<MapView> {
+    type TableRow<'a>
+        = MapRow
+    where
+        Self: 'a;
+    const NAME: &str = "maps";
+    fn to_table_row<'a>(&'a self) -> impl ExactSizeIterator<Item = Self::TableRow<'a>> {
+        self.iter().map(|m| MapRow {
+            id: m.id,
+            name: m.name.clone(),
+            updated_at: m.updated_at.date_naive().to_string(),
+            external_id: external_id(m.external_id.as_ref()),
+        })
+    }
+}
+
+#[derive(Tabled, Serialize)]
+pub struct DistrictRow {
+    #[tabled(rename = "ID")]
+    pub id: i32,
+    #[tabled(rename = "Name")]
+    pub name: String,
+    #[tabled(rename = "Center")]
+    pub center: String,
+    #[tabled(rename = "External ID")]
+    pub external_id: String,
+}
+
+impl AsTable for Vec<DistrictView> {
+    type TableRow<'a>
+        = DistrictRow
+    where
+        Self: 'a;
+    const NAME: &str = "districts";
+    fn to_table_row<'a>(&'a self) -> impl ExactSizeIterator<Item = Self::TableRow<'a>> {
+        self.iter().map(|d| DistrictRow {
+            id: d.id,
+            name: d.name.clone(),
+            center: format!("{:.4}, {:.4}", d.lat, d.lon),
+            external_id: external_id(d.external_id.as_ref()),
+        })
+    }
+    fn nest(&self) -> usize {
+        2
+    }
+}
+
+impl MapWithDistrictsView {
+    pub fn print(&self) {
+        print_value(self, || {
             println!(
-                "Created {} vote(s): {}",
-                self.created.len(),
-                dim(&format!("{:?}", self.created))
+                "{} {} {}",
+                bold(&self.name),
+                dim(&format!("(ID: {})", self.id)),
+                cyan(&format!("[{}]", external_id(self.external_id.as_ref())))
             );
-        }
+            println!(
+                "  {} {} to {}",
+                dim("Dates:"),
+                self.created_at,
+                self.updated_at
+            );
+            if let Some(url) = &self.external_url {
+                println!("  {} {}", dim("Source:"), url);
+            }
+
+            println!();
+            println!("  {} district(s):", self.districts.len());
+            self.districts.print();
+        });
+    }
+}
 
-        if !self.updated.is_empty() {
+impl MapUploadResponse {
+    pub fn print(&self) {
+        print_value(self, || {
             println!();
             println!(
-                "Existing {} vote(s): {}",
-                self.updated.len(),
-                dim(&format!("{:?}", self.updated))
+                "{} Created map {} {} with {} district(s)",
+                green("✓"),
+                bold(&self.name),
+                dim(&format!("(ID: {})", self.map_id)),
+                self.district_count
             );
-        }
+            if !self.message.is_empty() {
+                println!("  {}", dim(&self.message));
+            }
+        });
+    }
+}
 
-        if self.created.is_empty() && self.updated.is_empty() {
-            println!("{}", dim("No changes."));
-        }
+#[cfg(feature = "map_preview")]
+impl MapInspection {
+    pub fn print(&self) {
+        print_value(self, || {
+            println!(
+                "{} with {} district(s)",
+                bold(format!("{:?}", self.format)),
+                self.districts.len()
+            );
+            println!("  {} {}", dim("Fields:"), self.fields.join(", "));
+            println!(
+                "  {} name: {}, external id: {}",
+                dim("Mapping:"),
+                self.mapping.name_field.as_deref().unwrap_or("-"),
+                self.mapping.external_id_field.as_deref().unwrap_or("-")
+            );
+
+            println!();
+            if self.issues.is_empty() {
+                println!("{} Ready to upload", green("✓"));
+            } else {
+                println!("{} {} issue(s):", yellow("●"), self.issues.len());
+                for issue in &self.issues {
+                    println!("  {}", issue);
+                }
+            }
+        });
+    }
+}
+
+#[derive(Tabled, Serialize)]
+pub struct ProfileRow {
+    #[tabled(rename = "Profile")]
+    pub name: String,
+    #[tabled(rename = "Current")]
+    pub current: String,
+    #[tabled(rename = "URL")]
+    pub url: String,
+    #[tabled(rename = "API key")]
+    pub api_key: String,
+}
+
+impl AsTable for Config {
+    type TableRow<'a>
+        = ProfileRow
+    where
+        Self: 'a;
+    const NAME: &str = "profiles";
+    fn to_table_row<'a>(&'a self) -> impl ExactSizeIterator<Item = Self::TableRow<'a>> {
+        self.profiles.iter().map(|(name, options)| ProfileRow {
+            name: name.clone(),
+            current: if *name == self.current_profile {
+                "●".to_string()
+            } else {
+                String::new()
+            },
+            url: options.url.to_string(),
+            api_key: if options.api_key.is_some() {
+                "********".to_string()
+            } else {
+                "<not set>".to_string()
+            },
+        })
+    }
+}
+
+#[derive(Tabled, Serialize)]
+pub struct StageRow {
+    #[tabled(rename = "Stage")]
+    pub stage: String,
+    #[tabled(rename = "Last run")]
+    pub last_run: String,
+    #[tabled(rename = "Result")]
+    pub result: String,
+    #[tabled(rename = "Failures")]
+    pub failures: u32,
+    #[tabled(rename = "Next run")]
+    pub next_run: String,
+}
+
+impl AsTable for DaemonStatus {
+    type TableRow<'a>
+        = StageRow
+    where
+        Self: 'a;
+    const NAME: &str = "stages";
+    fn to_table_row<'a>(&'a self) -> impl ExactSizeIterator<Item = Self::TableRow<'a>> {
+        self.stages.iter().map(|(stage, status)| StageRow {
+            stage: stage.to_string(),
+            last_run: status
+                .last_run
+                .as_ref()
+                .map(|run| run.finished_at.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            result: status
+                .last_run
+                .as_ref()
+                .map(|run| {
+                    if run.succeeded {
+                        run.summary.clone()
+                    } else {
+                        yellow(&run.summary)
+                    }
+                })
+                .unwrap_or_else(|| "-".to_string()),
+            failures: status.failures,
+            next_run: status
+                .next_run_at
+                .map(|at| at.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        })
+    }
+}
+
+impl StageRun {
+    pub fn print(&self) {
+        print_value(self, || {
+            let seconds = (self.finished_at - self.started_at).num_seconds();
+            if self.succeeded {
+                println!(
+                    "{} {} {} {}",
+                    green("✓"),
+                    bold(self.stage),
+                    self.summary,
+                    dim(format!("({seconds}s)"))
+                );
+            } else {
+                println!(
+                    "{} {} failed: {} {}",
+                    Style::new().red().apply_to("✗"),
+                    bold(self.stage),
+                    self.summary,
+                    dim(format!("({seconds}s)"))
+                );
+            }
+        });
     }
 }
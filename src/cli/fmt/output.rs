@@ -0,0 +1,167 @@
+use std::{io::IsTerminal, sync::OnceLock};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+use tabled::Tabled;
+
+/// How commands write their results to stdout
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Colored tables for reading in a terminal
+    #[default]
+    Table,
+    /// A single JSON document
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    /// Comma separated values with a header line
+    Csv,
+}
+
+static OUTPUT: OnceLock<OutputFormat> = OnceLock::new();
+
+/// Sets the output format for the rest of the process. Only the first call has any effect.
+///
+/// Colors and spinners are turned off unless tables are written to a terminal.
+pub fn set_output(format: OutputFormat) {
+    let _ = OUTPUT.set(format);
+    if !is_interactive() {
+        console::set_colors_enabled(false);
+    }
+}
+
+pub fn output() -> OutputFormat {
+    OUTPUT.get().copied().unwrap_or_default()
+}
+
+/// Whether output is meant for a person at a terminal, rather than another program
+pub fn is_interactive() -> bool {
+    output() == OutputFormat::Table && std::io::stdout().is_terminal()
+}
+
+/// Prints rows in the current output format. Tables are left to the caller.
+pub fn print_rows<R: Tabled + Serialize>(rows: impl IntoIterator<Item = R>) {
+    match output() {
+        OutputFormat::Table => {}
+        OutputFormat::Json => {
+            let rows: Vec<R> = rows.into_iter().collect();
+            println!("{}", to_json(&rows, true));
+        }
+        OutputFormat::Ndjson => {
+            for row in rows {
+                println!("{}", to_json(&row, false));
+            }
+        }
+        OutputFormat::Csv => {
+            println!("{}", csv_record(R::headers()));
+            for row in rows {
+                println!("{}", csv_record(row.fields()));
+            }
+        }
+    }
+}
+
+/// Prints `value` in the current output format, or calls `table` for the table format.
+///
+/// As CSV, objects become a single record and arrays of objects one record each. Nested
+/// values are written as JSON.
+pub fn print_value<T: Serialize + ?Sized>(value: &T, table: impl FnOnce()) {
+    match output() {
+        OutputFormat::Table => table(),
+        OutputFormat::Json => println!("{}", to_json(value, true)),
+        OutputFormat::Ndjson => match serde_json::to_value(value) {
+            Ok(Value::Array(values)) => {
+                for value in values {
+                    println!("{}", to_json(&value, false));
+                }
+            }
+            _ => println!("{}", to_json(value, false)),
+        },
+        OutputFormat::Csv => print!(
+            "{}",
+            to_csv(serde_json::to_value(value).unwrap_or_default())
+        ),
+    }
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T, pretty: bool) -> String {
+    let json = if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    };
+    json.expect("CLI output serializes to JSON")
+}
+
+fn to_csv(value: Value) -> String {
+    let records = match value {
+        Value::Array(values) => values,
+        value => vec![value],
+    };
+
+    let mut headers: Vec<String> = Vec::new();
+    for record in &records {
+        if let Value::Object(fields) = record {
+            for key in fields.keys() {
+                if !headers.contains(key) {
+                    headers.push(key.clone());
+                }
+            }
+        }
+    }
+
+    if headers.is_empty() {
+        return records
+            .iter()
+            .map(|record| format!("{}\n", csv_record([csv_cell(Some(record))])))
+            .collect();
+    }
+
+    let mut csv = format!("{}\n", csv_record(&headers));
+    for record in &records {
+        let cells = headers.iter().map(|header| csv_cell(record.get(header)));
+        csv.push_str(&csv_record(cells));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(value) => value.to_string(),
+    }
+}
+
+fn csv_record<S: AsRef<str>>(fields: impl IntoIterator<Item = S>) -> String {
+    fields
+        .into_iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[test]
+fn csv_quotes_and_flattens() {
+    let value = serde_json::json!([
+        { "id": 1, "name": "Smith, Jo", "tags": ["a"] },
+        { "id": 2, "name": "say \"hi\"", "extra": null },
+    ]);
+    assert_eq!(
+        to_csv(value),
+        "id,name,tags,extra\n1,\"Smith, Jo\",\"[\"\"a\"\"]\",\n2,\"say \"\"hi\"\"\",,\n"
+    );
+    assert_eq!(
+        to_csv(serde_json::json!({ "map_id": 3, "name": "Wards" })),
+        "map_id,name\n3,Wards\n"
+    );
+}
@@ -38,7 +38,11 @@ where
     Style::new().bold().apply_to(text).to_string()
 }
 
+/// A spinner on stderr, hidden when output is not meant for a terminal
 pub fn spinner(message: impl Into<Cow<'static, str>>) -> ProgressBar {
+    if !super::is_interactive() {
+        return ProgressBar::hidden();
+    }
     let pb = ProgressBar::new_spinner();
     pb.set_style(
         ProgressStyle::default_spinner()
@@ -1,26 +1,32 @@
 #![allow(unused)]
 
 pub mod args;
+pub mod custom;
 pub mod fmt;
 pub mod resource;
 
+pub use custom::{CliContext, CustomCommand, NoCommands};
+
 use std::path::PathBuf;
 
 use crate::{
-    cli::resource::{Config, ConfigOptions, Resource},
+    cli::{
+        fmt::OutputFormat,
+        resource::{Config, ConfigOptions, DaemonFiles, Resource},
+    },
     client::PeacherClient,
     sync::{ApiSync, ExternalClient},
 };
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use url::Url;
 
 /// The Peacher SDK CLI tool for external clients
 #[derive(Parser, Debug, Clone)]
 #[command(version, about = "Peacher SDK CLI - Sync legislative data", long_about = None)]
-pub struct Args {
+pub struct Args<C: Subcommand = NoCommands> {
     #[command(subcommand)]
-    pub resource: Resource,
+    pub resource: Resource<C>,
 
     /// Override the default path of the config
     #[arg(short, long, global = true)]
@@ -33,6 +39,15 @@ pub struct Args {
     /// Override the API base URL (default: from config or https://api.peacher.app)
     #[arg(short, long, global = true)]
     pub url: Option<String>,
+
+    /// Use this profile from your config instead of the current one
+    #[arg(short, long, global = true)]
+    pub profile: Option<String>,
+
+    /// How results are written to stdout. Colors and spinners are only shown for tables
+    /// written to a terminal
+    #[arg(short, long, global = true, value_enum, default_value_t)]
+    pub output: OutputFormat,
 }
 
 #[derive(Default)]
@@ -45,6 +60,9 @@ pub struct CliOpts {
 
     /// Override the API base URL (default: from config or https://api.peacher.app)
     pub url: Option<String>,
+
+    /// Use this profile from the config instead of the current one
+    pub profile: Option<String>,
 }
 
 pub async fn cli<E: ExternalClient>(client: E) -> Result<()> {
@@ -52,16 +70,29 @@ pub async fn cli<E: ExternalClient>(client: E) -> Result<()> {
 }
 
 pub async fn cli_with_opts<E: ExternalClient>(client: E, options: CliOpts) -> Result<()> {
-    let mut args = Args::parse();
+    cli_with_commands::<E, NoCommands>(client, options).await
+}
+
+/// Runs the CLI with your own subcommands next to the built-in ones. See [`CustomCommand`].
+pub async fn cli_with_commands<E, C>(client: E, options: CliOpts) -> Result<()>
+where
+    E: ExternalClient,
+    C: CustomCommand<E>,
+{
+    let mut args = Args::<C>::parse();
 
     cli_with_opts_and_args(client, options, args).await
 }
 
-pub async fn cli_with_opts_and_args<E: ExternalClient>(
+pub async fn cli_with_opts_and_args<E, C>(
     client: E,
     options: CliOpts,
-    mut args: Args,
-) -> Result<()> {
+    mut args: Args<C>,
+) -> Result<()>
+where
+    E: ExternalClient,
+    C: CustomCommand<E>,
+{
     if args.config.is_none()
         && let Some(config) = options.config
     {
@@ -79,26 +110,35 @@ pub async fn cli_with_opts_and_args<E: ExternalClient>(
         args.url = Some(url);
     }
 
+    if args.profile.is_none()
+        && let Some(profile) = options.profile
+    {
+        args.profile = Some(profile);
+    }
+
+    fmt::set_output(args.output);
+
     if let Resource::Config { cmd } = args.resource {
         let override_config_path = args.config.map(PathBuf::from);
 
-        return cmd.run(override_config_path);
+        return cmd.run(override_config_path, args.profile);
     }
 
-    let mut config = match args.config {
+    let config = match args.config {
         Some(path) => Config::load_from_path(path.into())?,
         None => Config::load()?,
     };
+    let (profile, mut config_options) = config.resolve(args.profile.as_deref())?;
     if let Some(api_key) = args.api_key {
-        config.options.api_key = Some(api_key);
+        config_options.api_key = Some(api_key);
     }
     if let Some(url) = args.url {
         let url = Url::parse(&url)?;
-        config.options.url = url;
+        config_options.url = url;
     }
     let peacher_client = PeacherClient {
-        base: config.options.url,
-        api_key: config.options.api_key,
+        base: config_options.url,
+        api_key: config_options.api_key,
         ..Default::default()
     };
 
@@ -107,6 +147,22 @@ pub async fn cli_with_opts_and_args<E: ExternalClient>(
     match args.resource {
         Resource::Jurisdiction { cmd } => cmd.run(api_sync).await,
         Resource::Sessions { cmd } => cmd.run(api_sync).await,
+        Resource::Members { cmd } => cmd.run(api_sync).await,
+        Resource::Legislation { cmd } => cmd.run(api_sync).await,
+        Resource::Votes { cmd } => cmd.run(api_sync).await,
+        Resource::Maps { cmd } => cmd.run(api_sync).await,
+        Resource::Daemon { cmd } => {
+            cmd.run(api_sync, DaemonFiles::new(&config.path, &profile))
+                .await
+        }
+        Resource::Custom(cmd) => {
+            cmd.run(CliContext {
+                config,
+                profile,
+                sync: api_sync,
+            })
+            .await
+        }
         Resource::Config { cmd } => unreachable!(),
     }
 }
@@ -1,9 +1,10 @@
 use anyhow::{Result, bail};
 use clap::{Subcommand, ValueEnum};
 use std::{
+    collections::BTreeMap,
     error::Error,
     fmt,
-    io::ErrorKind,
+    io::{ErrorKind, Write},
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -12,6 +13,17 @@ use thiserror::Error;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::cli::fmt::{AsTable, print_value};
+
+/// Profile used when none is configured or given
+pub const DEFAULT_PROFILE: &str = "default";
+/// Selects the profile, like `--profile`
+pub const PROFILE_ENV: &str = "PEACHER_PROFILE";
+/// Overrides the api key of the active profile
+pub const API_KEY_ENV: &str = "PEACHER_API_KEY";
+/// Overrides the url of the active profile
+pub const URL_ENV: &str = "PEACHER_URL";
+
 #[derive(ValueEnum, Clone, Debug)]
 #[clap(rename_all = "snake_case")]
 pub enum ConfigKey {
@@ -23,16 +35,24 @@ pub enum ConfigKey {
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum ConfigCmd {
+    /// Shows the settings of the active profile, including environment overrides
     Show,
+    /// Sets a key of the active profile, creating the profile if it does not exist
     Set {
         #[arg(value_enum, help = "Which config key to set")]
         key: ConfigKey,
         value: String,
     },
+    /// Makes a profile the one used when no --profile is given
+    Use { profile: String },
+    /// Lists your profiles
+    List,
+    /// Deletes a profile along with its api key
+    Remove { profile: String },
 }
 
 impl ConfigCmd {
-    pub fn run(self, override_config_path: Option<PathBuf>) -> Result<()> {
+    pub fn run(self, override_config_path: Option<PathBuf>, profile: Option<String>) -> Result<()> {
         let mut config = match override_config_path {
             Some(path) => Config::load_or_default(path.clone())?,
             None => Config::load()?,
@@ -40,54 +60,108 @@ impl ConfigCmd {
 
         match self {
             ConfigCmd::Show => {
-                println!(
-                    "Current configuration({}):\n{}",
-                    config.path.display(),
-                    config.options
-                );
+                let (profile, options) = config.resolve(profile.as_deref())?;
+                let view = ProfileView {
+                    profile: &profile,
+                    options: options.redacted(),
+                };
+                print_value(&view, || {
+                    println!(
+                        "Current configuration({}, profile {}):\n{}",
+                        config.path.display(),
+                        profile,
+                        options
+                    )
+                });
                 Ok(())
             }
             ConfigCmd::Set { key, value } => {
+                let profile = config.active_profile(profile.as_deref());
+                let options = config.profiles.entry(profile.clone()).or_default();
                 match key {
-                    ConfigKey::ApiKey => config.options.api_key = Some(value.to_string()),
-                    ConfigKey::Url => config.options.url = Url::parse(&value)?,
+                    ConfigKey::ApiKey => options.api_key = Some(value.to_string()),
+                    ConfigKey::Url => options.url = Url::parse(&value)?,
                 }
+                let options = options.clone();
 
                 config.save()?;
-                println!(
-                    "Updated and saved to {}:\n{}",
-                    config.path.display(),
-                    config.options
-                );
+                let view = ProfileView {
+                    profile: &profile,
+                    options: options.redacted(),
+                };
+                print_value(&view, || {
+                    println!(
+                        "Updated profile {} and saved to {}:\n{}",
+                        profile,
+                        config.path.display(),
+                        options
+                    )
+                });
 
                 Ok(())
             }
+            ConfigCmd::Use { profile } => {
+                if !config.profiles.contains_key(&profile) && profile != DEFAULT_PROFILE {
+                    bail!(
+                        "Unknown profile {profile}, create it with `config set --profile {profile} url <url>`"
+                    );
+                }
+                config.current_profile = profile;
+                config.save()?;
+                config.print();
+                Ok(())
+            }
+            ConfigCmd::List => {
+                config.print();
+                Ok(())
+            }
+            ConfigCmd::Remove { profile } => {
+                if config.profiles.remove(&profile).is_none() {
+                    bail!("Unknown profile {profile}");
+                }
+                if config.current_profile == profile {
+                    config.current_profile = DEFAULT_PROFILE.to_string();
+                }
+                config.save()?;
+                config.print();
+                Ok(())
+            }
         }
     }
 }
 
+#[derive(Serialize)]
+struct ProfileView<'a> {
+    profile: &'a str,
+    #[serde(flatten)]
+    options: ConfigOptions,
+}
+
 fn default_url() -> Url {
     Url::from_str("https://api.peacher.app").unwrap()
 }
 
+fn credentials_path(config_path: &Path) -> PathBuf {
+    config_path.with_file_name("credentials.toml")
+}
+
 impl Default for Config {
     fn default() -> Self {
         let path = dirs::config_dir()
             .map(|p| p.join("peacher").join("config.toml"))
             .expect("A config directory for your operating system");
-        Config {
-            path,
-            options: ConfigOptions {
-                api_key: None,
-                url: default_url(),
-            },
-        }
+        Config::new(path)
     }
 }
 
+/// CLI configuration stored at ~/.config/peacher/config.toml, with api keys kept apart in
+/// credentials.toml next to it
 pub struct Config {
     pub path: PathBuf,
-    pub options: ConfigOptions,
+    pub credentials_path: PathBuf,
+    /// The profile used when none is given
+    pub current_profile: String,
+    pub profiles: BTreeMap<String, ConfigOptions>,
 }
 
 #[derive(Error, Debug)]
@@ -96,15 +170,72 @@ pub enum ConfigLoadErr {
     NotFound,
     #[error("Failed to deserialize {0}")]
     Deserialize(toml::de::Error),
+    /// Not treated as a missing config, since saving over it would lose every api key
+    #[error("Failed to read the api keys in {}, fix or move it first: {source}", path.display())]
+    Credentials {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
     #[error("Error while loading config from file: {0}")]
     Other(Box<dyn Error + Send + Sync>),
 }
 
+/// The contents of config.toml
+#[derive(Serialize, Deserialize, Default)]
+struct ConfigFile {
+    current_profile: Option<String>,
+    #[serde(default)]
+    profiles: BTreeMap<String, ProfileFile>,
+    /// Single profile configs from before profiles existed
+    #[serde(default, skip_serializing)]
+    api_key: Option<String>,
+    #[serde(default, skip_serializing)]
+    url: Option<Url>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProfileFile {
+    #[serde(default = "default_url")]
+    url: Url,
+}
+
+/// The contents of credentials.toml, by profile
+type CredentialsFile = BTreeMap<String, Credentials>;
+
+#[derive(Serialize, Deserialize)]
+struct Credentials {
+    api_key: Option<String>,
+}
+
+fn read_to_string(path: &Path) -> Result<Option<String>, ConfigLoadErr> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(ConfigLoadErr::Other(err.into())),
+    }
+}
+
+/// Writes a file only the current user can read
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(path)?;
+    // the mode above only applies to new files
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    file.write_all(contents.as_bytes())
+}
+
 impl Config {
     pub fn new(path: PathBuf) -> Self {
         Config {
+            credentials_path: credentials_path(&path),
             path,
-            ..Default::default()
+            current_profile: DEFAULT_PROFILE.to_string(),
+            profiles: BTreeMap::new(),
         }
     }
 
@@ -112,7 +243,8 @@ impl Config {
     ///
     /// # Errors
     ///
-    /// if the load error is not a deserialization or notfound issue
+    /// if the load error is not a deserialization or notfound issue, or the credentials file
+    /// is broken
     pub fn load_or_default(path: PathBuf) -> Result<Self> {
         match Config::load_from_path(path.clone()) {
             Ok(config) => Ok(config),
@@ -123,20 +255,55 @@ impl Config {
         }
     }
 
+    /// Loads the config file and the credentials file next to it. Configs from before
+    /// profiles existed are read as the default profile.
     pub fn load_from_path(path: PathBuf) -> Result<Self, ConfigLoadErr> {
-        let contents = match std::fs::read_to_string(&path) {
-            Ok(contents) => contents,
-            Err(err) => match err.kind() {
-                ErrorKind::NotFound => return Err(ConfigLoadErr::NotFound),
-                _ => return Err(ConfigLoadErr::Other(err.into())),
-            },
+        let contents = read_to_string(&path)?.ok_or(ConfigLoadErr::NotFound)?;
+        let file: ConfigFile = toml::from_str(&contents).map_err(ConfigLoadErr::Deserialize)?;
+
+        let credentials_path = credentials_path(&path);
+        let mut credentials: CredentialsFile = match read_to_string(&credentials_path)? {
+            Some(contents) => {
+                toml::from_str(&contents).map_err(|source| ConfigLoadErr::Credentials {
+                    path: credentials_path.clone(),
+                    source,
+                })?
+            }
+            None => CredentialsFile::new(),
         };
-        let config: ConfigOptions =
-            toml::from_str(&contents).map_err(ConfigLoadErr::Deserialize)?;
+
+        let mut profiles: BTreeMap<_, _> = file
+            .profiles
+            .into_iter()
+            .map(|(name, profile)| {
+                let api_key = credentials.remove(&name).and_then(|c| c.api_key);
+                (
+                    name,
+                    ConfigOptions {
+                        api_key,
+                        url: profile.url,
+                    },
+                )
+            })
+            .collect();
+        if (file.api_key.is_some() || file.url.is_some()) && !profiles.contains_key(DEFAULT_PROFILE)
+        {
+            profiles.insert(
+                DEFAULT_PROFILE.to_string(),
+                ConfigOptions {
+                    api_key: file.api_key,
+                    url: file.url.unwrap_or_else(default_url),
+                },
+            );
+        }
 
         Ok(Self {
             path,
-            options: config,
+            credentials_path,
+            current_profile: file
+                .current_profile
+                .unwrap_or_else(|| DEFAULT_PROFILE.to_string()),
+            profiles,
         })
     }
 
@@ -151,24 +318,84 @@ impl Config {
             Ok(this) => Ok(this),
             Err(e) => match e {
                 ConfigLoadErr::Deserialize(_) | ConfigLoadErr::NotFound => Ok(Self::default()),
+                ConfigLoadErr::Credentials { .. } => Err(e.into()),
                 ConfigLoadErr::Other(e) => bail!(e),
             },
         }
     }
 
+    /// Writes the config, and the api keys to a credentials file only you can read
     pub fn save(&self) -> Result<()> {
         let path = &self.path;
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
-        let contents = toml::to_string_pretty(&self.options)?;
-        std::fs::write(path, contents)?;
+        let file = ConfigFile {
+            current_profile: Some(self.current_profile.clone()),
+            profiles: self
+                .profiles
+                .iter()
+                .map(|(name, options)| {
+                    let profile = ProfileFile {
+                        url: options.url.clone(),
+                    };
+                    (name.clone(), profile)
+                })
+                .collect(),
+            ..Default::default()
+        };
+        std::fs::write(path, toml::to_string_pretty(&file)?)?;
+
+        let credentials: CredentialsFile = self
+            .profiles
+            .iter()
+            .filter(|(_, options)| options.api_key.is_some())
+            .map(|(name, options)| {
+                let credentials = Credentials {
+                    api_key: options.api_key.clone(),
+                };
+                (name.clone(), credentials)
+            })
+            .collect();
+        write_private(
+            &self.credentials_path,
+            &toml::to_string_pretty(&credentials)?,
+        )?;
         Ok(())
     }
+
+    /// The profile to use: the one given, then [`PROFILE_ENV`], then the current profile
+    pub fn active_profile(&self, profile: Option<&str>) -> String {
+        profile
+            .map(str::to_string)
+            .or_else(|| std::env::var(PROFILE_ENV).ok())
+            .unwrap_or_else(|| self.current_profile.clone())
+    }
+
+    /// The options of the active profile, with [`API_KEY_ENV`] and [`URL_ENV`] applied
+    ///
+    /// # Errors
+    /// if the profile does not exist, or the url in the environment is invalid
+    pub fn resolve(&self, profile: Option<&str>) -> Result<(String, ConfigOptions)> {
+        let profile = self.active_profile(profile);
+        let mut options = match self.profiles.get(&profile) {
+            Some(options) => options.clone(),
+            None if profile == DEFAULT_PROFILE => ConfigOptions::default(),
+            None => bail!("Unknown profile {profile}"),
+        };
+
+        if let Ok(api_key) = std::env::var(API_KEY_ENV) {
+            options.api_key = Some(api_key);
+        }
+        if let Ok(url) = std::env::var(URL_ENV) {
+            options.url = Url::parse(&url)?;
+        }
+        Ok((profile, options))
+    }
 }
 
-/// CLI configuration stored at ~/.config/peacher/config.toml
+/// Settings of a single profile
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ConfigOptions {
     /// API key for authentication with Peacher API (required for sync)
@@ -178,6 +405,25 @@ pub struct ConfigOptions {
     pub url: Url,
 }
 
+impl Default for ConfigOptions {
+    fn default() -> Self {
+        Self {
+            api_key: None,
+            url: default_url(),
+        }
+    }
+}
+
+impl ConfigOptions {
+    /// A copy that is safe to print, with the api key masked
+    pub fn redacted(&self) -> Self {
+        Self {
+            api_key: self.api_key.as_ref().map(|_| "********".to_string()),
+            url: self.url.clone(),
+        }
+    }
+}
+
 impl fmt::Display for ConfigOptions {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "  url:           {}", self.url)?;
@@ -192,3 +438,78 @@ impl fmt::Display for ConfigOptions {
         Ok(())
     }
 }
+
+#[test]
+fn keeps_api_keys_out_of_the_config_file() {
+    let dir = std::env::temp_dir().join(format!("peacher-config-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.toml");
+    std::fs::write(
+        &path,
+        "api_key = \"secret\"\nurl = \"http://localhost:8080\"\n",
+    )
+    .unwrap();
+
+    let mut config = Config::load_from_path(path.clone()).unwrap();
+    assert_eq!(config.current_profile, DEFAULT_PROFILE);
+    assert_eq!(
+        config.profiles[DEFAULT_PROFILE].api_key.as_deref(),
+        Some("secret")
+    );
+    config.profiles.insert(
+        "staging".to_string(),
+        ConfigOptions {
+            api_key: Some("staging-secret".to_string()),
+            url: Url::parse("https://staging.peacher.app").unwrap(),
+        },
+    );
+    config.current_profile = "staging".to_string();
+    config.save().unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(!contents.contains("secret"), "{contents}");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = std::fs::metadata(&config.credentials_path).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+    }
+
+    let config = Config::load_from_path(path).unwrap();
+    assert_eq!(config.current_profile, "staging");
+    assert_eq!(
+        config.profiles["staging"].api_key.as_deref(),
+        Some("staging-secret")
+    );
+    assert_eq!(
+        config.profiles[DEFAULT_PROFILE].url.as_str(),
+        "http://localhost:8080/"
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn broken_credentials_are_an_error_not_an_empty_config() {
+    let dir = std::env::temp_dir().join(format!("peacher-credentials-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.toml");
+    std::fs::write(
+        &path,
+        "[profiles.default]\nurl = \"http://localhost:8080\"\n",
+    )
+    .unwrap();
+    std::fs::write(credentials_path(&path), "[default\napi_key = \"secret\"\n").unwrap();
+
+    assert!(matches!(
+        Config::load_from_path(path.clone()),
+        Err(ConfigLoadErr::Credentials { .. })
+    ));
+    assert!(Config::load_or_default(path.clone()).is_err());
+    // nothing was written over
+    assert!(
+        std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("localhost")
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
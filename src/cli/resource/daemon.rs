@@ -0,0 +1,524 @@
+use std::{
+    collections::BTreeMap,
+    fmt as std_fmt,
+    fs::{File, TryLockError},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::cli::fmt::{self, AsTable};
+use crate::prelude::*;
+
+/// A part of the sync that the daemon runs on its own schedule
+#[derive(
+    ValueEnum,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    strum::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Stage {
+    /// Create and update sessions
+    Sessions,
+    /// Sync members of every chamber, for each synced session
+    Members,
+    /// Sync legislation, for each synced session
+    Legislation,
+}
+
+/// How often something happens, such as `90s`, `30m`, `6h` or `1d`, up to `3650d`. `off`
+/// never runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Every(pub Option<Duration>);
+
+impl Every {
+    /// Longest duration accepted when parsing, which keeps schedules well within dates
+    pub const MAX: Duration = Duration::from_secs(10 * 365 * 24 * 60 * 60);
+}
+
+impl FromStr for Every {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+        if value == "off" {
+            return Ok(Every(None));
+        }
+        let split = value
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len());
+        let (amount, unit) = value.split_at(split);
+        let amount: u64 = amount
+            .parse()
+            .map_err(|_| anyhow!("expected a duration such as 30m, 6h or 1d, got `{value}`"))?;
+        let seconds = match unit {
+            "" | "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            _ => bail!("unknown duration unit `{unit}`, expected s, m, h or d"),
+        };
+        let duration = amount
+            .checked_mul(seconds)
+            .map(Duration::from_secs)
+            .filter(|duration| *duration <= Every::MAX)
+            .ok_or_else(|| anyhow!("`{value}` is longer than the 3650d limit"))?;
+        if duration.is_zero() {
+            bail!("`{value}` would run without pause, use `off` instead");
+        }
+        Ok(Every(Some(duration)))
+    }
+}
+
+impl std_fmt::Display for Every {
+    fn fmt(&self, f: &mut std_fmt::Formatter<'_>) -> std_fmt::Result {
+        let Some(duration) = self.0 else {
+            return write!(f, "off");
+        };
+        let seconds = duration.as_secs();
+        match seconds {
+            0 => write!(f, "0s"),
+            s if s % 86_400 == 0 => write!(f, "{}d", s / 86_400),
+            s if s % 3_600 == 0 => write!(f, "{}h", s / 3_600),
+            s if s % 60 == 0 => write!(f, "{}m", s / 60),
+            s => write!(f, "{s}s"),
+        }
+    }
+}
+
+/// How often each stage runs
+#[derive(Args, Debug, Clone)]
+pub struct Schedule {
+    /// How often sessions are synced
+    #[arg(long, default_value = "1d")]
+    pub sessions: Every,
+    /// How often members are synced
+    #[arg(long, default_value = "1d")]
+    pub members: Every,
+    /// How often legislation is synced
+    #[arg(long, default_value = "1h")]
+    pub legislation: Every,
+    /// Delays each run by a random amount up to this, so daemons don't all hit the API at once
+    #[arg(long, default_value = "5m")]
+    pub jitter: Every,
+    /// Sync members and legislation of every session, not only current ones
+    #[arg(long)]
+    pub all_sessions: bool,
+}
+
+impl Schedule {
+    fn interval(&self, stage: Stage) -> Option<Duration> {
+        match stage {
+            Stage::Sessions => self.sessions.0,
+            Stage::Members => self.members.0,
+            Stage::Legislation => self.legislation.0,
+        }
+    }
+
+    fn jitter(&self) -> chrono::Duration {
+        let max = self.jitter.0.unwrap_or_default().as_millis() as u64;
+        chrono::Duration::milliseconds(rand::random_range(0..=max) as i64)
+    }
+
+    /// When a stage should next run after one that finished at `after`
+    fn next_run(&self, stage: Stage, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let interval = chrono::Duration::from_std(self.interval(stage)?).ok()?;
+        after
+            .checked_add_signed(interval)?
+            .checked_add_signed(self.jitter())
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum DaemonCmd {
+    /// Keeps syncing on a schedule until stopped with Ctrl-C or SIGTERM.
+    ///
+    /// A stage that is running when the daemon is asked to stop is finished first. Only one
+    /// daemon may run per profile.
+    Run {
+        #[command(flatten)]
+        schedule: Schedule,
+    },
+    /// Shows when each stage last ran and what it did
+    Status,
+}
+
+/// Where a profile's daemon keeps its lock and status
+#[derive(Debug, Clone)]
+pub struct DaemonFiles {
+    pub lock: PathBuf,
+    pub status: PathBuf,
+}
+
+impl DaemonFiles {
+    /// Files next to the config, named after the profile
+    pub fn new(config_path: &Path, profile: &str) -> Self {
+        Self {
+            lock: config_path.with_file_name(format!("daemon-{profile}.lock")),
+            status: config_path.with_file_name(format!("daemon-{profile}.json")),
+        }
+    }
+
+    /// The pid of the daemon holding the lock, if one is running.
+    ///
+    /// A pid left in the file by a daemon that has exited is ignored, since its lock is gone.
+    pub fn running_pid(&self) -> Option<u32> {
+        let file = File::open(&self.lock).ok()?;
+        match file.try_lock() {
+            Err(TryLockError::WouldBlock) => std::fs::read_to_string(&self.lock)
+                .ok()?
+                .trim()
+                .parse()
+                .ok(),
+            Ok(()) | Err(TryLockError::Error(_)) => None,
+        }
+    }
+}
+
+/// What the daemon last did, persisted after every run
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DaemonStatus {
+    pub started_at: Option<DateTime<Utc>>,
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub stages: BTreeMap<Stage, StageStatus>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct StageStatus {
+    pub last_run: Option<StageRun>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// Failed runs since the last success
+    pub failures: u32,
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+/// A single run of a stage
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StageRun {
+    pub stage: Stage,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub succeeded: bool,
+    /// What changed, or why the run failed
+    pub summary: String,
+}
+
+impl DaemonStatus {
+    /// Loads the status, or an empty one if the daemon never ran
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn record(&mut self, run: StageRun, next_run_at: Option<DateTime<Utc>>) {
+        let stage = self.stages.entry(run.stage).or_default();
+        if run.succeeded {
+            stage.last_success_at = Some(run.finished_at);
+            stage.failures = 0;
+        } else {
+            stage.failures += 1;
+        }
+        stage.next_run_at = next_run_at;
+        stage.last_run = Some(run);
+    }
+}
+
+/// Holds an advisory lock on the profile's lock file until dropped.
+///
+/// The OS releases the lock when the process exits, however it exits, so a crashed or
+/// killed daemon never keeps the next one from starting. The file stays behind with the
+/// last pid in it.
+struct Lock {
+    _file: File,
+}
+
+impl Lock {
+    fn acquire(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let pid = std::fs::read_to_string(path).unwrap_or_default();
+                bail!(
+                    "A daemon is already running for this profile (pid {})",
+                    pid.trim()
+                )
+            }
+            Err(TryLockError::Error(err)) => return Err(err.into()),
+        }
+        file.set_len(0)?;
+        write!(file, "{}", std::process::id())?;
+        Ok(Self { _file: file })
+    }
+}
+
+/// Installs the Ctrl-C and SIGTERM handlers right away, resolving once either arrives.
+fn shutdown_signal() -> std::io::Result<impl Future<Output = ()>> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        let mut interrupt = signal(SignalKind::interrupt())?;
+        let mut terminate = signal(SignalKind::terminate())?;
+        Ok(async move {
+            tokio::select! {
+                _ = interrupt.recv() => {}
+                _ = terminate.recv() => {}
+            }
+        })
+    }
+    #[cfg(not(unix))]
+    {
+        let mut ctrl_c = tokio::signal::windows::ctrl_c()?;
+        Ok(async move {
+            ctrl_c.recv().await;
+        })
+    }
+}
+
+impl DaemonCmd {
+    pub async fn run<'p, E, P>(self, sync: ApiSync<'p, E, P>, files: DaemonFiles) -> Result<()>
+    where
+        E: ExternalClient,
+        P: Client,
+    {
+        match self {
+            DaemonCmd::Run { schedule } => run_daemon(sync, schedule, files).await,
+            DaemonCmd::Status => {
+                let status = DaemonStatus::load(&files.status)?;
+                fmt::print_value(&status, || {
+                    match (files.running_pid(), status.stopped_at) {
+                        (Some(pid), _) => println!("{} (pid {pid})", fmt::green("● Running")),
+                        (None, Some(stopped_at)) => {
+                            println!("{} at {stopped_at}", fmt::dim("Stopped"))
+                        }
+                        (None, None) => println!("{}", fmt::dim("Never ran")),
+                    }
+                    status.print();
+                });
+                Ok(())
+            }
+        }
+    }
+}
+
+async fn run_daemon<'p, E, P>(
+    mut sync: ApiSync<'p, E, P>,
+    schedule: Schedule,
+    files: DaemonFiles,
+) -> Result<()>
+where
+    E: ExternalClient,
+    P: Client,
+{
+    let _lock = Lock::acquire(&files.lock)?;
+    let shutdown = shutdown_signal()?;
+    tokio::pin!(shutdown);
+
+    let mut status = DaemonStatus::load(&files.status).unwrap_or_default();
+    let now = Utc::now();
+    status.started_at = Some(now);
+    status.stopped_at = None;
+
+    // pick up where a previous daemon left off, instead of running everything on restart
+    let mut next_runs = BTreeMap::new();
+    for stage in [Stage::Sessions, Stage::Members, Stage::Legislation] {
+        let last_success = status
+            .stages
+            .get(&stage)
+            .and_then(|stage| stage.last_success_at);
+        let next_run = match (schedule.interval(stage), last_success) {
+            (None, _) => None,
+            (Some(_), Some(last_success)) => schedule
+                .next_run(stage, last_success)
+                .map(|next_run| next_run.max(now)),
+            (Some(_), None) => now.checked_add_signed(schedule.jitter()),
+        };
+        status.stages.entry(stage).or_default().next_run_at = next_run;
+        if let Some(next_run) = next_run {
+            next_runs.insert(stage, next_run);
+        }
+    }
+    if next_runs.is_empty() {
+        bail!("Every stage is off, there is nothing to run");
+    }
+
+    let mut jurisdiction = sync.jurisdiction();
+    tokio::select! {
+        result = jurisdiction.get() => {
+            result?;
+        }
+        _ = &mut shutdown => {
+            info!("Shutting down before the first run");
+            return Ok(());
+        }
+    }
+
+    loop {
+        status.save(&files.status)?;
+        let (stage, at) = next_runs
+            .iter()
+            .min_by_key(|(_, at)| **at)
+            .map(|(stage, at)| (*stage, *at))
+            .context("No stage is scheduled")?;
+        info!("Next run is {stage} at {at}");
+
+        let wait = (at - Utc::now()).to_std().unwrap_or_default();
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = &mut shutdown => break,
+        }
+
+        let started_at = Utc::now();
+        let result = run_stage(&mut sync, stage, schedule.all_sessions).await;
+        let finished_at = Utc::now();
+        if let Err(e) = &result {
+            error!("{stage} sync failed: {e:?}");
+        }
+        let run = StageRun {
+            stage,
+            started_at,
+            finished_at,
+            succeeded: result.is_ok(),
+            summary: result.unwrap_or_else(|e| e.to_string()),
+        };
+        run.print();
+
+        // scheduling from when the run finished keeps a slow run from overlapping the next
+        let next_run = schedule.next_run(stage, finished_at);
+        if let Some(next_run) = next_run {
+            next_runs.insert(stage, next_run);
+        }
+        status.record(run, next_run);
+    }
+
+    info!("Shutting down");
+    status.stopped_at = Some(Utc::now());
+    for stage in status.stages.values_mut() {
+        stage.next_run_at = None;
+    }
+    status.save(&files.status)?;
+    Ok(())
+}
+
+/// Runs a stage once, summarizing what changed
+async fn run_stage<'p, E, P>(
+    sync: &mut ApiSync<'p, E, P>,
+    stage: Stage,
+    all_sessions: bool,
+) -> Result<String>
+where
+    E: ExternalClient,
+    P: Client,
+{
+    if stage == Stage::Sessions {
+        let result = sync.sessions().sync_sessions().await?;
+        return Ok(changes(result.created.len(), result.updated.len()));
+    }
+
+    let sessions: Vec<ExternalId> = sync
+        .sessions()
+        .list()
+        .await?
+        .data
+        .into_iter()
+        .filter(|session| all_sessions || session.current)
+        .filter_map(|session| session.external_id)
+        .collect();
+
+    let (mut created, mut updated) = (0, 0);
+    for session in &sessions {
+        let mut session = sync.sessions().session(session);
+        if stage == Stage::Members {
+            for result in session.sync_all_members().await? {
+                created += result.created.len();
+                updated += result.updated.len();
+            }
+        } else {
+            let result = session.legislation().sync(None).await?;
+            created += result.created.len();
+            updated += result.updated.len();
+        }
+    }
+    Ok(format!(
+        "{} in {} session(s)",
+        changes(created, updated),
+        sessions.len()
+    ))
+}
+
+fn changes(created: usize, updated: usize) -> String {
+    format!("{created} created, {updated} updated")
+}
+
+#[test]
+fn parses_intervals() {
+    let hour: Every = "1h".parse().unwrap();
+    assert_eq!(hour, Every(Some(Duration::from_secs(3_600))));
+    assert_eq!(hour.to_string(), "1h");
+    assert_eq!("90".parse::<Every>().unwrap().to_string(), "90s");
+    assert_eq!("120m".parse::<Every>().unwrap().to_string(), "2h");
+    assert_eq!("off".parse::<Every>().unwrap(), Every(None));
+    assert!("1w".parse::<Every>().is_err());
+    assert!("soon".parse::<Every>().is_err());
+    assert!("999999999999999999d".parse::<Every>().is_err());
+    // fits in a u64 of seconds, but not in a date
+    assert!("100000000d".parse::<Every>().is_err());
+    assert_eq!("3650d".parse::<Every>().unwrap(), Every(Some(Every::MAX)));
+    assert!("0s".parse::<Every>().is_err());
+    assert!("0".parse::<Every>().is_err());
+}
+
+#[test]
+fn lock_is_held_until_dropped_and_ignores_stale_pids() {
+    let dir = std::env::temp_dir().join(format!("peacher-daemon-{}", std::process::id()));
+    let files = DaemonFiles::new(&dir.join("config.toml"), "default");
+
+    // a daemon that was killed leaves its pid behind, but not its lock
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(&files.lock, "4194305").unwrap();
+    assert_eq!(files.running_pid(), None);
+
+    let lock = Lock::acquire(&files.lock).unwrap();
+    assert_eq!(files.running_pid(), Some(std::process::id()));
+    assert!(Lock::acquire(&files.lock).is_err());
+
+    drop(lock);
+    assert_eq!(files.running_pid(), None);
+    assert!(Lock::acquire(&files.lock).is_ok());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
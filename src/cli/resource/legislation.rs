@@ -0,0 +1,130 @@
+use crate::cli::fmt::AsTable;
+use crate::prelude::*;
+use anyhow::{Result, anyhow};
+use clap::{Args, Subcommand};
+use strum::VariantArray;
+
+/// Command line flags for [`LegislationParams`]
+#[derive(Args, Debug, Clone, Default)]
+pub struct LegislationFilters {
+    /// Only legislation of this session
+    #[arg(long)]
+    pub session: Option<i32>,
+    /// Only legislation of this chamber
+    #[arg(long)]
+    pub chamber: Option<i32>,
+    /// resolution | bill | other. May be repeated
+    #[arg(long = "type")]
+    pub legislation_type: Vec<LegislationType>,
+    /// pending | passed | failed | signed | vetoed | veto_overridden | withdrawn. May be repeated
+    #[arg(long, value_parser = parse_status)]
+    pub status: Vec<LegislationStatus>,
+    /// Only legislation that is still in progress
+    #[arg(long, conflicts_with = "inactive")]
+    pub active: bool,
+    /// Only legislation that has reached a final status
+    #[arg(long)]
+    pub inactive: bool,
+    /// Filter by category name. May be repeated
+    #[arg(long)]
+    pub category: Vec<String>,
+    /// Filter by sponsoring member ID. May be repeated
+    #[arg(long)]
+    pub sponsor: Vec<i32>,
+    /// Filter by external ID. May be repeated
+    #[arg(long)]
+    pub external_id: Vec<String>,
+    /// id | external_id | created_at | introduced_at | status_updated_at | title
+    #[arg(long, default_value_t)]
+    pub order_by: LegislationOrder,
+    /// asc | desc
+    #[arg(long, default_value_t)]
+    pub order: Ordering,
+    /// Page to fetch, starting at 0
+    #[arg(long, default_value_t = 0)]
+    pub page: u64,
+    #[arg(long, default_value_t = 20)]
+    pub page_size: u64,
+}
+
+fn parse_status(value: &str) -> Result<LegislationStatus> {
+    let name = value.replace('_', "");
+    LegislationStatus::VARIANTS
+        .iter()
+        .find(|status| status.to_string().eq_ignore_ascii_case(&name))
+        .copied()
+        .ok_or_else(|| anyhow!("unknown legislation status `{value}`"))
+}
+
+impl From<LegislationFilters> for LegislationParams {
+    fn from(filters: LegislationFilters) -> Self {
+        let mut params = LegislationParams {
+            session_id: filters.session,
+            chamber_id: filters.chamber,
+            legislation_type: filters.legislation_type.into_iter().collect(),
+            page: Some(filters.page),
+            page_size: Some(filters.page_size),
+            ..Default::default()
+        }
+        .set_status(filters.status)
+        .set_categories(filters.category)
+        .set_sponsor_ids(filters.sponsor)
+        .set_external_ids(filters.external_id.iter().map(ExternalId::new))
+        .set_order_by(filters.order_by)
+        .set_order(filters.order);
+        if filters.active || filters.inactive {
+            params = params.set_is_active(filters.active);
+        }
+        params
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum LegislationCmd {
+    /// Lists legislation known by Peacher
+    List {
+        #[command(flatten)]
+        filters: LegislationFilters,
+    },
+    /// Searches legislation by its text
+    Search {
+        query: String,
+        #[command(flatten)]
+        filters: LegislationFilters,
+    },
+    /// Details and sponsors of a piece of legislation
+    Show { id: i32 },
+}
+
+impl LegislationCmd {
+    pub async fn run<'p, E, P>(self, mut sync: ApiSync<'p, E, P>) -> Result<()>
+    where
+        E: ExternalClient,
+        P: Client,
+    {
+        match self {
+            LegislationCmd::List { filters } => {
+                let legislation = LegislationParams::from(filters)
+                    .request(sync.peacher())
+                    .await?;
+                legislation.print();
+                Ok(())
+            }
+            LegislationCmd::Search { query, filters } => {
+                let legislation = LegislationParams {
+                    freetext: Some(query),
+                    ..filters.into()
+                }
+                .request(sync.peacher())
+                .await?;
+                legislation.print();
+                Ok(())
+            }
+            LegislationCmd::Show { id } => {
+                let details = GetLegislationDetails(id).request(sync.peacher()).await?;
+                details.print();
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+
+use crate::cli::fmt::{self, AsTable};
+use crate::prelude::*;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+/// Which attributes of the map file hold district names and external ids
+#[derive(Args, Debug, Clone, Default)]
+pub struct MappingArgs {
+    /// Field holding the district name (e.g. NAME, NAMELSAD)
+    #[arg(long)]
+    pub name_field: Option<String>,
+    /// Field holding the district external id (e.g. AFFGEOID)
+    #[arg(long)]
+    pub external_id_field: Option<String>,
+}
+
+impl MappingArgs {
+    fn into_mapping(self) -> Option<FieldMapping> {
+        if self.name_field.is_none() && self.external_id_field.is_none() {
+            return None;
+        }
+        Some(FieldMapping {
+            name_field: self.name_field,
+            external_id_field: self.external_id_field,
+        })
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum MapCmd {
+    /// Lists the maps known by Peacher
+    List,
+    /// Details about a map and its districts
+    Inspect { id: i32 },
+    /// Uploads a GeoJSON file or zipped shapefile as a new map
+    Upload {
+        path: PathBuf,
+        /// Name of the new map
+        #[arg(long)]
+        name: String,
+        #[command(flatten)]
+        mapping: MappingArgs,
+    },
+    /// Reads a map file locally and reports any issues, without uploading it
+    #[cfg(feature = "map_preview")]
+    Check {
+        path: PathBuf,
+        #[command(flatten)]
+        mapping: MappingArgs,
+    },
+}
+
+impl MapCmd {
+    pub async fn run<'p, E, P>(self, mut sync: ApiSync<'p, E, P>) -> Result<()>
+    where
+        E: ExternalClient,
+        P: Client,
+    {
+        match self {
+            MapCmd::List => {
+                let maps = ListMaps.request(sync.peacher()).await?;
+                maps.print();
+                Ok(())
+            }
+            MapCmd::Inspect { id } => {
+                let map = GetMapDistricts(id).request(sync.peacher()).await?;
+                map.print();
+                Ok(())
+            }
+            MapCmd::Upload {
+                path,
+                name,
+                mapping,
+            } => {
+                let mut upload = UploadMap::read(name, &path)?;
+                if let Some(mapping) = mapping.into_mapping() {
+                    upload = upload.with_mapping(mapping);
+                }
+
+                let spinner = fmt::spinner(format!("Uploading {}...", path.display()));
+                let result = match upload.request(sync.peacher()).await {
+                    Ok(result) => result,
+                    Err(e) => {
+                        fmt::spinner_error(&spinner, "Upload failed");
+                        return Err(e.into());
+                    }
+                };
+                fmt::spinner_success(&spinner, "Upload complete");
+                result.print();
+                Ok(())
+            }
+            #[cfg(feature = "map_preview")]
+            MapCmd::Check { path, mapping } => {
+                let mut upload = UploadMap::read("", &path)?;
+                if let Some(mapping) = mapping.into_mapping() {
+                    upload = upload.with_mapping(mapping);
+                }
+                upload.inspect()?.print();
+                Ok(())
+            }
+        }
+    }
+}
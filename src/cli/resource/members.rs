@@ -0,0 +1,90 @@
+use crate::cli::fmt::AsTable;
+use crate::prelude::*;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+/// Filters shared by listing and searching members
+#[derive(Args, Debug, Clone, Default)]
+pub struct MemberFilters {
+    /// Only members of this session
+    #[arg(long)]
+    pub session: Option<i32>,
+    /// Only members of this chamber
+    #[arg(long)]
+    pub chamber: Option<i32>,
+    /// Filter by external ID
+    #[arg(long)]
+    pub external_id: Option<String>,
+    /// Page to fetch, starting at 0
+    #[arg(long, default_value_t = 0)]
+    pub page: u64,
+    #[arg(long, default_value_t = 20)]
+    pub page_size: u64,
+}
+
+impl MemberFilters {
+    fn into_request(self) -> ListMembers {
+        let mut request = ListMembers::new().page(self.page).page_size(self.page_size);
+        request.params.session_id = self.session;
+        request.params.chamber_id = self.chamber;
+        if let Some(external_id) = self.external_id {
+            request = request.with_external_id(external_id);
+        }
+        request
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum MemberCmd {
+    /// Lists members known by Peacher
+    List {
+        #[command(flatten)]
+        filters: MemberFilters,
+    },
+    /// Searches members by display name, handle or full name
+    Search {
+        query: String,
+        #[command(flatten)]
+        filters: MemberFilters,
+    },
+    /// Details about a member, by ID or @handle
+    Show { member: String },
+}
+
+impl MemberCmd {
+    pub async fn run<'p, E, P>(self, mut sync: ApiSync<'p, E, P>) -> Result<()>
+    where
+        E: ExternalClient,
+        P: Client,
+    {
+        match self {
+            MemberCmd::List { filters } => {
+                let members = filters.into_request().request(sync.peacher()).await?;
+                members.print();
+                Ok(())
+            }
+            MemberCmd::Search { query, filters } => {
+                let members = filters
+                    .into_request()
+                    .freetext(query)
+                    .request(sync.peacher())
+                    .await?;
+                members.print();
+                Ok(())
+            }
+            MemberCmd::Show { member } => {
+                let details = match member.parse::<i32>() {
+                    Ok(id) => GetMemberDetails(id).request(sync.peacher()).await?,
+                    Err(_) => {
+                        let handle = member.strip_prefix('@').unwrap_or(&member);
+                        GetMemberByHandle::new(handle)
+                            .request(sync.peacher())
+                            .await?
+                    }
+                };
+                details.print();
+                Ok(())
+            }
+        }
+    }
+}
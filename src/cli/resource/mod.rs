@@ -1,19 +1,35 @@
 mod config;
 pub use config::*;
 
+mod daemon;
+pub use daemon::*;
+
 mod jurisdiction;
 pub use jurisdiction::*;
 
+mod legislation;
+pub use legislation::*;
+
+mod maps;
+pub use maps::*;
+
+mod members;
+pub use members::*;
+
 mod sessions;
 pub use sessions::*;
 
+mod votes;
+pub use votes::*;
+
 use anyhow::Result;
 use clap::Subcommand;
 
+use crate::cli::NoCommands;
 use crate::sync::{ApiSync, ExternalClient};
 
 #[derive(Subcommand, Debug, Clone)]
-pub enum Resource {
+pub enum Resource<C: Subcommand = NoCommands> {
     /// Commands to modify your config
     Config {
         #[command(subcommand)]
@@ -30,4 +46,38 @@ pub enum Resource {
         #[command(subcommand)]
         cmd: SessionCmd,
     },
+
+    /// Commands to list, search and look up members
+    Members {
+        #[command(subcommand)]
+        cmd: MemberCmd,
+    },
+
+    /// Commands to list, search and look up legislation
+    Legislation {
+        #[command(subcommand)]
+        cmd: LegislationCmd,
+    },
+
+    /// Commands to look up the votes held on legislation
+    Votes {
+        #[command(subcommand)]
+        cmd: VoteCmd,
+    },
+
+    /// Commands to keep syncing on a schedule, and to see how it went
+    Daemon {
+        #[command(subcommand)]
+        cmd: DaemonCmd,
+    },
+
+    /// Commands to upload and look up district maps
+    Maps {
+        #[command(subcommand)]
+        cmd: MapCmd,
+    },
+
+    /// Commands added by your client, see [`CustomCommand`](crate::cli::CustomCommand)
+    #[command(flatten)]
+    Custom(C),
 }
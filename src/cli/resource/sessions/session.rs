@@ -1,6 +1,7 @@
 use crate::{cli::fmt, prelude::*};
 use anyhow::{Context, Result};
 use clap::{Subcommand, ValueEnum};
+use serde::Serialize;
 
 #[derive(Subcommand, Debug, Clone, Copy)]
 pub enum SyncType {
@@ -53,33 +54,51 @@ impl SessionAction {
                 Ok(())
             }
             SessionAction::Sync { chamber, sync_type } => {
+                let mut result = SessionSyncResult::default();
                 match sync_type {
                     SyncType::All | SyncType::Members => match chamber.as_deref() {
                         Some(id) => {
                             let id = ExternalId::new(id);
-                            let result = session.members(&id).sync().await?;
-                            result.print()
-                        }
-                        None => {
-                            let results = session.sync_all_members().await?;
-                            for result in results {
-                                result.print();
-                            }
+                            result.members.push(session.members(&id).sync().await?);
                         }
+                        None => result.members = session.sync_all_members().await?,
                     },
                     SyncType::Legislation => {}
                 }
 
                 match sync_type {
                     SyncType::All | SyncType::Legislation => {
-                        let value = session.legislation().sync(None).await?;
-                        value.print();
+                        result.legislation = Some(session.legislation().sync(None).await?);
                     }
                     SyncType::Members => {}
                 }
 
+                result.print();
                 Ok(())
             }
         }
     }
 }
+
+/// Everything one `session sync` did, printed as a single document
+#[derive(Serialize, Debug, Default)]
+struct SessionSyncResult {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    members: Vec<MembersSyncResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    legislation: Option<LegislationSyncResult>,
+}
+
+impl SessionSyncResult {
+    fn print(&self) {
+        fmt::print_value(self, || {
+            // only called for tables, so each part prints its own table
+            for members in &self.members {
+                members.print();
+            }
+            if let Some(legislation) = &self.legislation {
+                legislation.print();
+            }
+        });
+    }
+}
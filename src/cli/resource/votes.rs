@@ -0,0 +1,37 @@
+use crate::cli::fmt::AsTable;
+use crate::prelude::*;
+use anyhow::Result;
+use clap::Subcommand;
+
+#[derive(Subcommand, Debug, Clone, Copy)]
+pub enum VoteCmd {
+    /// Lists the votes held on a piece of legislation
+    List { legislation: i32 },
+    /// How each member voted in a single vote
+    Show { legislation: i32, vote: i32 },
+}
+
+impl VoteCmd {
+    pub async fn run<'p, E, P>(self, mut sync: ApiSync<'p, E, P>) -> Result<()>
+    where
+        E: ExternalClient,
+        P: Client,
+    {
+        match self {
+            VoteCmd::List { legislation } => {
+                let votes = GetLegislationVotes(legislation)
+                    .request(sync.peacher())
+                    .await?;
+                votes.print();
+                Ok(())
+            }
+            VoteCmd::Show { legislation, vote } => {
+                let details = GetLegislationVoteDetails::new(legislation, vote)
+                    .request(sync.peacher())
+                    .await?;
+                details.print();
+                Ok(())
+            }
+        }
+    }
+}
@@ -38,6 +38,11 @@ impl BoundingBox {
         Self::new(p1, p2)
     }
 
+    /// The GeoJSON `bbox` member for this bounding box: `[min_x, min_y, max_x, max_y]`.
+    pub fn to_vec(&self) -> Vec<f64> {
+        vec![self.min.x, self.min.y, self.max.x, self.max.y]
+    }
+
     /// Expand this bounding box to also contain `other`.
     pub fn union(&self, other: &BoundingBox) -> BoundingBox {
         BoundingBox {
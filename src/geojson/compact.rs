@@ -0,0 +1,279 @@
+use serde::{Serialize, ser::SerializeMap};
+
+use crate::geojson::*;
+
+/// Serializes a [`GeoJson`] with every line and ring written as an
+/// [encoded polyline](https://developers.google.com/maps/documentation/utilities/polylinealgorithm)
+/// instead of an array of positions.
+///
+/// Each geometry gets a `precision` member holding the number of decimals that were kept.
+/// As in the polyline format, latitude is encoded before longitude, and elevation is dropped.
+/// Points and multi points are encoded as a single polyline.
+pub struct CompactGeoJson<'a, T> {
+    geojson: &'a GeoJson<T>,
+    precision: u32,
+}
+
+/// Most decimals a polyline keeps. Ten is already far below a millimeter, and more would
+/// overflow the encoded integers.
+pub const MAX_POLYLINE_PRECISION: u32 = 10;
+
+impl<T> GeoJson<T> {
+    /// Compact serialization keeping `precision` decimals, up to [`MAX_POLYLINE_PRECISION`].
+    /// Five matches most polyline decoders.
+    pub fn compact(&self, precision: u32) -> CompactGeoJson<'_, T> {
+        CompactGeoJson {
+            geojson: self,
+            precision: precision.min(MAX_POLYLINE_PRECISION),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for CompactGeoJson<'_, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.geojson {
+            GeoJson::Feature(feature) => CompactFeature {
+                feature,
+                precision: self.precision,
+            }
+            .serialize(serializer),
+            GeoJson::FeatureCollection(collection) => {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("type", "FeatureCollection")?;
+                if let Some(bbox) = &collection.bbox {
+                    map.serialize_entry("bbox", bbox)?;
+                }
+                map.serialize_key("features")?;
+                map.serialize_value(&Features {
+                    features: &collection.features,
+                    precision: self.precision,
+                })?;
+                for (key, value) in &collection.foreign_members {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+struct Features<'a, T> {
+    features: &'a [GeoJsonFeature<T>],
+    precision: u32,
+}
+
+impl<T: Serialize> Serialize for Features<'_, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.features.iter().map(|feature| CompactFeature {
+            feature,
+            precision: self.precision,
+        }))
+    }
+}
+
+struct CompactFeature<'a, T> {
+    feature: &'a GeoJsonFeature<T>,
+    precision: u32,
+}
+
+impl<T: Serialize> Serialize for CompactFeature<'_, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let feature = self.feature;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", "Feature")?;
+        if let Some(id) = &feature.id {
+            map.serialize_entry("id", id)?;
+        }
+        if let Some(bbox) = &feature.bbox {
+            map.serialize_entry("bbox", bbox)?;
+        }
+        map.serialize_entry(
+            "geometry",
+            &CompactGeometry {
+                geometry: &feature.geometry,
+                precision: self.precision,
+            },
+        )?;
+        map.serialize_entry("properties", &feature.properties)?;
+        for (key, value) in &feature.foreign_members {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+struct CompactGeometry<'a> {
+    geometry: &'a Geometry,
+    precision: u32,
+}
+
+impl Serialize for CompactGeometry<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let encode = |positions: &[Vec<f64>]| encode_polyline(positions, self.precision);
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("type", self.geometry.type_name())?;
+        map.serialize_entry("precision", &self.precision)?;
+        match self.geometry {
+            Geometry::Point(position) => {
+                map.serialize_entry("coordinates", &encode(std::slice::from_ref(position)))?
+            }
+            Geometry::MultiPoint(positions) | Geometry::LineString(positions) => {
+                map.serialize_entry("coordinates", &encode(positions))?
+            }
+            Geometry::MultiLineString(lines) | Geometry::Polygon(lines) => {
+                let lines: Vec<_> = lines.iter().map(|line| encode(line)).collect();
+                map.serialize_entry("coordinates", &lines)?
+            }
+            Geometry::MultiPolygon(polygons) => {
+                let polygons: Vec<Vec<_>> = polygons
+                    .iter()
+                    .map(|rings| rings.iter().map(|ring| encode(ring)).collect())
+                    .collect();
+                map.serialize_entry("coordinates", &polygons)?
+            }
+            Geometry::GeometryCollection(geometries) => {
+                let geometries: Vec<_> = geometries
+                    .iter()
+                    .map(|geometry| CompactGeometry {
+                        geometry,
+                        precision: self.precision,
+                    })
+                    .collect();
+                map.serialize_entry("geometries", &geometries)?
+            }
+        }
+        map.end()
+    }
+}
+
+/// Encodes `[longitude, latitude]` positions as a polyline with `precision` decimals, up to
+/// [`MAX_POLYLINE_PRECISION`].
+pub fn encode_polyline(positions: &[Vec<f64>], precision: u32) -> String {
+    let factor = 10f64.powi(precision.min(MAX_POLYLINE_PRECISION) as i32);
+    let mut encoded = String::new();
+    let (mut last_lat, mut last_lng) = (0, 0);
+    for position in positions {
+        let [lng, lat, ..] = position[..] else {
+            continue;
+        };
+        let (lat, lng) = ((lat * factor).round() as i64, (lng * factor).round() as i64);
+        // only coordinates far outside of longitude and latitude ranges can overflow, and
+        // decoding reports those as malformed.
+        encode_value(lat.wrapping_sub(last_lat), &mut encoded);
+        encode_value(lng.wrapping_sub(last_lng), &mut encoded);
+        (last_lat, last_lng) = (lat, lng);
+    }
+    encoded
+}
+
+fn encode_value(value: i64, encoded: &mut String) {
+    let mut value = if value < 0 { !(value << 1) } else { value << 1 } as u64;
+    while value >= 0x20 {
+        encoded.push(char::from((0x20 | (value & 0x1f)) as u8 + 63));
+        value >>= 5;
+    }
+    encoded.push(char::from(value as u8 + 63));
+}
+
+/// Decodes a polyline back into `[longitude, latitude]` positions, or `None` if it is malformed.
+pub fn decode_polyline(encoded: &str, precision: u32) -> Option<Vec<Vec<f64>>> {
+    let factor = 10f64.powi(precision.min(MAX_POLYLINE_PRECISION) as i32);
+    let mut bytes = encoded.bytes();
+    let mut next_value = || -> Option<Option<i64>> {
+        let (mut result, mut shift) = (0u64, 0);
+        loop {
+            let Some(byte) = bytes.next() else {
+                // running out of input is only fine between values.
+                return (shift == 0).then_some(None);
+            };
+            let chunk = u64::from(byte.checked_sub(63)?);
+            if chunk >= 0x40 || shift > 60 {
+                return None;
+            }
+            result |= (chunk & 0x1f) << shift;
+            shift += 5;
+            if chunk < 0x20 {
+                let value = (result >> 1) as i64;
+                return Some(Some(if result & 1 == 1 { !value } else { value }));
+            }
+        }
+    };
+
+    let mut positions = Vec::new();
+    let (mut lat, mut lng) = (0i64, 0i64);
+    while let Some(delta_lat) = next_value()? {
+        lat = lat.checked_add(delta_lat)?;
+        lng = lng.checked_add(next_value()??)?;
+        positions.push(vec![lng as f64 / factor, lat as f64 / factor]);
+    }
+    Some(positions)
+}
+
+#[test]
+fn polyline_matches_reference_encoding() {
+    let positions = vec![
+        vec![-120.2, 38.5],
+        vec![-120.95, 40.7],
+        vec![-126.453, 43.252],
+    ];
+    let encoded = encode_polyline(&positions, 5);
+    assert_eq!(encoded, "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+    assert_eq!(decode_polyline(&encoded, 5), Some(positions));
+    assert_eq!(decode_polyline("_p~iF~ps|U_ulL", 5), None);
+}
+
+#[test]
+fn polyline_rejects_overflowing_input() {
+    // two maximal latitude deltas in a row
+    assert_eq!(decode_polyline("~~~~~~~~~~~~]?~~~~~~~~~~~~]?", 5), None);
+
+    let positions = vec![vec![-180.0, -90.0], vec![180.0, 90.0]];
+    let encoded = encode_polyline(&positions, 20);
+    assert_eq!(encoded, encode_polyline(&positions, MAX_POLYLINE_PRECISION));
+    assert_eq!(decode_polyline(&encoded, 20), Some(positions));
+    // garbage coordinates don't panic, and don't decode either
+    let encoded = encode_polyline(&[vec![0.0, -1e300], vec![0.0, 1e300]], 5);
+    assert_eq!(decode_polyline(&encoded, 5), None);
+}
+
+#[test]
+fn compact_geojson_encodes_rings() {
+    let geojson = GeoJson::many([GeoJsonFeature::new(
+        Geometry::Polygon(vec![vec![
+            vec![0., 0.],
+            vec![1., 0.],
+            vec![1., 1.],
+            vec![0., 0.],
+        ]]),
+        serde_json::json!({ "name": "District 1" }),
+    )
+    .with_id("d1")]);
+
+    let value = serde_json::to_value(geojson.compact(5)).unwrap();
+    let GeoJson::FeatureCollection(collection) = &geojson else {
+        unreachable!()
+    };
+    let Geometry::Polygon(rings) = &collection.features[0].geometry else {
+        unreachable!()
+    };
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "type": "FeatureCollection",
+            "features": [{
+                "type": "Feature",
+                "id": "d1",
+                "geometry": {
+                    "type": "Polygon",
+                    "precision": 5,
+                    "coordinates": [encode_polyline(&rings[0], 5)],
+                },
+                "properties": { "name": "District 1" },
+            }],
+        })
+    );
+    assert_eq!(
+        decode_polyline(&encode_polyline(&rings[0], 5), 5).as_ref(),
+        Some(&rings[0])
+    );
+}
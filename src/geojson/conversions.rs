@@ -0,0 +1,184 @@
+use geo::{Coord, LineString, Polygon};
+
+use crate::geojson::Geometry;
+
+/// Reasons a [`Geometry`] can't be converted to or from a [`geo::Geometry`].
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum GeometryError {
+    #[error("Positions need at least two coordinates, found {0}")]
+    Position(usize),
+    #[error("Coordinates must be finite, found ({0}, {1})")]
+    NotFinite(f64, f64),
+}
+
+fn coord(position: &[f64]) -> Result<Coord, GeometryError> {
+    match *position {
+        [x, y, ..] => finite(Coord { x, y }),
+        _ => Err(GeometryError::Position(position.len())),
+    }
+}
+
+fn finite(coord: Coord) -> Result<Coord, GeometryError> {
+    if coord.x.is_finite() && coord.y.is_finite() {
+        Ok(coord)
+    } else {
+        Err(GeometryError::NotFinite(coord.x, coord.y))
+    }
+}
+
+fn line_string(positions: &[Vec<f64>]) -> Result<LineString, GeometryError> {
+    positions
+        .iter()
+        .map(|position| coord(position))
+        .collect::<Result<Vec<_>, _>>()
+        .map(LineString::new)
+}
+
+fn polygon(rings: &[Vec<Vec<f64>>]) -> Result<Polygon, GeometryError> {
+    let mut rings = rings.iter().map(|ring| line_string(ring));
+    let exterior = rings
+        .next()
+        .transpose()?
+        .unwrap_or_else(|| LineString::new(Vec::new()));
+    Ok(Polygon::new(exterior, rings.collect::<Result<_, _>>()?))
+}
+
+impl TryFrom<&Geometry> for geo::Geometry {
+    type Error = GeometryError;
+
+    fn try_from(value: &Geometry) -> Result<Self, Self::Error> {
+        Ok(match value {
+            Geometry::Point(position) => geo::Point(coord(position)?).into(),
+            Geometry::MultiPoint(positions) => geo::MultiPoint(
+                positions
+                    .iter()
+                    .map(|position| coord(position).map(geo::Point))
+                    .collect::<Result<_, _>>()?,
+            )
+            .into(),
+            Geometry::LineString(positions) => line_string(positions)?.into(),
+            Geometry::MultiLineString(lines) => geo::MultiLineString(
+                lines
+                    .iter()
+                    .map(|line| line_string(line))
+                    .collect::<Result<_, _>>()?,
+            )
+            .into(),
+            Geometry::Polygon(rings) => polygon(rings)?.into(),
+            Geometry::MultiPolygon(polygons) => geo::MultiPolygon(
+                polygons
+                    .iter()
+                    .map(|rings| polygon(rings))
+                    .collect::<Result<_, _>>()?,
+            )
+            .into(),
+            Geometry::GeometryCollection(geometries) => {
+                geo::Geometry::GeometryCollection(geo::GeometryCollection(
+                    geometries
+                        .iter()
+                        .map(geo::Geometry::try_from)
+                        .collect::<Result<_, _>>()?,
+                ))
+            }
+        })
+    }
+}
+
+impl TryFrom<Geometry> for geo::Geometry {
+    type Error = GeometryError;
+
+    fn try_from(value: Geometry) -> Result<Self, Self::Error> {
+        geo::Geometry::try_from(&value)
+    }
+}
+
+fn position(coord: Coord) -> Result<Vec<f64>, GeometryError> {
+    finite(coord).map(|coord| vec![coord.x, coord.y])
+}
+
+fn positions(line: &LineString) -> Result<Vec<Vec<f64>>, GeometryError> {
+    line.coords().map(|coord| position(*coord)).collect()
+}
+
+fn rings(polygon: &Polygon) -> Result<Vec<Vec<Vec<f64>>>, GeometryError> {
+    std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .map(positions)
+        .collect()
+}
+
+/// `Line`, `Rect` and `Triangle` have no GeoJSON equivalent, so they become a
+/// `LineString` or `Polygon`.
+impl TryFrom<&geo::Geometry> for Geometry {
+    type Error = GeometryError;
+
+    fn try_from(value: &geo::Geometry) -> Result<Self, Self::Error> {
+        Ok(match value {
+            geo::Geometry::Point(point) => Geometry::Point(position(point.0)?),
+            geo::Geometry::Line(line) => {
+                Geometry::LineString(vec![position(line.start)?, position(line.end)?])
+            }
+            geo::Geometry::LineString(line) => Geometry::LineString(positions(line)?),
+            geo::Geometry::Polygon(polygon) => Geometry::Polygon(rings(polygon)?),
+            geo::Geometry::MultiPoint(points) => Geometry::MultiPoint(
+                points
+                    .iter()
+                    .map(|point| position(point.0))
+                    .collect::<Result<_, _>>()?,
+            ),
+            geo::Geometry::MultiLineString(lines) => {
+                Geometry::MultiLineString(lines.iter().map(positions).collect::<Result<_, _>>()?)
+            }
+            geo::Geometry::MultiPolygon(polygons) => {
+                Geometry::MultiPolygon(polygons.iter().map(rings).collect::<Result<_, _>>()?)
+            }
+            geo::Geometry::GeometryCollection(geometries) => Geometry::GeometryCollection(
+                geometries
+                    .iter()
+                    .map(Geometry::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            geo::Geometry::Rect(rect) => Geometry::Polygon(rings(&rect.to_polygon())?),
+            geo::Geometry::Triangle(triangle) => Geometry::Polygon(rings(&triangle.to_polygon())?),
+        })
+    }
+}
+
+impl TryFrom<geo::Geometry> for Geometry {
+    type Error = GeometryError;
+
+    fn try_from(value: geo::Geometry) -> Result<Self, Self::Error> {
+        Geometry::try_from(&value)
+    }
+}
+
+#[test]
+fn round_trips_through_geo() {
+    let geometry = Geometry::GeometryCollection(vec![
+        Geometry::Point(vec![-84.39, 33.75]),
+        Geometry::MultiPoint(vec![vec![0.0, 0.0], vec![1.0, 1.0]]),
+        Geometry::LineString(vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![1.0, 1.0]]),
+        Geometry::MultiLineString(vec![vec![vec![0.0, 0.0], vec![2.0, 2.0]]]),
+        Geometry::Polygon(vec![vec![
+            vec![0.0, 0.0],
+            vec![1.0, 0.0],
+            vec![1.0, 1.0],
+            vec![0.0, 0.0],
+        ]]),
+    ]);
+    let converted = geo::Geometry::try_from(&geometry).unwrap();
+    assert_eq!(Geometry::try_from(converted).unwrap(), geometry);
+}
+
+#[test]
+fn rejects_invalid_positions() {
+    assert_eq!(
+        geo::Geometry::try_from(Geometry::Point(vec![1.0])),
+        Err(GeometryError::Position(1))
+    );
+    let point = geo::Geometry::Point(geo::Point::new(f64::NAN, 1.0));
+    assert!(matches!(
+        Geometry::try_from(point),
+        Err(GeometryError::NotFinite(_, _))
+    ));
+}
@@ -3,7 +3,39 @@ pub mod props_iter;
 mod bounding_box;
 pub use bounding_box::*;
 
+mod simplify;
+pub use simplify::*;
+
+mod compact;
+pub use compact::*;
+
+mod stream;
+pub use stream::*;
+
+#[cfg(feature = "geo")]
+mod conversions;
+#[cfg(feature = "geo")]
+pub use conversions::*;
+
+#[cfg(feature = "geo")]
+mod overlap;
+#[cfg(feature = "geo")]
+pub use overlap::*;
+
+#[cfg(feature = "geo")]
+mod spatial_index;
+#[cfg(feature = "geo")]
+pub use spatial_index::*;
+
+#[cfg(feature = "geo")]
+mod vector_tile;
+#[cfg(feature = "geo")]
+pub use vector_tile::*;
+
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use crate::geojson::props_iter::RefPropsIter;
 
@@ -20,9 +52,7 @@ impl<T> GeoJson<T> {
         Self::Feature(feature)
     }
     pub fn many(features: impl IntoIterator<Item = GeoJsonFeature<T>>) -> Self {
-        Self::FeatureCollection(GeoJsonFeatureCollection {
-            features: features.into_iter().collect(),
-        })
+        Self::FeatureCollection(GeoJsonFeatureCollection::new(features))
     }
 
     pub fn iter_props(&self) -> RefPropsIter<'_, T> {
@@ -56,24 +86,18 @@ impl<T> GeoJson<T> {
         F: FnMut(T) -> U,
     {
         match self {
-            GeoJson::Feature(features) => {
-                let props = func(features.properties);
-
-                GeoJson::Feature(GeoJsonFeature {
-                    geometry: features.geometry,
-                    properties: props,
-                })
-            }
+            GeoJson::Feature(feature) => GeoJson::Feature(feature.map_props(func)),
             GeoJson::FeatureCollection(collection) => {
-                let collection = collection.features.into_iter().map(|feature| {
-                    let props = func(feature.properties);
-                    GeoJsonFeature {
-                        geometry: feature.geometry,
-                        properties: props,
-                    }
-                });
+                let features = collection
+                    .features
+                    .into_iter()
+                    .map(|feature| feature.map_props(&mut func))
+                    .collect();
                 GeoJson::FeatureCollection(GeoJsonFeatureCollection {
-                    features: collection.collect(),
+                    kind: collection.kind,
+                    bbox: collection.bbox,
+                    features,
+                    foreign_members: collection.foreign_members,
                 })
             }
         }
@@ -82,11 +106,65 @@ impl<T> GeoJson<T> {
 
 /// This is a GeoJSON feature. Perfectly fine as a GeoJSON itself.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-#[serde(tag = "type", rename = "Feature")]
 pub struct GeoJsonFeature<T> {
-    //can have id
+    /// Always `"Feature"`. Not a serde tag, since those aren't checked alongside `flatten`.
+    #[serde(rename = "type")]
+    kind: FeatureType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<FeatureId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<Vec<f64>>,
     pub geometry: Geometry,
     pub properties: T,
+    /// Members that aren't part of the GeoJSON specification, kept as-is.
+    #[serde(flatten)]
+    pub foreign_members: Map<String, Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+enum FeatureType {
+    #[default]
+    Feature,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+enum FeatureCollectionType {
+    #[default]
+    FeatureCollection,
+}
+
+/// Identifier of a [`GeoJsonFeature`], either a string or a number.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(untagged)]
+pub enum FeatureId {
+    String(String),
+    #[cfg_attr(feature = "utoipa", schema(value_type = f64))]
+    Number(serde_json::Number),
+}
+
+impl From<String> for FeatureId {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for FeatureId {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<i64> for FeatureId {
+    fn from(value: i64) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<i32> for FeatureId {
+    fn from(value: i32) -> Self {
+        Self::Number(value.into())
+    }
 }
 
 #[cfg(feature = "utoipa")]
@@ -101,6 +179,11 @@ fn build_geojson_feature_schema<T: utoipa::PartialSchema>()
                 .enum_values::<_, &str>(Some(["Feature"])),
         )
         .required("type")
+        .property("id", <Option<FeatureId> as utoipa::PartialSchema>::schema())
+        .property(
+            "bbox",
+            <Option<Vec<f64>> as utoipa::PartialSchema>::schema(),
+        )
         .property("geometry", <Geometry as utoipa::PartialSchema>::schema())
         .property("properties", T::schema())
         .description(Some("A GeoJSON Feature"))
@@ -144,8 +227,42 @@ impl<T: utoipa::ToSchema> utoipa::__dev::ComposeSchema for GeoJsonFeature<T> {
 impl<T> GeoJsonFeature<T> {
     pub fn new(geometry: impl Into<Geometry>, properties: T) -> Self {
         Self {
+            kind: FeatureType::Feature,
+            id: None,
+            bbox: None,
             geometry: geometry.into(),
             properties,
+            foreign_members: Map::new(),
+        }
+    }
+
+    pub fn with_id(mut self, id: impl Into<FeatureId>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the `bbox` member to the bounds of the geometry.
+    pub fn with_computed_bbox(mut self) -> Self {
+        self.bbox = self.geometry.bbox().map(|bbox| bbox.to_vec());
+        self
+    }
+
+    pub fn with_foreign_member(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.foreign_members.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn map_props<F, U>(self, func: F) -> GeoJsonFeature<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        GeoJsonFeature {
+            kind: self.kind,
+            id: self.id,
+            bbox: self.bbox,
+            geometry: self.geometry,
+            properties: func(self.properties),
+            foreign_members: self.foreign_members,
         }
     }
 }
@@ -157,9 +274,26 @@ impl<T> From<GeoJsonFeature<T>> for GeoJson<T> {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-#[serde(tag = "type", rename = "FeatureCollection")]
 pub struct GeoJsonFeatureCollection<T> {
+    #[serde(rename = "type")]
+    kind: FeatureCollectionType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<Vec<f64>>,
     pub features: Vec<GeoJsonFeature<T>>,
+    /// Members that aren't part of the GeoJSON specification, kept as-is.
+    #[serde(flatten)]
+    pub foreign_members: Map<String, Value>,
+}
+
+impl<T> GeoJsonFeatureCollection<T> {
+    pub fn new(features: impl IntoIterator<Item = GeoJsonFeature<T>>) -> Self {
+        Self {
+            kind: FeatureCollectionType::FeatureCollection,
+            bbox: None,
+            features: features.into_iter().collect(),
+            foreign_members: Map::new(),
+        }
+    }
 }
 
 #[cfg(feature = "utoipa")]
@@ -194,6 +328,10 @@ impl<T: utoipa::ToSchema> utoipa::__dev::ComposeSchema for GeoJsonFeatureCollect
                     .enum_values::<_, &str>(Some(["FeatureCollection"])),
             )
             .required("type")
+            .property(
+                "bbox",
+                <Option<Vec<f64>> as utoipa::PartialSchema>::schema(),
+            )
             .property(
                 "features",
                 <Vec<GeoJsonFeature<T>> as utoipa::PartialSchema>::schema(),
@@ -203,21 +341,58 @@ impl<T: utoipa::ToSchema> utoipa::__dev::ComposeSchema for GeoJsonFeatureCollect
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
-#[serde(tag = "type", content = "coordinates")]
+/// A GeoJSON geometry. Positions are `[longitude, latitude]`, optionally followed by an elevation.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Geometry {
     MultiPolygon(Vec<Vec<Vec<Vec<f64>>>>),
     Polygon(Vec<Vec<Vec<f64>>>),
-    // Point(Vec<f64>),
-    // MultiPoint(Vec<Vec<f64>>),
-    // LineString(Vec<Vec<f64>>),
-    // MultiLineString(Vec<LineStringType>),
-    // Polygon(Vec<Vec<f64>>),
-    // GeometryCollection(Vec<Geometry>),
+    Point(Vec<f64>),
+    MultiPoint(Vec<Vec<f64>>),
+    LineString(Vec<Vec<f64>>),
+    MultiLineString(Vec<Vec<Vec<f64>>>),
+    GeometryCollection(Vec<Geometry>),
 }
 
 impl Geometry {
+    /// The GeoJSON `type` of this geometry.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Geometry::MultiPolygon(_) => "MultiPolygon",
+            Geometry::Polygon(_) => "Polygon",
+            Geometry::Point(_) => "Point",
+            Geometry::MultiPoint(_) => "MultiPoint",
+            Geometry::LineString(_) => "LineString",
+            Geometry::MultiLineString(_) => "MultiLineString",
+            Geometry::GeometryCollection(_) => "GeometryCollection",
+        }
+    }
+
+    /// Calls `func` with every position in this geometry, including those of nested geometries.
+    pub fn for_each_position<F>(&self, func: &mut F)
+    where
+        F: FnMut(&[f64]),
+    {
+        fn each<'a, F: FnMut(&[f64])>(
+            positions: impl IntoIterator<Item = &'a Vec<f64>>,
+            func: &mut F,
+        ) {
+            positions.into_iter().for_each(|position| func(position));
+        }
+        match self {
+            Geometry::Point(position) => func(position),
+            Geometry::MultiPoint(positions) | Geometry::LineString(positions) => {
+                each(positions, func)
+            }
+            Geometry::MultiLineString(lines) | Geometry::Polygon(lines) => {
+                each(lines.iter().flatten(), func)
+            }
+            Geometry::MultiPolygon(polygons) => each(polygons.iter().flatten().flatten(), func),
+            Geometry::GeometryCollection(geometries) => geometries
+                .iter()
+                .for_each(|geometry| geometry.for_each_position(func)),
+        }
+    }
+
     /// Compute the bounding box of this geometry by iterating all coordinates.
     ///
     /// Coordinates follow GeoJSON convention: `[longitude, latitude]`,
@@ -229,87 +404,134 @@ impl Geometry {
         let mut max_y = f64::NEG_INFINITY;
         let mut found = false;
 
-        match self {
-            Geometry::Polygon(rings) => {
-                for ring in rings {
-                    for coord in ring {
-                        if coord.len() >= 2 {
-                            found = true;
-                            min_x = min_x.min(coord[0]);
-                            min_y = min_y.min(coord[1]);
-                            max_x = max_x.max(coord[0]);
-                            max_y = max_y.max(coord[1]);
-                        }
-                    }
-                }
-            }
-            Geometry::MultiPolygon(polygons) => {
-                for rings in polygons {
-                    for ring in rings {
-                        for coord in ring {
-                            if coord.len() >= 2 {
-                                found = true;
-                                min_x = min_x.min(coord[0]);
-                                min_y = min_y.min(coord[1]);
-                                max_x = max_x.max(coord[0]);
-                                max_y = max_y.max(coord[1]);
-                            }
-                        }
-                    }
-                }
+        self.for_each_position(&mut |coord| {
+            if coord.len() >= 2 {
+                found = true;
+                min_x = min_x.min(coord[0]);
+                min_y = min_y.min(coord[1]);
+                max_x = max_x.max(coord[0]);
+                max_y = max_y.max(coord[1]);
             }
-        }
+        });
 
         found.then(|| BoundingBox::new(Vec2 { x: min_x, y: min_y }, Vec2 { x: max_x, y: max_y }))
     }
 }
 
-#[cfg(feature = "geo")]
-impl From<geo::Geometry> for Geometry {
-    fn from(value: geo::Geometry) -> Self {
-        let value = geojson::GeometryValue::from(&value);
-        match value {
-            geojson::GeometryValue::MultiPolygon { coordinates } => Geometry::MultiPolygon(
-                coordinates
-                    .into_iter()
-                    .map(|coord| {
-                        coord
-                            .into_iter()
-                            .map(|coord| {
-                                coord
-                                    .into_iter()
-                                    .map(|coord| vec![coord[0], coord[1]])
-                                    .collect()
-                            })
-                            .collect()
-                    })
-                    .collect(),
-            ),
-            geojson::GeometryValue::Polygon { coordinates } => Geometry::Polygon(
-                coordinates
-                    .into_iter()
-                    .map(|coord| {
-                        coord
-                            .into_iter()
-                            .map(|coord| vec![coord[0], coord[1]])
-                            .collect()
-                    })
-                    .collect(),
-            ),
-            _ => panic!("hit unimplmeneted conversion"),
+/// Serialized form of [`Geometry`]. A collection holds `geometries` rather than `coordinates`,
+/// so the variants can't share an adjacent tag.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "utoipa", schema(as = Geometry))]
+#[serde(tag = "type")]
+enum GeometryRepr<'a> {
+    MultiPolygon {
+        coordinates: Cow<'a, [Vec<Vec<Vec<f64>>>]>,
+    },
+    Polygon {
+        coordinates: Cow<'a, [Vec<Vec<f64>>]>,
+    },
+    Point {
+        coordinates: Cow<'a, [f64]>,
+    },
+    MultiPoint {
+        coordinates: Cow<'a, [Vec<f64>]>,
+    },
+    LineString {
+        coordinates: Cow<'a, [Vec<f64>]>,
+    },
+    MultiLineString {
+        coordinates: Cow<'a, [Vec<Vec<f64>>]>,
+    },
+    GeometryCollection {
+        #[cfg_attr(feature = "utoipa", schema(no_recursion))]
+        geometries: Cow<'a, [Geometry]>,
+    },
+}
+
+impl Serialize for Geometry {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use Cow::Borrowed;
+        match self {
+            Geometry::MultiPolygon(c) => GeometryRepr::MultiPolygon {
+                coordinates: Borrowed(c),
+            },
+            Geometry::Polygon(c) => GeometryRepr::Polygon {
+                coordinates: Borrowed(c),
+            },
+            Geometry::Point(c) => GeometryRepr::Point {
+                coordinates: Borrowed(c),
+            },
+            Geometry::MultiPoint(c) => GeometryRepr::MultiPoint {
+                coordinates: Borrowed(c),
+            },
+            Geometry::LineString(c) => GeometryRepr::LineString {
+                coordinates: Borrowed(c),
+            },
+            Geometry::MultiLineString(c) => GeometryRepr::MultiLineString {
+                coordinates: Borrowed(c),
+            },
+            Geometry::GeometryCollection(g) => GeometryRepr::GeometryCollection {
+                geometries: Borrowed(g),
+            },
         }
+        .serialize(serializer)
     }
 }
 
+impl<'de> Deserialize<'de> for Geometry {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match GeometryRepr::deserialize(deserializer)? {
+            GeometryRepr::MultiPolygon { coordinates } => {
+                Geometry::MultiPolygon(coordinates.into_owned())
+            }
+            GeometryRepr::Polygon { coordinates } => Geometry::Polygon(coordinates.into_owned()),
+            GeometryRepr::Point { coordinates } => Geometry::Point(coordinates.into_owned()),
+            GeometryRepr::MultiPoint { coordinates } => {
+                Geometry::MultiPoint(coordinates.into_owned())
+            }
+            GeometryRepr::LineString { coordinates } => {
+                Geometry::LineString(coordinates.into_owned())
+            }
+            GeometryRepr::MultiLineString { coordinates } => {
+                Geometry::MultiLineString(coordinates.into_owned())
+            }
+            GeometryRepr::GeometryCollection { geometries } => {
+                Geometry::GeometryCollection(geometries.into_owned())
+            }
+        })
+    }
+}
+
+#[cfg(feature = "utoipa")]
+impl utoipa::PartialSchema for Geometry {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        <GeometryRepr<'static> as utoipa::PartialSchema>::schema()
+    }
+}
+
+#[cfg(feature = "utoipa")]
+impl utoipa::ToSchema for Geometry {
+    fn name() -> std::borrow::Cow<'static, str> {
+        "Geometry".into()
+    }
+}
+
+/// The closed ring of an axis aligned rectangle, for building test polygons with holes
 #[cfg(test)]
-fn square_polygon(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Geometry {
-    Geometry::Polygon(vec![vec![
+fn square_ring(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Vec<Vec<f64>> {
+    vec![
         vec![min_x, min_y],
         vec![max_x, min_y],
         vec![max_x, max_y],
         vec![min_x, max_y],
         vec![min_x, min_y],
-    ]])
+    ]
+}
+
+#[cfg(test)]
+fn square_polygon(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Geometry {
+    Geometry::Polygon(vec![square_ring(min_x, min_y, max_x, max_y)])
 }
 
 #[test]
@@ -357,3 +579,62 @@ fn feature_collection_bbox_unions_all_features() {
     assert_eq!(bbox.max.x, -81.0);
     assert_eq!(bbox.max.y, 35.0);
 }
+
+#[test]
+fn geometry_collection_round_trips() {
+    let json = serde_json::json!({
+        "type": "GeometryCollection",
+        "geometries": [
+            { "type": "Point", "coordinates": [-84.39, 33.75] },
+            { "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 2.0]] }
+        ]
+    });
+    let geometry: Geometry = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(
+        geometry,
+        Geometry::GeometryCollection(vec![
+            Geometry::Point(vec![-84.39, 33.75]),
+            Geometry::LineString(vec![vec![0.0, 0.0], vec![1.0, 2.0]]),
+        ])
+    );
+    assert_eq!(serde_json::to_value(&geometry).unwrap(), json);
+
+    let bbox = geometry.bbox().unwrap();
+    assert_eq!(bbox.to_vec(), vec![-84.39, 0.0, 1.0, 33.75]);
+}
+
+#[test]
+fn feature_keeps_id_bbox_and_foreign_members() {
+    let json = serde_json::json!({
+        "type": "Feature",
+        "id": 12,
+        "bbox": [-84.39, 33.75, -84.39, 33.75],
+        "geometry": { "type": "Point", "coordinates": [-84.39, 33.75] },
+        "properties": { "name": "City Hall" },
+        "title": "Polling place"
+    });
+    let feature: GeoJsonFeature<serde_json::Value> = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(feature.id, Some(FeatureId::from(12)));
+    assert_eq!(
+        feature.bbox.as_deref(),
+        Some(&[-84.39, 33.75, -84.39, 33.75][..])
+    );
+    assert_eq!(feature.foreign_members["title"], "Polling place");
+    assert_eq!(serde_json::to_value(&feature).unwrap(), json);
+
+    let built = GeoJsonFeature::new(
+        Geometry::Point(vec![-84.39, 33.75]),
+        json["properties"].clone(),
+    )
+    .with_id(12)
+    .with_computed_bbox()
+    .with_foreign_member("title", "Polling place");
+    assert_eq!(built, feature);
+
+    let geojson: GeoJson<serde_json::Value> = serde_json::from_value(json.clone()).unwrap();
+    assert!(matches!(geojson, GeoJson::Feature(_)));
+
+    let mut wrong_type = json;
+    wrong_type["type"] = "Point".into();
+    assert!(serde_json::from_value::<GeoJsonFeature<serde_json::Value>>(wrong_type).is_err());
+}
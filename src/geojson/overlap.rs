@@ -0,0 +1,342 @@
+use geo::{
+    BooleanOps, BoundingRect, GeodesicArea, Intersects, MultiPolygon, Orient, Rect,
+    orient::Direction, unary_union,
+};
+use serde::Serialize;
+
+use crate::geojson::*;
+
+/// Intersections smaller than this many square meters are rounding noise along shared
+/// boundaries and are left out of the results.
+pub const SLIVER_AREA: f64 = 1.0;
+
+/// Part of a district in one map that falls within a district of another,
+/// as found by [`GeoJson::overlaps_with`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DistrictOverlap<'a, A, B> {
+    pub from: &'a A,
+    pub to: &'a B,
+    pub area_m2: f64,
+    /// How much of `from` lies within `to`, from 0 to 100.
+    pub percent_of_from: f64,
+    /// How much of `to` lies within `from`, from 0 to 100.
+    pub percent_of_to: f64,
+}
+
+/// Where two districts of the same map claim the same ground.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InternalOverlap<'a, T> {
+    pub first: &'a T,
+    pub second: &'a T,
+    pub area_m2: f64,
+    pub geometry: Geometry,
+}
+
+/// An area enclosed by a map's districts that none of them cover.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Gap {
+    pub area_m2: f64,
+    pub geometry: Geometry,
+}
+
+/// Overlaps and gaps within a single map, from [`GeoJson::topology`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MapTopology<'a, T> {
+    pub overlaps: Vec<InternalOverlap<'a, T>>,
+    pub gaps: Vec<Gap>,
+}
+
+impl<T> MapTopology<'_, T> {
+    /// Whether every district borders its neighbours exactly.
+    pub fn is_clean(&self) -> bool {
+        self.overlaps.is_empty() && self.gaps.is_empty()
+    }
+}
+
+/// How well a map covers a jurisdiction's outline, from [`GeoJson::coverage`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Coverage {
+    pub outline_area_m2: f64,
+    pub covered_area_m2: f64,
+    /// How much of the outline is covered by districts, from 0 to 100.
+    pub percent_covered: f64,
+    /// The parts of the outline no district covers.
+    pub uncovered: Geometry,
+    /// The parts of districts that fall outside the outline.
+    pub outside: Geometry,
+    pub outside_area_m2: f64,
+}
+
+struct District<'a, T> {
+    properties: &'a T,
+    area: MultiPolygon,
+    bounds: Option<Rect>,
+    area_m2: f64,
+}
+
+impl<T> District<'_, T> {
+    fn may_intersect<U>(&self, other: &District<'_, U>) -> bool {
+        match (self.bounds, other.bounds) {
+            (Some(bounds), Some(other)) => bounds.intersects(&other),
+            _ => false,
+        }
+    }
+}
+
+/// The polygons of a geometry, consistently wound so they can be unioned.
+fn polygons(geometry: &Geometry) -> Result<MultiPolygon, GeometryError> {
+    Ok(areal(geo::Geometry::try_from(geometry)?).orient(Direction::Default))
+}
+
+/// Points and lines have no area and give an empty multi polygon.
+fn areal(geometry: geo::Geometry) -> MultiPolygon {
+    match geometry {
+        geo::Geometry::Polygon(polygon) => MultiPolygon(vec![polygon]),
+        geo::Geometry::MultiPolygon(polygons) => polygons,
+        geo::Geometry::Rect(rect) => MultiPolygon(vec![rect.to_polygon()]),
+        geo::Geometry::Triangle(triangle) => MultiPolygon(vec![triangle.to_polygon()]),
+        geo::Geometry::GeometryCollection(geometries) => MultiPolygon(
+            geometries
+                .into_iter()
+                .flat_map(|geometry| areal(geometry).0)
+                .collect(),
+        ),
+        _ => MultiPolygon(vec![]),
+    }
+}
+
+fn districts<T>(map: &GeoJson<T>) -> Result<Vec<District<'_, T>>, GeometryError> {
+    let features = match map {
+        GeoJson::Feature(feature) => std::slice::from_ref(feature),
+        GeoJson::FeatureCollection(collection) => &collection.features,
+    };
+    features
+        .iter()
+        .map(|feature| {
+            let area = polygons(&feature.geometry)?;
+            Ok(District {
+                properties: &feature.properties,
+                bounds: area.bounding_rect(),
+                area_m2: area.geodesic_area_unsigned(),
+                area,
+            })
+        })
+        .collect()
+}
+
+fn percent(part: f64, whole: f64) -> f64 {
+    if whole > 0. {
+        (part / whole * 100.).min(100.)
+    } else {
+        0.
+    }
+}
+
+fn geometry(polygons: MultiPolygon) -> Result<Geometry, GeometryError> {
+    Geometry::try_from(geo::Geometry::MultiPolygon(polygons))
+}
+
+/// Areas are geodesic, in square meters on the WGS84 ellipsoid. Only polygons are considered,
+/// any points or lines in a map are ignored.
+impl<T> GeoJson<T> {
+    /// Every pair of districts from this map and `other` sharing some area, such as old
+    /// districts and the new ones they were redrawn into.
+    ///
+    /// Pairs are ordered by the position of the district in this map, then in `other`.
+    pub fn overlaps_with<'a, U>(
+        &'a self,
+        other: &'a GeoJson<U>,
+    ) -> Result<Vec<DistrictOverlap<'a, T, U>>, GeometryError> {
+        let (from, to) = (districts(self)?, districts(other)?);
+        let mut overlaps = Vec::new();
+        for from in &from {
+            for to in to.iter().filter(|to| from.may_intersect(to)) {
+                let area_m2 = from.area.intersection(&to.area).geodesic_area_unsigned();
+                if area_m2 >= SLIVER_AREA {
+                    overlaps.push(DistrictOverlap {
+                        from: from.properties,
+                        to: to.properties,
+                        area_m2,
+                        percent_of_from: percent(area_m2, from.area_m2),
+                        percent_of_to: percent(area_m2, to.area_m2),
+                    });
+                }
+            }
+        }
+        Ok(overlaps)
+    }
+
+    /// Districts of this map that overlap each other, and holes left between them.
+    ///
+    /// Only gaps fully enclosed by districts are found, the area around the map as a whole
+    /// is checked with [`GeoJson::coverage`].
+    pub fn topology(&self) -> Result<MapTopology<'_, T>, GeometryError> {
+        let districts = districts(self)?;
+        let mut overlaps = Vec::new();
+        for (index, first) in districts.iter().enumerate() {
+            for second in districts[index + 1..]
+                .iter()
+                .filter(|second| first.may_intersect(second))
+            {
+                let shared = first.area.intersection(&second.area);
+                let area_m2 = shared.geodesic_area_unsigned();
+                if area_m2 >= SLIVER_AREA {
+                    overlaps.push(InternalOverlap {
+                        first: first.properties,
+                        second: second.properties,
+                        area_m2,
+                        geometry: geometry(shared)?,
+                    });
+                }
+            }
+        }
+
+        let union = unary_union(districts.iter().map(|district| &district.area));
+        let mut gaps = Vec::new();
+        for ring in union.iter().flat_map(|polygon| polygon.interiors()) {
+            let hole = MultiPolygon(vec![geo::Polygon::new(ring.clone(), vec![])]);
+            let area_m2 = hole.geodesic_area_unsigned();
+            if area_m2 >= SLIVER_AREA {
+                gaps.push(Gap {
+                    area_m2,
+                    geometry: geometry(hole)?,
+                });
+            }
+        }
+
+        Ok(MapTopology { overlaps, gaps })
+    }
+
+    /// How much of a jurisdiction's `outline` this map's districts cover, and what they
+    /// cover outside of it.
+    pub fn coverage(&self, outline: &Geometry) -> Result<Coverage, GeometryError> {
+        let outline = polygons(outline)?;
+        let districts = districts(self)?;
+        let union = unary_union(districts.iter().map(|district| &district.area));
+
+        let outline_area_m2 = outline.geodesic_area_unsigned();
+        let covered_area_m2 = outline.intersection(&union).geodesic_area_unsigned();
+        let outside = union.difference(&outline);
+        Ok(Coverage {
+            outline_area_m2,
+            covered_area_m2,
+            percent_covered: percent(covered_area_m2, outline_area_m2),
+            uncovered: geometry(outline.difference(&union))?,
+            outside_area_m2: outside.geodesic_area_unsigned(),
+            outside: geometry(outside)?,
+        })
+    }
+}
+
+#[cfg(test)]
+use crate::geojson::{square_polygon, square_ring};
+
+#[cfg(test)]
+fn map(districts: impl IntoIterator<Item = (&'static str, Geometry)>) -> GeoJson<&'static str> {
+    GeoJson::many(
+        districts
+            .into_iter()
+            .map(|(name, geometry)| GeoJsonFeature::new(geometry, name)),
+    )
+}
+
+#[test]
+fn redistricting_overlaps() {
+    let old = map([
+        ("old 1", square_polygon(0., 0., 1., 1.)),
+        ("old 2", square_polygon(1., 0., 2., 1.)),
+    ]);
+    let new = map([
+        ("new 1", square_polygon(0., 0., 1.5, 1.)),
+        ("new 2", square_polygon(1.5, 0., 2., 1.)),
+    ]);
+
+    let overlaps = old.overlaps_with(&new).unwrap();
+    let pairs: Vec<_> = overlaps
+        .iter()
+        .map(|overlap| {
+            (
+                *overlap.from,
+                *overlap.to,
+                overlap.percent_of_from.round(),
+                overlap.percent_of_to.round(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        pairs,
+        [
+            ("old 1", "new 1", 100., 67.),
+            ("old 2", "new 1", 50., 33.),
+            ("old 2", "new 2", 50., 100.),
+        ]
+    );
+    // a degree square at the equator is about 12,300 km²
+    assert!(
+        (overlaps[0].area_m2 / 1e6 - 12_308.).abs() < 10.,
+        "{}",
+        overlaps[0].area_m2
+    );
+
+    let report = serde_json::to_value(&overlaps[0]).unwrap();
+    assert_eq!(report["from"], "old 1");
+    assert_eq!(report["percent_of_from"], 100.);
+}
+
+#[test]
+fn finds_overlaps_and_gaps_within_a_map() {
+    let clean = map([
+        ("west", square_polygon(0., 0., 1., 1.)),
+        ("east", square_polygon(1., 0., 2., 1.)),
+    ]);
+    assert!(clean.topology().unwrap().is_clean());
+
+    let messy = map([
+        (
+            "county",
+            Geometry::Polygon(vec![
+                square_ring(0., 0., 3., 3.),
+                square_ring(1., 1., 2., 2.),
+            ]),
+        ),
+        ("north", square_polygon(0., 2.5, 3., 4.)),
+    ]);
+    let topology = messy.topology().unwrap();
+    assert_eq!(topology.overlaps.len(), 1);
+    assert_eq!(
+        (topology.overlaps[0].first, topology.overlaps[0].second),
+        (&"county", &"north")
+    );
+    assert_eq!(topology.gaps.len(), 1);
+    assert_eq!(
+        topology.gaps[0].geometry.bbox(),
+        Some(BoundingBox::new(
+            Vec2 { x: 1., y: 1. },
+            Vec2 { x: 2., y: 2. }
+        ))
+    );
+}
+
+#[test]
+fn coverage_of_outline() {
+    let districts = map([
+        ("west", square_polygon(0., 0., 1., 1.)),
+        ("east", square_polygon(1., 0., 2.5, 1.)),
+    ]);
+    let outline = square_polygon(0., 0., 3., 1.);
+    let coverage = districts.coverage(&outline).unwrap();
+
+    assert!(
+        (coverage.percent_covered - 250. / 3.).abs() < 0.01,
+        "{}",
+        coverage.percent_covered
+    );
+    assert_eq!(coverage.outside_area_m2, 0.);
+    assert_eq!(
+        coverage.uncovered.bbox(),
+        Some(BoundingBox::new(
+            Vec2 { x: 2.5, y: 0. },
+            Vec2 { x: 3., y: 1. }
+        ))
+    );
+}
@@ -0,0 +1,504 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use ahash::{HashMap, HashSet};
+
+use crate::geojson::*;
+
+/// How vertices are chosen for removal when simplifying.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SimplifyAlgorithm {
+    /// Removes vertices closer than the tolerance to the line between the vertices kept around them.
+    #[default]
+    DouglasPeucker,
+    /// Removes vertices that form a triangle with their neighbors smaller than the tolerance squared.
+    Visvalingam,
+}
+
+/// Options for [`GeoJson::simplify`] and [`Geometry::simplify`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimplifyOptions {
+    tolerance: f64,
+    algorithm: SimplifyAlgorithm,
+    preserve_topology: bool,
+}
+
+impl SimplifyOptions {
+    /// `tolerance` is in coordinate units, so degrees for longitude and latitude.
+    pub fn new(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            algorithm: SimplifyAlgorithm::default(),
+            preserve_topology: true,
+        }
+    }
+
+    pub fn with_algorithm(mut self, algorithm: SimplifyAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// When enabled (the default), borders shared by several rings or lines are simplified
+    /// once, so neighboring districts stay aligned without gaps or overlaps.
+    pub fn with_preserve_topology(mut self, preserve_topology: bool) -> Self {
+        self.preserve_topology = preserve_topology;
+        self
+    }
+}
+
+impl<T> GeoJson<T> {
+    /// Removes vertices from every feature. Shared borders are simplified together across features.
+    ///
+    /// Rings or lines that would collapse are left as they were.
+    pub fn simplify(mut self, options: &SimplifyOptions) -> Self {
+        let mut paths = Vec::new();
+        for geometry in self.geometries_mut() {
+            geometry.collect_paths(&mut paths);
+        }
+        simplify_paths(paths, options);
+        self
+    }
+
+    /// Rounds every coordinate to `decimals` decimal places, dropping repeated vertices.
+    ///
+    /// Five decimals is roughly one meter of precision.
+    pub fn quantize(mut self, decimals: u32) -> Self {
+        for geometry in self.geometries_mut() {
+            geometry.quantize_in_place(decimals);
+        }
+        self
+    }
+
+    fn geometries_mut(&mut self) -> impl Iterator<Item = &mut Geometry> {
+        let features = match self {
+            GeoJson::Feature(feature) => std::slice::from_mut(feature),
+            GeoJson::FeatureCollection(collection) => collection.features.as_mut_slice(),
+        };
+        features.iter_mut().map(|feature| &mut feature.geometry)
+    }
+}
+
+impl Geometry {
+    /// Removes vertices from this geometry. See [`GeoJson::simplify`].
+    pub fn simplify(mut self, options: &SimplifyOptions) -> Self {
+        let mut paths = Vec::new();
+        self.collect_paths(&mut paths);
+        simplify_paths(paths, options);
+        self
+    }
+
+    /// Rounds every coordinate to `decimals` decimal places. See [`GeoJson::quantize`].
+    pub fn quantize(mut self, decimals: u32) -> Self {
+        self.quantize_in_place(decimals);
+        self
+    }
+
+    fn collect_paths<'a>(&'a mut self, paths: &mut Vec<Path<'a>>) {
+        let ring = |positions| Path {
+            positions,
+            closed: true,
+        };
+        let line = |positions| Path {
+            positions,
+            closed: false,
+        };
+        match self {
+            Geometry::Point(_) | Geometry::MultiPoint(_) => {}
+            Geometry::LineString(positions) => paths.push(line(positions)),
+            Geometry::MultiLineString(lines) => paths.extend(lines.iter_mut().map(line)),
+            Geometry::Polygon(rings) => paths.extend(rings.iter_mut().map(ring)),
+            Geometry::MultiPolygon(polygons) => {
+                paths.extend(polygons.iter_mut().flatten().map(ring))
+            }
+            Geometry::GeometryCollection(geometries) => geometries
+                .iter_mut()
+                .for_each(|geometry| geometry.collect_paths(paths)),
+        }
+    }
+
+    fn quantize_in_place(&mut self, decimals: u32) {
+        let factor = 10f64.powi(decimals as i32);
+        let round = |position: &mut Vec<f64>| {
+            position
+                .iter_mut()
+                .for_each(|value| *value = (*value * factor).round() / factor)
+        };
+        match self {
+            Geometry::Point(position) => round(position),
+            Geometry::MultiPoint(positions) => positions.iter_mut().for_each(round),
+            Geometry::GeometryCollection(geometries) => geometries
+                .iter_mut()
+                .for_each(|geometry| geometry.quantize_in_place(decimals)),
+            _ => {
+                let mut paths = Vec::new();
+                self.collect_paths(&mut paths);
+                for path in paths {
+                    path.positions.iter_mut().for_each(round);
+                    let mut deduped = path.positions.clone();
+                    deduped.dedup_by(|a, b| key(a) == key(b));
+                    if path.is_valid(&deduped) {
+                        *path.positions = deduped;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A line, or a ring whose last position repeats its first.
+struct Path<'a> {
+    positions: &'a mut Vec<Vec<f64>>,
+    closed: bool,
+}
+
+impl Path<'_> {
+    fn is_valid(&self, positions: &[Vec<f64>]) -> bool {
+        positions.len() >= if self.closed { 4 } else { 2 }
+    }
+}
+
+/// Identifies a vertex by its exact longitude and latitude.
+type Key = (u64, u64);
+
+fn key(position: &[f64]) -> Key {
+    match position {
+        [x, y, ..] => (x.to_bits(), y.to_bits()),
+        _ => (f64::NAN.to_bits(), f64::NAN.to_bits()),
+    }
+}
+
+fn simplify_paths(paths: Vec<Path<'_>>, options: &SimplifyOptions) {
+    let junctions = if options.preserve_topology {
+        find_junctions(&paths)
+    } else {
+        HashSet::default()
+    };
+
+    for path in paths {
+        let positions = path.positions.as_slice();
+        let vertices = if path.closed && positions.len() > 1 {
+            &positions[..positions.len() - 1]
+        } else {
+            positions
+        };
+
+        let mut simplified = if !path.closed {
+            split_and_simplify(vertices, &junctions, options)
+        } else if let Some(start) = vertices.iter().position(|v| junctions.contains(&key(v))) {
+            // start the ring at a junction so that every arc runs from one junction to another.
+            let mut ring = vertices[start..].to_vec();
+            ring.extend_from_slice(&vertices[..start]);
+            ring.push(ring[0].clone());
+            split_and_simplify(&ring, &junctions, options)
+        } else if options.preserve_topology && !vertices.is_empty() {
+            // the whole ring may be shared with another, so start it at the same vertex.
+            let start = (0..vertices.len())
+                .min_by_key(|index| key(&vertices[*index]))
+                .unwrap();
+            let mut ring = vertices[start..].to_vec();
+            ring.extend_from_slice(&vertices[..start]);
+            ring.push(ring[0].clone());
+            simplify_arc(&ring, options)
+        } else {
+            simplify_arc(positions, options)
+        };
+
+        if path.closed
+            && simplified.len() > 1
+            && key(&simplified[0]) != key(simplified.last().unwrap())
+        {
+            simplified.push(simplified[0].clone());
+        }
+        if path.is_valid(&simplified) {
+            *path.positions = simplified;
+        }
+    }
+}
+
+/// A vertex is a junction where the set of paths passing through it changes, which is
+/// where a shared border starts or ends.
+fn find_junctions(paths: &[Path<'_>]) -> HashSet<Key> {
+    let mut owners: HashMap<Key, Vec<usize>> = HashMap::default();
+    for (index, path) in paths.iter().enumerate() {
+        for position in path.positions.iter() {
+            let owners = owners.entry(key(position)).or_default();
+            if !owners.contains(&index) {
+                owners.push(index);
+            }
+        }
+    }
+
+    let mut junctions = HashSet::default();
+    for path in paths {
+        let positions = path.positions.as_slice();
+        let Some((first, last)) = positions.first().zip(positions.last()) else {
+            continue;
+        };
+        if !path.closed {
+            junctions.insert(key(first));
+            junctions.insert(key(last));
+        }
+        for pair in positions.windows(2) {
+            let (a, b) = (key(&pair[0]), key(&pair[1]));
+            if owners[&a] != owners[&b] {
+                junctions.insert(a);
+                junctions.insert(b);
+            }
+        }
+    }
+    junctions
+}
+
+/// Simplifies each arc between junctions on its own, keeping the junctions.
+fn split_and_simplify(
+    positions: &[Vec<f64>],
+    junctions: &HashSet<Key>,
+    options: &SimplifyOptions,
+) -> Vec<Vec<f64>> {
+    let mut simplified = Vec::with_capacity(positions.len());
+    let mut start = 0;
+    for end in 1..positions.len() {
+        if end == positions.len() - 1 || junctions.contains(&key(&positions[end])) {
+            let arc = simplify_arc(&positions[start..=end], options);
+            simplified.extend(arc.into_iter().skip(usize::from(start > 0)));
+            start = end;
+        }
+    }
+    if simplified.is_empty() {
+        simplified.extend_from_slice(positions);
+    }
+    simplified
+}
+
+/// Simplifies an arc, keeping both ends. The arc is processed in a canonical direction so
+/// that the same border walked by two neighbors gives the same result.
+fn simplify_arc(arc: &[Vec<f64>], options: &SimplifyOptions) -> Vec<Vec<f64>> {
+    let last = arc.len().saturating_sub(1);
+    let reversed =
+        arc.len() > 2 && (key(&arc[last]), key(&arc[last - 1])) < (key(&arc[0]), key(&arc[1]));
+    let ordered: Vec<&[f64]> = if reversed {
+        arc.iter().rev().map(Vec::as_slice).collect()
+    } else {
+        arc.iter().map(Vec::as_slice).collect()
+    };
+
+    let mut keep = vec![false; ordered.len()];
+    match options.algorithm {
+        SimplifyAlgorithm::DouglasPeucker => {
+            douglas_peucker(&ordered, options.tolerance, &mut keep)
+        }
+        SimplifyAlgorithm::Visvalingam => visvalingam(&ordered, options.tolerance, &mut keep),
+    }
+
+    let mut simplified: Vec<Vec<f64>> = ordered
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(position, _)| position.to_vec())
+        .collect();
+    if reversed {
+        simplified.reverse();
+    }
+    simplified
+}
+
+fn douglas_peucker(points: &[&[f64]], tolerance: f64, keep: &mut [bool]) {
+    let Some(last) = points.len().checked_sub(1) else {
+        return;
+    };
+    keep[0] = true;
+    keep[last] = true;
+
+    let mut stack = vec![(0, last)];
+    while let Some((start, end)) = stack.pop() {
+        let farthest = (start + 1..end)
+            .map(|index| {
+                let distance = segment_distance(points[index], points[start], points[end]);
+                (index, distance)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((index, distance)) = farthest
+            && distance > tolerance
+        {
+            keep[index] = true;
+            stack.push((start, index));
+            stack.push((index, end));
+        }
+    }
+}
+
+fn segment_distance(point: &[f64], start: &[f64], end: &[f64]) -> f64 {
+    let (dx, dy) = (end[0] - start[0], end[1] - start[1]);
+    let length_2 = dx * dx + dy * dy;
+    let t = if length_2 == 0. {
+        0.
+    } else {
+        (((point[0] - start[0]) * dx + (point[1] - start[1]) * dy) / length_2).clamp(0., 1.)
+    };
+    let (x, y) = (start[0] + t * dx, start[1] + t * dy);
+    ((point[0] - x).powi(2) + (point[1] - y).powi(2)).sqrt()
+}
+
+fn triangle_area(a: &[f64], b: &[f64], c: &[f64]) -> f64 {
+    ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs() / 2.
+}
+
+/// Smallest area first in a [`BinaryHeap`].
+struct Candidate {
+    area: f64,
+    index: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .area
+            .total_cmp(&self.area)
+            .then(other.index.cmp(&self.index))
+    }
+}
+
+fn visvalingam(points: &[&[f64]], tolerance: f64, keep: &mut [bool]) {
+    keep.fill(true);
+    if points.len() < 3 {
+        return;
+    }
+    let threshold = tolerance * tolerance;
+    let mut previous: Vec<usize> = (0..points.len()).map(|i| i.saturating_sub(1)).collect();
+    let mut next: Vec<usize> = (1..=points.len()).collect();
+    let mut areas = vec![f64::INFINITY; points.len()];
+    let mut heap = BinaryHeap::new();
+
+    for index in 1..points.len() - 1 {
+        areas[index] = triangle_area(points[index - 1], points[index], points[index + 1]);
+        heap.push(Candidate {
+            area: areas[index],
+            index,
+        });
+    }
+
+    while let Some(Candidate { area, index }) = heap.pop() {
+        // skip entries made stale by the removal of a neighbor.
+        if !keep[index] || area != areas[index] {
+            continue;
+        }
+        if area >= threshold {
+            break;
+        }
+        keep[index] = false;
+        let (before, after) = (previous[index], next[index]);
+        next[before] = after;
+        previous[after] = before;
+        for neighbor in [before, after] {
+            if neighbor == 0 || neighbor == points.len() - 1 {
+                continue;
+            }
+            areas[neighbor] = triangle_area(
+                points[previous[neighbor]],
+                points[neighbor],
+                points[next[neighbor]],
+            );
+            heap.push(Candidate {
+                area: areas[neighbor],
+                index: neighbor,
+            });
+        }
+    }
+}
+
+#[test]
+fn removes_vertices_within_tolerance() {
+    let line = Geometry::LineString(vec![
+        vec![0., 0.],
+        vec![1., 0.01],
+        vec![2., -0.01],
+        vec![3., 5.],
+        vec![4., 6.],
+    ]);
+    for algorithm in [
+        SimplifyAlgorithm::DouglasPeucker,
+        SimplifyAlgorithm::Visvalingam,
+    ] {
+        let options = SimplifyOptions::new(0.2).with_algorithm(algorithm);
+        assert_eq!(
+            line.clone().simplify(&options),
+            Geometry::LineString(vec![
+                vec![0., 0.],
+                vec![2., -0.01],
+                vec![3., 5.],
+                vec![4., 6.]
+            ]),
+            "{algorithm:?}"
+        );
+    }
+}
+
+#[test]
+fn shared_borders_stay_aligned() {
+    // two districts sharing a jagged border running from (1, 0) to (1, 4)
+    let border = [
+        vec![1., 0.],
+        vec![1.01, 1.],
+        vec![0.99, 2.],
+        vec![1.02, 3.],
+        vec![1., 4.],
+    ];
+    let mut west = vec![vec![0., 0.]];
+    west.extend(border.iter().cloned());
+    west.extend([vec![0., 4.], vec![0., 0.]]);
+    let mut east: Vec<_> = border.iter().rev().cloned().collect();
+    east.extend([vec![2., 0.], vec![2., 4.], vec![1., 4.]]);
+
+    let geojson = GeoJson::many([
+        GeoJsonFeature::new(Geometry::Polygon(vec![west]), "west"),
+        GeoJsonFeature::new(Geometry::Polygon(vec![east]), "east"),
+    ])
+    .simplify(&SimplifyOptions::new(0.05));
+
+    let GeoJson::FeatureCollection(collection) = geojson else {
+        unreachable!()
+    };
+    let border_of = |geometry: &Geometry| {
+        let Geometry::Polygon(rings) = geometry else {
+            unreachable!()
+        };
+        let mut border: Vec<_> = rings[0]
+            .iter()
+            .filter(|position| (position[0] - 1.).abs() < 0.1)
+            .cloned()
+            .collect();
+        border.sort_by(|a, b| a[1].total_cmp(&b[1]));
+        border.dedup();
+        border
+    };
+    let west = border_of(&collection.features[0].geometry);
+    let east = border_of(&collection.features[1].geometry);
+    assert_eq!(west, east);
+    assert_eq!(west, vec![vec![1., 0.], vec![1., 4.]]);
+}
+
+#[test]
+fn quantize_rounds_and_drops_repeated_vertices() {
+    let line = Geometry::LineString(vec![
+        vec![-84.123456, 33.987654],
+        vec![-84.123457, 33.987653],
+        vec![-84.2, 34.],
+    ]);
+    assert_eq!(
+        line.quantize(4),
+        Geometry::LineString(vec![vec![-84.1235, 33.9877], vec![-84.2, 34.]])
+    );
+}
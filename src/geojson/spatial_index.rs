@@ -0,0 +1,234 @@
+use geo::{BoundingRect, Coord, Distance, Euclidean, Intersects, MapCoords, Point, Rect};
+use rstar::{AABB, PointDistance, RTree, RTreeObject};
+
+use crate::geojson::*;
+
+/// Mean radius of the earth in meters.
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Spatial index over the features of a [`GeoJson`], answering which features contain,
+/// overlap or are nearest to a location.
+///
+/// Coordinates are treated as planar `[longitude, latitude]`, so features must not cross the
+/// antimeridian. A point on a boundary shared by two features is contained by both, and a
+/// point inside a hole is contained by neither.
+///
+/// ```no_run
+/// # async fn run(client: &peacher_sdk::prelude::PeacherClient) -> anyhow::Result<()> {
+/// use peacher_sdk::prelude::*;
+///
+/// let districts = SpatialIndex::new(GetMapGeojson(1).request(client).await?)?;
+/// for district in districts.containing(geo::Point::new(-84.39, 33.75)) {
+///     println!("{}", district.name);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct SpatialIndex<T> {
+    tree: RTree<FeatureEnvelope>,
+    features: Vec<IndexedFeature<T>>,
+}
+
+struct IndexedFeature<T> {
+    geometry: geo::Geometry,
+    properties: T,
+}
+
+/// Bounds of a feature, stored in the tree in place of the feature itself.
+struct FeatureEnvelope {
+    index: usize,
+    aabb: AABB<[f64; 2]>,
+}
+
+impl RTreeObject for FeatureEnvelope {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        self.aabb
+    }
+}
+
+impl PointDistance for FeatureEnvelope {
+    fn distance_2(&self, point: &[f64; 2]) -> f64 {
+        self.aabb.distance_2(point)
+    }
+}
+
+/// A feature found by [`SpatialIndex::nearest`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Nearest<'a, T> {
+    pub properties: &'a T,
+    /// Approximate distance in meters, zero if the point is within the feature.
+    pub distance_meters: f64,
+}
+
+impl<T> SpatialIndex<T> {
+    pub fn new(geojson: GeoJson<T>) -> Result<Self, GeometryError> {
+        let features = match geojson {
+            GeoJson::Feature(feature) => vec![feature],
+            GeoJson::FeatureCollection(collection) => collection.features,
+        };
+        let features = features
+            .into_iter()
+            .map(|feature| {
+                Ok(IndexedFeature {
+                    geometry: geo::Geometry::try_from(feature.geometry)?,
+                    properties: feature.properties,
+                })
+            })
+            .collect::<Result<Vec<_>, GeometryError>>()?;
+
+        let envelopes = features
+            .iter()
+            .enumerate()
+            .filter_map(|(index, feature)| {
+                let rect = feature.geometry.bounding_rect()?;
+                Some(FeatureEnvelope {
+                    index,
+                    aabb: AABB::from_corners(rect.min().into(), rect.max().into()),
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            tree: RTree::bulk_load(envelopes),
+            features,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.features.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    /// Properties of every feature containing `point`.
+    pub fn containing(&self, point: Point) -> impl Iterator<Item = &T> {
+        self.tree
+            .locate_in_envelope_intersecting(&AABB::from_point(point.into()))
+            .map(|envelope| &self.features[envelope.index])
+            .filter(move |feature| feature.geometry.intersects(&point))
+            .map(|feature| &feature.properties)
+    }
+
+    /// Properties of every feature that overlaps `bbox`.
+    pub fn intersecting(&self, bbox: &BoundingBox) -> impl Iterator<Item = &T> {
+        let min = Coord {
+            x: bbox.min.x,
+            y: bbox.min.y,
+        };
+        let max = Coord {
+            x: bbox.max.x,
+            y: bbox.max.y,
+        };
+        let rect = Rect::new(min, max);
+        self.tree
+            .locate_in_envelope_intersecting(&AABB::from_corners(min.into(), max.into()))
+            .map(|envelope| &self.features[envelope.index])
+            .filter(move |feature| feature.geometry.intersects(&rect))
+            .map(|feature| &feature.properties)
+    }
+
+    /// The feature closest to `point`, or one containing it.
+    ///
+    /// Distances are measured on an equirectangular projection centered on `point`, which is
+    /// accurate to within a fraction of a percent at the scale of legislative districts.
+    pub fn nearest(&self, point: Point) -> Option<Nearest<'_, T>> {
+        let scale = point.y().to_radians().cos();
+        let project = |coord: Coord| Coord {
+            x: (coord.x - point.x()) * scale,
+            y: coord.y - point.y(),
+        };
+        let origin = Point::new(0., 0.);
+
+        let mut best: Option<(usize, f64)> = None;
+        for (envelope, distance_2) in self
+            .tree
+            .nearest_neighbor_iter_with_distance_2(&point.into())
+        {
+            // projecting shrinks distances by at most `scale`, so nothing further out can be closer.
+            if best.is_some_and(|(_, best)| distance_2.sqrt() * scale > best) {
+                break;
+            }
+            let geometry = self.features[envelope.index].geometry.map_coords(project);
+            let distance = Euclidean.distance(&origin, &geometry);
+            if best.is_none_or(|(_, best)| distance < best) {
+                best = Some((envelope.index, distance));
+            }
+        }
+
+        best.map(|(index, distance)| Nearest {
+            properties: &self.features[index].properties,
+            distance_meters: distance.to_radians() * EARTH_RADIUS,
+        })
+    }
+}
+
+impl<T> TryFrom<GeoJson<T>> for SpatialIndex<T> {
+    type Error = GeometryError;
+
+    fn try_from(value: GeoJson<T>) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+use crate::geojson::{square_polygon, square_ring};
+
+#[cfg(test)]
+fn districts() -> SpatialIndex<&'static str> {
+    SpatialIndex::new(GeoJson::many([
+        // a district with a hole cut out for the city
+        GeoJsonFeature::new(
+            Geometry::Polygon(vec![
+                square_ring(-85.0, 33.0, -84.0, 34.0),
+                square_ring(-84.6, 33.4, -84.4, 33.6),
+            ]),
+            "county",
+        ),
+        GeoJsonFeature::new(square_polygon(-84.6, 33.4, -84.4, 33.6), "city"),
+        GeoJsonFeature::new(square_polygon(-80.0, 33.0, -79.0, 34.0), "coast"),
+    ]))
+    .unwrap()
+}
+
+#[test]
+fn point_in_district_respects_holes() {
+    let index = districts();
+    let found = |x, y| {
+        index
+            .containing(Point::new(x, y))
+            .copied()
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(found(-84.9, 33.1), ["county"]);
+    assert_eq!(found(-84.5, 33.5), ["city"]);
+    assert!(found(-82.0, 33.5).is_empty());
+
+    let mut shared = found(-84.6, 33.5);
+    shared.sort();
+    assert_eq!(shared, ["city", "county"]);
+}
+
+#[test]
+fn bbox_and_nearest_queries() {
+    let index = districts();
+    let bbox = BoundingBox::new(Vec2 { x: -79.5, y: 30.0 }, Vec2 { x: -70.0, y: 33.5 });
+    assert_eq!(index.intersecting(&bbox).collect::<Vec<_>>(), [&"coast"]);
+
+    let inside = index.nearest(Point::new(-79.5, 33.5)).unwrap();
+    assert_eq!(inside.properties, &"coast");
+    assert_eq!(inside.distance_meters, 0.);
+
+    // one degree of longitude east of the coast district, at 33.5°N
+    let east = index.nearest(Point::new(-78.0, 33.5)).unwrap();
+    assert_eq!(east.properties, &"coast");
+    assert!(
+        (east.distance_meters - 92_728.).abs() < 100.,
+        "{}",
+        east.distance_meters
+    );
+}
@@ -0,0 +1,584 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Write},
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use futures_core::Stream;
+use futures_util::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+
+use crate::geojson::*;
+
+/// How much is read from the underlying reader at a time.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GeoJsonStreamError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Invalid GeoJSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid GeoJSON at byte {offset}: {reason}")]
+    Syntax { offset: u64, reason: &'static str },
+    #[error("GeoJSON ended unexpectedly")]
+    UnexpectedEof,
+}
+
+#[derive(Default, Clone, Copy, PartialEq)]
+enum State {
+    #[default]
+    Start,
+    /// Within the top level object, outside of its `features`.
+    Top,
+    /// Between the elements of `features`.
+    Features,
+    Feature,
+    Done,
+}
+
+/// Splits a GeoJSON document into the bytes of each feature as chunks of it arrive.
+///
+/// Everything outside the `features` array is kept, so a lone feature or the collection's
+/// own members can be read once the document ends.
+#[derive(Default)]
+struct Scanner {
+    state: State,
+    /// The top level object, with an empty `features` array.
+    top: Vec<u8>,
+    feature: Vec<u8>,
+    ready: VecDeque<Vec<u8>>,
+    offset: u64,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    expect_key: bool,
+    key_start: Option<usize>,
+    features_key: bool,
+    awaiting_features: bool,
+    bbox: Option<Vec<f64>>,
+    foreign_members: Map<String, Value>,
+}
+
+impl Scanner {
+    fn error(&self, reason: &'static str) -> GeoJsonStreamError {
+        GeoJsonStreamError::Syntax {
+            offset: self.offset,
+            reason,
+        }
+    }
+
+    /// Tracks strings, returning whether `byte` was part of one.
+    fn string(&mut self, byte: u8) -> bool {
+        if !self.in_string {
+            return false;
+        }
+        if self.escaped {
+            self.escaped = false;
+        } else if byte == b'\\' {
+            self.escaped = true;
+        } else if byte == b'"' {
+            self.in_string = false;
+        }
+        true
+    }
+
+    fn feed(&mut self, bytes: &[u8]) -> Result<(), GeoJsonStreamError> {
+        for &byte in bytes {
+            self.byte(byte)?;
+            self.offset += 1;
+        }
+        Ok(())
+    }
+
+    fn byte(&mut self, byte: u8) -> Result<(), GeoJsonStreamError> {
+        match self.state {
+            State::Start | State::Done if byte.is_ascii_whitespace() => {}
+            State::Start if byte == b'{' => {
+                self.top.push(byte);
+                self.depth = 1;
+                self.expect_key = true;
+                self.state = State::Top;
+            }
+            State::Start => return Err(self.error("expected an object")),
+            State::Done => return Err(self.error("unexpected data after the object")),
+            State::Top if self.awaiting_features && byte.is_ascii_whitespace() => {
+                self.top.push(byte);
+            }
+            State::Top if self.awaiting_features && byte == b'[' => {
+                self.awaiting_features = false;
+                self.top.extend(b"[]");
+                self.state = State::Features;
+            }
+            State::Top => {
+                self.awaiting_features = false;
+                self.top.push(byte);
+                if self.string(byte) {
+                    if !self.in_string
+                        && let Some(start) = self.key_start.take()
+                    {
+                        self.features_key = &self.top[start..] == b"\"features\"";
+                    }
+                    return Ok(());
+                }
+                match byte {
+                    b'"' => {
+                        self.in_string = true;
+                        if self.depth == 1 && self.expect_key {
+                            self.key_start = Some(self.top.len() - 1);
+                            self.expect_key = false;
+                        }
+                    }
+                    b'{' | b'[' => self.depth += 1,
+                    b'}' | b']' => {
+                        self.depth -= 1;
+                        if self.depth == 0 {
+                            self.state = State::Done;
+                        }
+                    }
+                    b':' if self.depth == 1 => {
+                        self.awaiting_features = std::mem::take(&mut self.features_key);
+                    }
+                    b',' if self.depth == 1 => self.expect_key = true,
+                    _ => {}
+                }
+            }
+            State::Features => match byte {
+                b',' => {}
+                _ if byte.is_ascii_whitespace() => {}
+                b']' => self.state = State::Top,
+                b'{' => {
+                    self.feature.push(byte);
+                    self.depth += 1;
+                    self.state = State::Feature;
+                }
+                _ => return Err(self.error("features must be objects")),
+            },
+            State::Feature => {
+                self.feature.push(byte);
+                if self.string(byte) {
+                    return Ok(());
+                }
+                match byte {
+                    b'"' => self.in_string = true,
+                    b'{' | b'[' => self.depth += 1,
+                    b'}' | b']' => {
+                        self.depth -= 1;
+                        // the feature closes when back at the depth of the features array.
+                        if self.depth == 1 {
+                            self.ready.push_back(std::mem::take(&mut self.feature));
+                            self.state = State::Features;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn next<T: DeserializeOwned>(
+        &mut self,
+    ) -> Option<Result<GeoJsonFeature<T>, GeoJsonStreamError>> {
+        let bytes = self.ready.pop_front()?;
+        Some(serde_json::from_slice(&bytes).map_err(Into::into))
+    }
+
+    /// Checks the document is complete, and reads what was outside the `features` array.
+    /// A document that is a lone feature gives it back.
+    fn end<T: DeserializeOwned>(
+        &mut self,
+    ) -> Result<Option<GeoJsonFeature<T>>, GeoJsonStreamError> {
+        if self.state != State::Done {
+            return Err(GeoJsonStreamError::UnexpectedEof);
+        }
+        let top = std::mem::take(&mut self.top);
+        let Ok(collection) = serde_json::from_slice::<GeoJsonFeatureCollection<Value>>(&top) else {
+            return Ok(Some(serde_json::from_slice(&top)?));
+        };
+        self.bbox = collection.bbox;
+        self.foreign_members = collection.foreign_members;
+        Ok(None)
+    }
+}
+
+/// Reads the features of a GeoJSON document one at a time, so memory use depends on the size
+/// of a feature rather than of the whole document. A document holding a single feature yields
+/// just that feature.
+///
+/// ```no_run
+/// # fn run() -> Result<(), peacher_sdk::geojson::GeoJsonStreamError> {
+/// use std::fs::File;
+///
+/// use peacher_sdk::geojson::*;
+/// use serde_json::{Map, Value};
+///
+/// let reader = FeatureReader::<_, Map<String, Value>>::new(File::open("precincts.geojson")?);
+/// let mut writer = FeatureWriter::new(File::create("names.geojson")?);
+/// for feature in reader {
+///     writer.write(&feature?.map_props(|props| props.get("NAME").cloned()))?;
+/// }
+/// writer.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FeatureReader<R, T> {
+    reader: R,
+    scanner: Scanner,
+    buffer: Vec<u8>,
+    finished: bool,
+    _properties: PhantomData<fn() -> T>,
+}
+
+impl<R, T> FeatureReader<R, T> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            scanner: Scanner::default(),
+            buffer: vec![0; CHUNK_SIZE],
+            finished: false,
+            _properties: PhantomData,
+        }
+    }
+
+    /// The collection's bounding box. Only known for certain once every feature was read,
+    /// since it may come after them.
+    pub fn bbox(&self) -> Option<&[f64]> {
+        self.scanner.bbox.as_deref()
+    }
+
+    /// Members of the collection that aren't part of the GeoJSON specification. Like
+    /// [`FeatureReader::bbox`], only complete once every feature was read.
+    pub fn foreign_members(&self) -> &Map<String, Value> {
+        &self.scanner.foreign_members
+    }
+
+    fn fail(
+        &mut self,
+        error: GeoJsonStreamError,
+    ) -> Option<Result<GeoJsonFeature<T>, GeoJsonStreamError>> {
+        self.finished = true;
+        Some(Err(error))
+    }
+}
+
+impl<R: Read, T: DeserializeOwned> Iterator for FeatureReader<R, T> {
+    type Item = Result<GeoJsonFeature<T>, GeoJsonStreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(feature) = self.scanner.next() {
+                return Some(feature);
+            }
+            if self.finished {
+                return None;
+            }
+            match self.reader.read(&mut self.buffer) {
+                Ok(0) => {
+                    self.finished = true;
+                    return self.scanner.end().transpose();
+                }
+                Ok(read) => {
+                    if let Err(error) = self.scanner.feed(&self.buffer[..read]) {
+                        return self.fail(error);
+                    }
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return self.fail(error.into()),
+            }
+        }
+    }
+}
+
+/// [`FeatureReader`] for asynchronous readers, as a [`Stream`] of features.
+pub struct AsyncFeatureReader<R, T> {
+    inner: FeatureReader<R, T>,
+}
+
+impl<R, T> AsyncFeatureReader<R, T> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            inner: FeatureReader::new(reader),
+        }
+    }
+
+    /// See [`FeatureReader::bbox`].
+    pub fn bbox(&self) -> Option<&[f64]> {
+        self.inner.bbox()
+    }
+
+    /// See [`FeatureReader::foreign_members`].
+    pub fn foreign_members(&self) -> &Map<String, Value> {
+        self.inner.foreign_members()
+    }
+}
+
+impl<R: AsyncRead + Unpin, T: DeserializeOwned> Stream for AsyncFeatureReader<R, T> {
+    type Item = Result<GeoJsonFeature<T>, GeoJsonStreamError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let reader = &mut self.get_mut().inner;
+        loop {
+            if let Some(feature) = reader.scanner.next() {
+                return Poll::Ready(Some(feature));
+            }
+            if reader.finished {
+                return Poll::Ready(None);
+            }
+            match ready!(Pin::new(&mut reader.reader).poll_read(cx, &mut reader.buffer)) {
+                Ok(0) => {
+                    reader.finished = true;
+                    return Poll::Ready(reader.scanner.end().transpose());
+                }
+                Ok(read) => {
+                    if let Err(error) = reader.scanner.feed(&reader.buffer[..read]) {
+                        return Poll::Ready(reader.fail(error));
+                    }
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Poll::Ready(reader.fail(error.into())),
+            }
+        }
+    }
+}
+
+/// The parts of a feature collection around its features.
+#[derive(Default)]
+struct Framing {
+    bbox: Option<Vec<f64>>,
+    foreign_members: Map<String, Value>,
+    started: bool,
+}
+
+impl Framing {
+    fn start(&mut self, bytes: &mut Vec<u8>) -> Result<(), GeoJsonStreamError> {
+        if std::mem::replace(&mut self.started, true) {
+            bytes.push(b',');
+            return Ok(());
+        }
+        bytes.extend(br#"{"type":"FeatureCollection""#);
+        if let Some(bbox) = &self.bbox {
+            bytes.extend(br#","bbox":"#);
+            serde_json::to_writer(&mut *bytes, bbox)?;
+        }
+        for (key, value) in &self.foreign_members {
+            bytes.push(b',');
+            serde_json::to_writer(&mut *bytes, key)?;
+            bytes.push(b':');
+            serde_json::to_writer(&mut *bytes, value)?;
+        }
+        bytes.extend(br#","features":["#);
+        Ok(())
+    }
+
+    fn feature<T: Serialize>(
+        &mut self,
+        feature: &GeoJsonFeature<T>,
+    ) -> Result<Vec<u8>, GeoJsonStreamError> {
+        let mut bytes = Vec::new();
+        self.start(&mut bytes)?;
+        serde_json::to_writer(&mut bytes, feature)?;
+        Ok(bytes)
+    }
+
+    fn end(&mut self) -> Result<Vec<u8>, GeoJsonStreamError> {
+        let mut bytes = Vec::new();
+        if !self.started {
+            self.start(&mut bytes)?;
+        }
+        bytes.extend(b"]}");
+        Ok(bytes)
+    }
+}
+
+/// Writes features one at a time as a GeoJSON feature collection.
+/// Nothing is complete until [`FeatureWriter::finish`] is called.
+pub struct FeatureWriter<W> {
+    writer: W,
+    framing: Framing,
+}
+
+impl<W: Write> FeatureWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            framing: Framing::default(),
+        }
+    }
+
+    /// Sets the collection's bounding box. Ignored once a feature was written.
+    pub fn with_bbox(mut self, bbox: Vec<f64>) -> Self {
+        self.framing.bbox = Some(bbox);
+        self
+    }
+
+    /// Adds a member outside the GeoJSON specification. Ignored once a feature was written.
+    pub fn with_foreign_member(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.framing
+            .foreign_members
+            .insert(key.into(), value.into());
+        self
+    }
+
+    pub fn write<T: Serialize>(
+        &mut self,
+        feature: &GeoJsonFeature<T>,
+    ) -> Result<(), GeoJsonStreamError> {
+        let bytes = self.framing.feature(feature)?;
+        self.writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Closes the collection and flushes, giving back the writer.
+    pub fn finish(mut self) -> Result<W, GeoJsonStreamError> {
+        let bytes = self.framing.end()?;
+        self.writer.write_all(&bytes)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// [`FeatureWriter`] for asynchronous writers.
+pub struct AsyncFeatureWriter<W> {
+    writer: W,
+    framing: Framing,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncFeatureWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            framing: Framing::default(),
+        }
+    }
+
+    /// See [`FeatureWriter::with_bbox`].
+    pub fn with_bbox(mut self, bbox: Vec<f64>) -> Self {
+        self.framing.bbox = Some(bbox);
+        self
+    }
+
+    /// See [`FeatureWriter::with_foreign_member`].
+    pub fn with_foreign_member(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.framing
+            .foreign_members
+            .insert(key.into(), value.into());
+        self
+    }
+
+    pub async fn write<T: Serialize>(
+        &mut self,
+        feature: &GeoJsonFeature<T>,
+    ) -> Result<(), GeoJsonStreamError> {
+        let bytes = self.framing.feature(feature)?;
+        self.writer.write_all(&bytes).await?;
+        Ok(())
+    }
+
+    /// Closes the collection and flushes, giving back the writer.
+    pub async fn finish(mut self) -> Result<W, GeoJsonStreamError> {
+        let bytes = self.framing.end()?;
+        self.writer.write_all(&bytes).await?;
+        self.writer.flush().await?;
+        Ok(self.writer)
+    }
+}
+
+/// Hands out a single byte per read, to split the document at every possible place.
+#[cfg(test)]
+struct Trickle<'a>(&'a [u8]);
+
+#[cfg(test)]
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some((first, rest)) = self.0.split_first() else {
+            return Ok(0);
+        };
+        buf[0] = *first;
+        self.0 = rest;
+        Ok(1)
+    }
+}
+
+#[cfg(test)]
+const COLLECTION: &str = r#" {
+    "name": "precincts } [ \"quoted\"",
+    "features" : [
+        {"type": "Feature", "geometry": {"type": "Point", "coordinates": [1, 2]}, "properties": {"name": "a}\\"}},
+        {"type": "Feature", "geometry": {"type": "Point", "coordinates": [3, 4]}, "properties": {"name": "b", "features": []}}
+    ],
+    "type": "FeatureCollection",
+    "bbox": [1, 2, 3, 4]
+} "#;
+
+#[test]
+fn reads_features_one_at_a_time() {
+    let mut reader = FeatureReader::<_, Map<String, Value>>::new(Trickle(COLLECTION.as_bytes()));
+    let names: Vec<_> = reader
+        .by_ref()
+        .map(|feature| feature.unwrap().properties["name"].clone())
+        .collect();
+    assert_eq!(names, ["a}\\", "b"]);
+    assert_eq!(reader.bbox(), Some(&[1., 2., 3., 4.][..]));
+    assert_eq!(reader.foreign_members()["name"], "precincts } [ \"quoted\"");
+}
+
+#[test]
+fn reads_a_lone_feature_and_reports_errors() {
+    let feature = r#"{"type": "Feature", "geometry": {"type": "Point", "coordinates": [1, 2]}, "properties": 5}"#;
+    let features: Vec<_> = FeatureReader::<_, u32>::new(feature.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(features.len(), 1);
+    assert_eq!(features[0].properties, 5);
+
+    let truncated = &COLLECTION[..COLLECTION.find("],").unwrap()];
+    let results: Vec<_> = FeatureReader::<_, Value>::new(truncated.as_bytes()).collect();
+    assert_eq!(results.len(), 3);
+    assert!(matches!(results[2], Err(GeoJsonStreamError::UnexpectedEof)));
+
+    let mut reader = FeatureReader::<_, Value>::new(r#"{"features": [1]}"#.as_bytes());
+    assert!(matches!(
+        reader.next(),
+        Some(Err(GeoJsonStreamError::Syntax { offset: 14, .. }))
+    ));
+    assert!(reader.next().is_none());
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn writes_and_reads_asynchronously() {
+    use futures_util::{StreamExt, io::Cursor};
+
+    let features =
+        [1, 2, 3].map(|index| GeoJsonFeature::new(Geometry::Point(vec![index as f64, 0.]), index));
+    let mut writer = AsyncFeatureWriter::new(Cursor::new(Vec::new()))
+        .with_bbox(vec![1., 0., 3., 0.])
+        .with_foreign_member("name", "points");
+    for feature in &features {
+        writer.write(feature).await.unwrap();
+    }
+    let bytes = writer.finish().await.unwrap().into_inner();
+
+    let mut expected = GeoJsonFeatureCollection::new(features.clone());
+    expected.bbox = Some(vec![1., 0., 3., 0.]);
+    expected
+        .foreign_members
+        .insert("name".into(), "points".into());
+    assert_eq!(
+        serde_json::from_slice::<GeoJson<i32>>(&bytes).unwrap(),
+        GeoJson::FeatureCollection(expected)
+    );
+
+    let read: Vec<_> = AsyncFeatureReader::<_, i32>::new(Cursor::new(bytes))
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(read, features);
+
+    let empty = FeatureWriter::new(Vec::new()).finish().unwrap();
+    assert_eq!(empty, br#"{"type":"FeatureCollection","features":[]}"#);
+}
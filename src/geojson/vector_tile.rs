@@ -0,0 +1,782 @@
+use std::{f64::consts::PI, fmt};
+
+use ahash::HashMap;
+use geo::{BooleanOps, BoundingRect, Coord, MultiLineString, MultiPolygon, Rect};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::geojson::*;
+
+/// Web mercator stops at this latitude, where the projected world becomes square.
+const MAX_LATITUDE: f64 = 85.051_128_779_806_59;
+
+/// A tile in the web mercator tiling scheme used by slippy maps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TileId {
+    pub z: u8,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl fmt::Display for TileId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.z, self.x, self.y)
+    }
+}
+
+impl TileId {
+    pub fn new(z: u8, x: u32, y: u32) -> Self {
+        Self { z, x, y }
+    }
+
+    /// The tile at zoom `z` containing a `[longitude, latitude]` location.
+    pub fn containing(longitude: f64, latitude: f64, z: u8) -> Self {
+        let tiles = (1u64 << z) as f64;
+        let [x, y] = mercator(longitude, latitude);
+        let index = |value: f64| (value * tiles).floor().clamp(0., tiles - 1.) as u32;
+        Self {
+            z,
+            x: index(x),
+            y: index(y),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.z < 32 && u64::from(self.x.max(self.y)) < 1u64 << self.z
+    }
+
+    /// Bounds of the tile in longitude and latitude.
+    pub fn bounds(&self) -> BoundingBox {
+        self.bounds_with_margin(0.)
+    }
+
+    /// Bounds grown by `margin` tiles on every side.
+    fn bounds_with_margin(&self, margin: f64) -> BoundingBox {
+        let tiles = (1u64 << self.z) as f64;
+        let corner = |x: f64, y: f64| {
+            let y = (PI * (1. - 2. * y / tiles)).sinh().atan().to_degrees();
+            Vec2 {
+                x: x / tiles * 360. - 180.,
+                y,
+            }
+        };
+        let (x, y) = (self.x as f64, self.y as f64);
+        BoundingBox::new(
+            corner(x - margin, y + 1. + margin),
+            corner(x + 1. + margin, y - margin),
+        )
+    }
+}
+
+/// Position of a location on the web mercator world square, from 0 to 1 on both axes.
+/// `y` grows southwards.
+fn mercator(longitude: f64, latitude: f64) -> [f64; 2] {
+    let sin = latitude
+        .clamp(-MAX_LATITUDE, MAX_LATITUDE)
+        .to_radians()
+        .sin();
+    [
+        (longitude + 180.) / 360.,
+        0.5 - ((1. + sin) / (1. - sin)).ln() / (4. * PI),
+    ]
+}
+
+/// Options for encoding [`VectorTile`]s.
+#[derive(Debug, Clone)]
+pub struct VectorTileOptions {
+    extent: u32,
+    buffer: u32,
+    tolerance: f64,
+}
+
+impl Default for VectorTileOptions {
+    fn default() -> Self {
+        Self {
+            extent: 4096,
+            buffer: 64,
+            tolerance: 1.,
+        }
+    }
+}
+
+impl VectorTileOptions {
+    /// Size of the tile's coordinate grid, 4096 by default.
+    pub fn with_extent(mut self, extent: u32) -> Self {
+        self.extent = extent;
+        self
+    }
+
+    /// How far features extend past the tile edge, in grid units, so strokes along the edge
+    /// render without seams. 64 by default.
+    pub fn with_buffer(mut self, buffer: u32) -> Self {
+        self.buffer = buffer;
+        self
+    }
+
+    /// Simplification tolerance in grid units, 1 by default. Since the grid covers less ground
+    /// as the zoom increases, low zoom tiles are simplified more. Zero disables simplification.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VectorTileError {
+    #[error("Tile {0} does not exist")]
+    InvalidTile(TileId),
+    #[error(transparent)]
+    Geometry(#[from] GeometryError),
+    #[error("Could not serialize feature properties: {0}")]
+    Properties(#[from] serde_json::Error),
+    #[error("Feature properties must serialize to an object, found {0}")]
+    PropertiesNotAnObject(Value),
+}
+
+/// A [Mapbox Vector Tile](https://github.com/mapbox/vector-tile-spec/tree/master/2.1) built
+/// from one or more [`GeoJson`] layers.
+///
+/// Features are clipped to the tile and its buffer, simplified for the tile's zoom, and their
+/// properties are encoded as tags. Nested arrays and objects become JSON strings and null
+/// values are left out. Numeric feature ids are kept, string ids are dropped.
+///
+/// ```no_run
+/// # async fn run(client: &peacher_sdk::prelude::PeacherClient) -> anyhow::Result<()> {
+/// use peacher_sdk::prelude::*;
+///
+/// let districts = GetMapGeojson(1).request(client).await?;
+/// let bytes = VectorTile::new(TileId::new(7, 34, 51), VectorTileOptions::default())?
+///     .with_layer("districts", &districts)?
+///     .into_bytes();
+/// # Ok(())
+/// # }
+/// ```
+pub struct VectorTile {
+    tile: TileId,
+    options: VectorTileOptions,
+    message: Message,
+}
+
+impl VectorTile {
+    pub fn new(tile: TileId, options: VectorTileOptions) -> Result<Self, VectorTileError> {
+        if !tile.is_valid() {
+            return Err(VectorTileError::InvalidTile(tile));
+        }
+        Ok(Self {
+            tile,
+            options,
+            message: Message::default(),
+        })
+    }
+
+    /// Adds a layer holding the features of `geojson` that reach into this tile.
+    /// Layers without any such feature are left out.
+    pub fn with_layer<T: Serialize>(
+        mut self,
+        name: &str,
+        geojson: &GeoJson<T>,
+    ) -> Result<Self, VectorTileError> {
+        let mut layer = Layer::default();
+        let features = match geojson {
+            GeoJson::Feature(feature) => std::slice::from_ref(feature),
+            GeoJson::FeatureCollection(collection) => &collection.features,
+        };
+
+        let margin = f64::from(self.options.buffer) / f64::from(self.options.extent);
+        let bounds = self.tile.bounds_with_margin(margin);
+        let candidates: Vec<_> = features
+            .iter()
+            .filter(|feature| {
+                feature.geometry.bbox().is_some_and(|bbox| {
+                    bbox.min.x <= bounds.max.x
+                        && bbox.max.x >= bounds.min.x
+                        && bbox.min.y <= bounds.max.y
+                        && bbox.max.y >= bounds.min.y
+                })
+            })
+            .collect();
+
+        // simplifying the candidates together keeps borders between neighbours seamless.
+        let mut projected = GeoJson::many(candidates.iter().enumerate().map(|(index, feature)| {
+            let mut geometry = feature.geometry.clone();
+            self.project(&mut geometry);
+            GeoJsonFeature::new(geometry, index)
+        }));
+        if self.options.tolerance > 0. {
+            projected = projected.simplify(&SimplifyOptions::new(self.options.tolerance));
+        }
+        let projected = match projected {
+            GeoJson::Feature(feature) => vec![feature],
+            GeoJson::FeatureCollection(collection) => collection.features,
+        };
+
+        let (min, max) = (
+            -f64::from(self.options.buffer),
+            f64::from(self.options.extent + self.options.buffer),
+        );
+        let clip = Rect::new(Coord { x: min, y: min }, Coord { x: max, y: max });
+        for projected in projected {
+            let feature = candidates[projected.properties];
+            let mut parts = Parts::default();
+            parts.add(geo::Geometry::try_from(projected.geometry)?, &clip);
+            if !parts.is_empty() {
+                layer.add_feature(feature, parts)?;
+            }
+        }
+
+        if !layer.features.is_empty() {
+            self.message
+                .bytes(3, &layer.encode(name, self.options.extent));
+        }
+        Ok(self)
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.message.0
+    }
+
+    /// Moves positions from longitude and latitude to the tile's grid.
+    fn project(&self, geometry: &mut Geometry) {
+        let tiles = (1u64 << self.tile.z) as f64;
+        let extent = f64::from(self.options.extent);
+        let project = |position: &mut Vec<f64>| {
+            if let [longitude, latitude, ..] = position[..] {
+                let [x, y] = mercator(longitude, latitude);
+                *position = vec![
+                    (x * tiles - self.tile.x as f64) * extent,
+                    (y * tiles - self.tile.y as f64) * extent,
+                ];
+            }
+        };
+        match geometry {
+            Geometry::Point(position) => project(position),
+            Geometry::MultiPoint(positions) | Geometry::LineString(positions) => {
+                positions.iter_mut().for_each(project)
+            }
+            Geometry::MultiLineString(lines) | Geometry::Polygon(lines) => {
+                lines.iter_mut().flatten().for_each(project)
+            }
+            Geometry::MultiPolygon(polygons) => {
+                polygons.iter_mut().flatten().flatten().for_each(project)
+            }
+            Geometry::GeometryCollection(geometries) => geometries
+                .iter_mut()
+                .for_each(|geometry| self.project(geometry)),
+        }
+    }
+}
+
+impl<T: Serialize> GeoJson<T> {
+    /// Encodes the features reaching into `tile` as a vector tile with a single layer.
+    pub fn to_vector_tile(
+        &self,
+        layer: &str,
+        tile: TileId,
+        options: &VectorTileOptions,
+    ) -> Result<Vec<u8>, VectorTileError> {
+        Ok(VectorTile::new(tile, options.clone())?
+            .with_layer(layer, self)?
+            .into_bytes())
+    }
+}
+
+type TilePoint = [i32; 2];
+
+/// The clipped geometry of a feature, grouped by vector tile geometry type.
+#[derive(Default)]
+struct Parts {
+    points: Vec<TilePoint>,
+    lines: Vec<Vec<TilePoint>>,
+    /// Each polygon's exterior ring followed by its holes, without repeating the first point.
+    polygons: Vec<Vec<Vec<TilePoint>>>,
+}
+
+fn round(coord: Coord) -> TilePoint {
+    [coord.x.round() as i32, coord.y.round() as i32]
+}
+
+fn rounded(coords: impl Iterator<Item = Coord>) -> Vec<TilePoint> {
+    let mut points: Vec<_> = coords.map(round).collect();
+    points.dedup();
+    points
+}
+
+/// Twice the signed area of a ring, positive when clockwise on the tile's y-down grid.
+fn signed_area(ring: &[TilePoint]) -> i64 {
+    let next = ring.iter().cycle().skip(1);
+    ring.iter()
+        .zip(next)
+        .map(|(a, b)| i64::from(a[0]) * i64::from(b[1]) - i64::from(b[0]) * i64::from(a[1]))
+        .sum()
+}
+
+fn within(rect: Rect, clip: &Rect) -> bool {
+    rect.min().x >= clip.min().x
+        && rect.min().y >= clip.min().y
+        && rect.max().x <= clip.max().x
+        && rect.max().y <= clip.max().y
+}
+
+impl Parts {
+    fn is_empty(&self) -> bool {
+        self.points.is_empty() && self.lines.is_empty() && self.polygons.is_empty()
+    }
+
+    fn add(&mut self, geometry: geo::Geometry, clip: &Rect) {
+        match geometry {
+            geo::Geometry::Point(point) => {
+                if within(Rect::new(point.0, point.0), clip) {
+                    self.points.push(round(point.0));
+                }
+            }
+            geo::Geometry::MultiPoint(points) => points
+                .into_iter()
+                .for_each(|point| self.add(point.into(), clip)),
+            geo::Geometry::Line(line) => self.add_lines(MultiLineString::from(line), clip),
+            geo::Geometry::LineString(line) => self.add_lines(MultiLineString::from(line), clip),
+            geo::Geometry::MultiLineString(lines) => self.add_lines(lines, clip),
+            geo::Geometry::Polygon(polygon) => self.add_polygons(polygon.into(), clip),
+            geo::Geometry::MultiPolygon(polygons) => self.add_polygons(polygons, clip),
+            geo::Geometry::Rect(rect) => self.add_polygons(rect.to_polygon().into(), clip),
+            geo::Geometry::Triangle(triangle) => {
+                self.add_polygons(triangle.to_polygon().into(), clip)
+            }
+            geo::Geometry::GeometryCollection(geometries) => geometries
+                .into_iter()
+                .for_each(|geometry| self.add(geometry, clip)),
+        }
+    }
+
+    fn add_lines(&mut self, lines: MultiLineString, clip: &Rect) {
+        let lines = clip.to_polygon().clip(&lines, false);
+        self.lines.extend(
+            lines
+                .into_iter()
+                .map(|line| rounded(line.into_inner().into_iter()))
+                .filter(|line| line.len() > 1),
+        );
+    }
+
+    fn add_polygons(&mut self, polygons: MultiPolygon, clip: &Rect) {
+        // districts are usually far smaller than a tile, and need no clipping.
+        let polygons = match polygons.bounding_rect() {
+            Some(bounds) if within(bounds, clip) => polygons,
+            _ => polygons.intersection(&clip.to_polygon()),
+        };
+
+        let ring = |ring: &geo::LineString, exterior: bool| {
+            let mut points = rounded(ring.coords().copied());
+            if points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+            let area = signed_area(&points);
+            if points.len() < 3 || area == 0 {
+                return None;
+            }
+            if (area > 0) != exterior {
+                points.reverse();
+            }
+            Some(points)
+        };
+        for polygon in &polygons {
+            let Some(exterior) = ring(polygon.exterior(), true) else {
+                continue;
+            };
+            let mut rings = vec![exterior];
+            rings.extend(
+                polygon
+                    .interiors()
+                    .iter()
+                    .filter_map(|interior| ring(interior, false)),
+            );
+            self.polygons.push(rings);
+        }
+    }
+}
+
+/// Geometry command stream of a vector tile feature.
+#[derive(Default)]
+struct Commands {
+    data: Vec<u32>,
+    cursor: TilePoint,
+}
+
+impl Commands {
+    const MOVE_TO: u32 = 1;
+    const LINE_TO: u32 = 2;
+    const CLOSE_PATH: u32 = 7;
+
+    fn command(&mut self, id: u32, count: usize) {
+        self.data.push(id | (count as u32) << 3);
+    }
+
+    fn point(&mut self, point: TilePoint) {
+        let zigzag = |value: i32| ((value << 1) ^ (value >> 31)) as u32;
+        self.data.push(zigzag(point[0] - self.cursor[0]));
+        self.data.push(zigzag(point[1] - self.cursor[1]));
+        self.cursor = point;
+    }
+
+    fn path(&mut self, points: &[TilePoint], closed: bool) {
+        self.command(Self::MOVE_TO, 1);
+        self.point(points[0]);
+        self.command(Self::LINE_TO, points.len() - 1);
+        points[1..].iter().for_each(|point| self.point(*point));
+        if closed {
+            self.command(Self::CLOSE_PATH, 1);
+        }
+    }
+}
+
+/// A property value, hashable so each distinct value is stored once per layer.
+#[derive(PartialEq, Eq, Hash)]
+enum TileValue {
+    String(String),
+    /// Bits of the float.
+    Double(u64),
+    Uint(u64),
+    Sint(i64),
+    Bool(bool),
+}
+
+impl TileValue {
+    fn from_json(value: Value) -> Option<Self> {
+        Some(match value {
+            Value::Null => return None,
+            Value::Bool(value) => TileValue::Bool(value),
+            Value::String(value) => TileValue::String(value),
+            Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+                (Some(value), _) => TileValue::Uint(value),
+                (None, Some(value)) => TileValue::Sint(value),
+                _ => TileValue::Double(number.as_f64()?.to_bits()),
+            },
+            nested @ (Value::Array(_) | Value::Object(_)) => TileValue::String(nested.to_string()),
+        })
+    }
+
+    fn encode(&self) -> Message {
+        let mut message = Message::default();
+        match self {
+            TileValue::String(value) => message.bytes(1, value.as_bytes()),
+            TileValue::Double(bits) => message.double(3, f64::from_bits(*bits)),
+            TileValue::Uint(value) => message.uint(5, *value),
+            TileValue::Sint(value) => message.uint(6, ((value << 1) ^ (value >> 63)) as u64),
+            TileValue::Bool(value) => message.uint(7, u64::from(*value)),
+        }
+        message
+    }
+}
+
+#[derive(Default)]
+struct Layer {
+    keys: HashMap<String, u32>,
+    values: HashMap<TileValue, u32>,
+    features: Vec<Message>,
+}
+
+impl Layer {
+    fn add_feature<T: Serialize>(
+        &mut self,
+        feature: &GeoJsonFeature<T>,
+        parts: Parts,
+    ) -> Result<(), VectorTileError> {
+        let properties = match serde_json::to_value(&feature.properties)? {
+            Value::Object(properties) => properties,
+            Value::Null => Default::default(),
+            other => return Err(VectorTileError::PropertiesNotAnObject(other)),
+        };
+        let mut tags = Vec::new();
+        for (key, value) in properties {
+            let Some(value) = TileValue::from_json(value) else {
+                continue;
+            };
+            let next_key = self.keys.len() as u32;
+            tags.push(*self.keys.entry(key).or_insert(next_key));
+            let next_value = self.values.len() as u32;
+            tags.push(*self.values.entry(value).or_insert(next_value));
+        }
+        let id = match &feature.id {
+            Some(FeatureId::Number(number)) => number.as_u64(),
+            _ => None,
+        };
+
+        // vector tile features have a single geometry type.
+        let mut push = |kind: u64, commands: Commands| {
+            let mut message = Message::default();
+            if let Some(id) = id {
+                message.uint(1, id);
+            }
+            message.packed(2, &tags);
+            message.uint(3, kind);
+            message.packed(4, &commands.data);
+            self.features.push(message);
+        };
+        if !parts.points.is_empty() {
+            let mut commands = Commands::default();
+            commands.command(Commands::MOVE_TO, parts.points.len());
+            parts.points.iter().for_each(|point| commands.point(*point));
+            push(1, commands);
+        }
+        if !parts.lines.is_empty() {
+            let mut commands = Commands::default();
+            parts
+                .lines
+                .iter()
+                .for_each(|line| commands.path(line, false));
+            push(2, commands);
+        }
+        if !parts.polygons.is_empty() {
+            let mut commands = Commands::default();
+            parts
+                .polygons
+                .iter()
+                .flatten()
+                .for_each(|ring| commands.path(ring, true));
+            push(3, commands);
+        }
+        Ok(())
+    }
+
+    fn encode(self, name: &str, extent: u32) -> Vec<u8> {
+        let mut message = Message::default();
+        message.uint(15, 2);
+        message.bytes(1, name.as_bytes());
+        for feature in &self.features {
+            message.bytes(2, &feature.0);
+        }
+        let mut keys: Vec<_> = self.keys.into_iter().collect();
+        keys.sort_by_key(|(_, index)| *index);
+        for (key, _) in keys {
+            message.bytes(3, key.as_bytes());
+        }
+        let mut values: Vec<_> = self.values.into_iter().collect();
+        values.sort_by_key(|(_, index)| *index);
+        for (value, _) in values {
+            message.bytes(4, &value.encode().0);
+        }
+        message.uint(5, extent.into());
+        message.0
+    }
+}
+
+/// Protocol buffers encoding, just enough for the vector tile schema.
+#[derive(Default)]
+struct Message(Vec<u8>);
+
+impl Message {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn key(&mut self, field: u32, wire_type: u8) {
+        self.varint(u64::from(field) << 3 | u64::from(wire_type));
+    }
+
+    fn uint(&mut self, field: u32, value: u64) {
+        self.key(field, 0);
+        self.varint(value);
+    }
+
+    fn double(&mut self, field: u32, value: f64) {
+        self.key(field, 1);
+        self.0.extend(value.to_le_bytes());
+    }
+
+    fn bytes(&mut self, field: u32, bytes: &[u8]) {
+        self.key(field, 2);
+        self.varint(bytes.len() as u64);
+        self.0.extend(bytes);
+    }
+
+    fn packed(&mut self, field: u32, values: &[u32]) {
+        if values.is_empty() {
+            return;
+        }
+        let mut packed = Message::default();
+        values
+            .iter()
+            .for_each(|value| packed.varint(u64::from(*value)));
+        self.bytes(field, &packed.0);
+    }
+}
+
+/// Reads the fields of a protocol buffers message, length delimited ones as bytes.
+#[cfg(test)]
+fn read_message(mut bytes: &[u8]) -> Vec<(u32, Result<u64, &[u8]>)> {
+    fn varint(bytes: &mut &[u8]) -> u64 {
+        let mut value = 0;
+        for shift in (0..).step_by(7) {
+            let byte = bytes[0];
+            *bytes = &bytes[1..];
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte < 0x80 {
+                break;
+            }
+        }
+        value
+    }
+    let mut fields = Vec::new();
+    while !bytes.is_empty() {
+        let key = varint(&mut bytes);
+        let value = match key & 7 {
+            0 => Ok(varint(&mut bytes)),
+            1 => {
+                let (value, rest) = bytes.split_at(8);
+                bytes = rest;
+                Ok(u64::from_le_bytes(value.try_into().unwrap()))
+            }
+            2 => {
+                let length = varint(&mut bytes) as usize;
+                let (value, rest) = bytes.split_at(length);
+                bytes = rest;
+                Err(value)
+            }
+            wire_type => panic!("unexpected wire type {wire_type}"),
+        };
+        fields.push(((key >> 3) as u32, value));
+    }
+    fields
+}
+
+#[cfg(test)]
+fn read_packed(mut bytes: &[u8]) -> Vec<u32> {
+    let mut values = Vec::new();
+    while !bytes.is_empty() {
+        let mut value = 0u32;
+        for shift in (0..).step_by(7) {
+            let byte = bytes[0];
+            bytes = &bytes[1..];
+            value |= u32::from(byte & 0x7f) << shift;
+            if byte < 0x80 {
+                break;
+            }
+        }
+        values.push(value);
+    }
+    values
+}
+
+#[test]
+fn tile_coordinates() {
+    let atlanta = TileId::containing(-84.39, 33.75, 10);
+    assert_eq!(atlanta, TileId::new(10, 271, 409));
+    assert_eq!(atlanta.to_string(), "10/271/409");
+
+    let bounds = TileId::new(1, 1, 0).bounds();
+    assert_eq!((bounds.min.x, bounds.max.x), (0., 180.));
+    assert_eq!(bounds.min.y, 0.);
+    assert!((bounds.max.y - MAX_LATITUDE).abs() < 1e-9);
+
+    assert!(matches!(
+        VectorTile::new(TileId::new(1, 2, 0), VectorTileOptions::default()),
+        Err(VectorTileError::InvalidTile(_))
+    ));
+}
+
+#[test]
+fn encodes_clipped_and_simplified_polygon() {
+    // the top edge has redundant vertices that simplification removes.
+    let mut ring = vec![vec![-10., -10.], vec![90., -10.], vec![90., 40.]];
+    ring.extend((1..9).rev().map(|step| vec![step as f64 * 10., 40.]));
+    ring.extend([vec![-10., 40.], vec![-10., -10.]]);
+    let districts = GeoJson::many([
+        GeoJsonFeature::new(
+            Geometry::Polygon(vec![ring]),
+            serde_json::json!({ "name": "District 1", "population": 700000, "share": 0.5, "note": null }),
+        )
+        .with_id(7),
+        GeoJsonFeature::new(
+            Geometry::Point(vec![-120., -45.]),
+            serde_json::json!({ "name": "Elsewhere" }),
+        ),
+    ]);
+
+    let options = VectorTileOptions::default().with_buffer(0);
+    let bytes = districts
+        .to_vector_tile("districts", TileId::new(1, 1, 0), &options)
+        .unwrap();
+
+    let tile = read_message(&bytes);
+    assert_eq!(tile.len(), 1);
+    let (3, Err(layer)) = tile[0] else {
+        panic!("expected a layer, found {:?}", tile[0]);
+    };
+    let layer = read_message(layer);
+    let field = |number: u32| {
+        layer
+            .iter()
+            .filter(move |(field, _)| *field == number)
+            .map(|(_, value)| *value)
+    };
+    assert_eq!(field(15).collect::<Vec<_>>(), [Ok(2)]);
+    assert_eq!(field(1).collect::<Vec<_>>(), [Err(&b"districts"[..])]);
+    assert_eq!(field(5).collect::<Vec<_>>(), [Ok(4096)]);
+    assert_eq!(
+        field(3).collect::<Vec<_>>(),
+        [Err(&b"name"[..]), Err(b"population"), Err(b"share")]
+    );
+    let values: Vec<_> = field(4)
+        .map(|value| read_message(value.unwrap_err()))
+        .collect();
+    assert_eq!(
+        values,
+        [
+            vec![(1, Err(&b"District 1"[..]))],
+            vec![(5, Ok(700000))],
+            vec![(3, Ok(0.5f64.to_bits()))],
+        ]
+    );
+
+    // the point lies outside the tile, leaving the polygon as the only feature.
+    let features: Vec<_> = field(2).collect();
+    assert_eq!(features.len(), 1);
+    let feature = read_message(features[0].unwrap_err());
+    assert_eq!(feature[0], (1, Ok(7)));
+    assert_eq!(read_packed(feature[1].1.unwrap_err()), [0, 0, 1, 1, 2, 2]);
+    assert_eq!(feature[2], (3, Ok(3)));
+
+    // the polygon is cut at the equator and the prime meridian, 40°N is at y = 3101.
+    let unzigzag = |value: u32| (value >> 1) as i32 ^ -((value & 1) as i32);
+    let commands = read_packed(feature[3].1.unwrap_err());
+    assert_eq!(commands[0], 1 | 1 << 3);
+    assert_eq!(commands[3], 2 | 3 << 3);
+    assert_eq!(commands[10], 7 | 1 << 3);
+    let mut cursor = [0, 0];
+    let ring: Vec<_> = [1, 4, 6, 8]
+        .into_iter()
+        .map(|index| {
+            cursor[0] += unzigzag(commands[index]);
+            cursor[1] += unzigzag(commands[index + 1]);
+            cursor
+        })
+        .collect();
+    assert!(signed_area(&ring) > 0, "{ring:?}");
+    let mut corners = ring.clone();
+    corners.sort();
+    assert_eq!(corners, [[0, 3101], [0, 4096], [2048, 3101], [2048, 4096]]);
+}
+
+#[test]
+fn empty_layers_are_left_out() {
+    let districts = GeoJson::one(GeoJsonFeature::new(
+        Geometry::Polygon(vec![vec![
+            vec![-100., 30.],
+            vec![-99., 30.],
+            vec![-99., 31.],
+            vec![-100., 30.],
+        ]]),
+        serde_json::json!({}),
+    ));
+    let bytes = districts
+        .to_vector_tile(
+            "districts",
+            TileId::containing(0., 0., 6),
+            &VectorTileOptions::default(),
+        )
+        .unwrap();
+    assert!(bytes.is_empty());
+}
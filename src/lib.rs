@@ -2,6 +2,7 @@ pub mod client;
 #[cfg(feature = "external")]
 pub mod external;
 pub mod geojson;
+pub mod money;
 pub mod paginate;
 pub mod params;
 pub mod peanut;
@@ -20,13 +21,14 @@ pub mod tippytappy;
 #[cfg(feature = "cli")]
 pub mod cli;
 #[cfg(feature = "cli")]
-pub use cli::{cli, cli_with_client};
+pub use cli::{cli, cli_with_client, cli_with_commands};
 
 pub mod prelude {
     pub use crate::client::*;
     #[cfg(feature = "external")]
     pub use crate::external::*;
     pub use crate::geojson::*;
+    pub use crate::money::*;
     pub use crate::paginate::*;
     pub use crate::params::*;
     pub use crate::peanut::prelude::*;
@@ -0,0 +1,303 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    #[error("`{0}` is not a three letter currency code")]
+    InvalidCurrency(String),
+    #[error("`{0}` is not an amount of money")]
+    InvalidAmount(String),
+    #[error("{currency} amounts have at most {exponent} decimal places")]
+    TooPrecise { currency: Currency, exponent: u32 },
+    #[error("cannot combine {0} and {1} amounts")]
+    CurrencyMismatch(Currency, Currency),
+    #[error("amount overflowed")]
+    Overflow,
+}
+
+/// An ISO 4217 currency, stored lowercase as Stripe expects.
+///
+/// Serializes as its lowercase code (`"usd"`) and displays as its uppercase code (`USD`).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema), schema(value_type = String, example = "usd"))]
+pub struct Currency([u8; 3]);
+
+/// Currencies without a minor unit. See <https://docs.stripe.com/currencies#zero-decimal>
+const ZERO_DECIMAL: &[&str] = &[
+    "bif", "clp", "djf", "gnf", "jpy", "kmf", "krw", "mga", "pyg", "rwf", "ugx", "vnd", "vuv",
+    "xaf", "xof", "xpf",
+];
+
+/// Currencies with a thousandth minor unit. See <https://docs.stripe.com/currencies#three-decimal>
+const THREE_DECIMAL: &[&str] = &["bhd", "jod", "kwd", "omr", "tnd"];
+
+impl Currency {
+    pub const USD: Currency = Currency(*b"usd");
+    pub const EUR: Currency = Currency(*b"eur");
+    pub const GBP: Currency = Currency(*b"gbp");
+    pub const CAD: Currency = Currency(*b"cad");
+    pub const JPY: Currency = Currency(*b"jpy");
+
+    /// Accepts a code in any case, e.g. `usd` or `USD`.
+    pub fn new(code: &str) -> Result<Self, MoneyError> {
+        match code.as_bytes() {
+            [a, b, c] if code.bytes().all(|byte| byte.is_ascii_alphabetic()) => Ok(Self([
+                a.to_ascii_lowercase(),
+                b.to_ascii_lowercase(),
+                c.to_ascii_lowercase(),
+            ])),
+            _ => Err(MoneyError::InvalidCurrency(code.to_string())),
+        }
+    }
+
+    /// The lowercase code
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("currency codes are ascii")
+    }
+
+    /// Number of decimal places of the minor unit: 0 for JPY, 3 for KWD, 2 for most others.
+    pub fn exponent(&self) -> u32 {
+        if ZERO_DECIMAL.contains(&self.as_str()) {
+            0
+        } else if THREE_DECIMAL.contains(&self.as_str()) {
+            3
+        } else {
+            2
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.as_str().to_ascii_uppercase())
+    }
+}
+
+impl fmt::Debug for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Currency({})", self.as_str())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = MoneyError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for Currency {
+    type Error = MoneyError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(&value)
+    }
+}
+
+impl From<Currency> for String {
+    fn from(value: Currency) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+/// An amount of money in the smallest unit of its currency: cents for USD, yen for JPY.
+///
+/// Displays and parses as `12.34 USD`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Money {
+    pub minor_units: i64,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub const fn new(minor_units: i64, currency: Currency) -> Self {
+        Self {
+            minor_units,
+            currency,
+        }
+    }
+
+    pub const fn zero(currency: Currency) -> Self {
+        Self::new(0, currency)
+    }
+
+    /// Parses an amount in major units, e.g. `"12.34"` for 1234 cents.
+    pub fn parse_major(amount: &str, currency: Currency) -> Result<Self, MoneyError> {
+        let invalid = || MoneyError::InvalidAmount(amount.to_string());
+        let (negative, unsigned) = match amount.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, amount),
+        };
+        let (major, minor) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let digits = |s: &str| s.bytes().all(|byte| byte.is_ascii_digit());
+        if major.is_empty() || !digits(major) || !digits(minor) {
+            return Err(invalid());
+        }
+        if unsigned.contains('.') && minor.is_empty() {
+            return Err(invalid());
+        }
+
+        let exponent = currency.exponent();
+        if minor.len() > exponent as usize {
+            return Err(MoneyError::TooPrecise { currency, exponent });
+        }
+        let scale = 10i64.pow(exponent);
+        let major: i64 = major.parse().map_err(|_| MoneyError::Overflow)?;
+        let minor: i64 = if minor.is_empty() {
+            0
+        } else {
+            minor.parse::<i64>().map_err(|_| invalid())? * 10i64.pow(exponent - minor.len() as u32)
+        };
+
+        let minor_units = major
+            .checked_mul(scale)
+            .and_then(|units| units.checked_add(minor))
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Self::new(
+            if negative { -minor_units } else { minor_units },
+            currency,
+        ))
+    }
+
+    pub fn checked_add(self, rhs: Money) -> Result<Money, MoneyError> {
+        let currency = self.same_currency(rhs)?;
+        self.minor_units
+            .checked_add(rhs.minor_units)
+            .map(|units| Money::new(units, currency))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    pub fn checked_sub(self, rhs: Money) -> Result<Money, MoneyError> {
+        let currency = self.same_currency(rhs)?;
+        self.minor_units
+            .checked_sub(rhs.minor_units)
+            .map(|units| Money::new(units, currency))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    pub fn checked_mul(self, factor: i64) -> Result<Money, MoneyError> {
+        self.minor_units
+            .checked_mul(factor)
+            .map(|units| Money::new(units, self.currency))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.minor_units == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.minor_units < 0
+    }
+
+    fn same_currency(self, rhs: Money) -> Result<Currency, MoneyError> {
+        if self.currency == rhs.currency {
+            Ok(self.currency)
+        } else {
+            Err(MoneyError::CurrencyMismatch(self.currency, rhs.currency))
+        }
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let exponent = self.currency.exponent();
+        let sign = if self.is_negative() { "-" } else { "" };
+        let units = self.minor_units.unsigned_abs();
+        let scale = 10u64.pow(exponent);
+        if exponent == 0 {
+            write!(f, "{sign}{units} {}", self.currency)
+        } else {
+            write!(
+                f,
+                "{sign}{}.{:0width$} {}",
+                units / scale,
+                units % scale,
+                self.currency,
+                width = exponent as usize
+            )
+        }
+    }
+}
+
+impl FromStr for Money {
+    type Err = MoneyError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (amount, currency) = s
+            .trim()
+            .rsplit_once(' ')
+            .ok_or_else(|| MoneyError::InvalidAmount(s.to_string()))?;
+        Self::parse_major(amount.trim(), currency.parse()?)
+    }
+}
+
+#[test]
+fn formats_by_currency_exponent() {
+    assert_eq!(Money::new(1234, Currency::USD).to_string(), "12.34 USD");
+    assert_eq!(Money::new(-5, Currency::USD).to_string(), "-0.05 USD");
+    assert_eq!(Money::new(500, Currency::JPY).to_string(), "500 JPY");
+    let kwd = Currency::new("KWD").unwrap();
+    assert_eq!(Money::new(1500, kwd).to_string(), "1.500 KWD");
+    assert_eq!(
+        Money::new(i64::MIN, Currency::USD).to_string(),
+        "-92233720368547758.08 USD"
+    );
+}
+
+#[test]
+fn parses_major_units() {
+    assert_eq!("12.34 USD".parse(), Ok(Money::new(1234, Currency::USD)));
+    assert_eq!("12.3 usd".parse(), Ok(Money::new(1230, Currency::USD)));
+    assert_eq!("-7 EUR".parse(), Ok(Money::new(-700, Currency::EUR)));
+    assert_eq!("500 JPY".parse(), Ok(Money::new(500, Currency::JPY)));
+    assert_eq!(
+        "1.5 JPY".parse::<Money>(),
+        Err(MoneyError::TooPrecise {
+            currency: Currency::JPY,
+            exponent: 0
+        })
+    );
+    assert!("1.005 USD".parse::<Money>().is_err());
+    assert!("1. USD".parse::<Money>().is_err());
+    assert!("12.34".parse::<Money>().is_err());
+    assert!("1e3 USD".parse::<Money>().is_err());
+    assert!("1 DOLLARS".parse::<Money>().is_err());
+    assert_eq!(
+        "99999999999999999999 USD".parse::<Money>(),
+        Err(MoneyError::Overflow)
+    );
+}
+
+#[test]
+fn arithmetic_is_checked() {
+    let ten = Money::new(1000, Currency::USD);
+    assert_eq!(ten.checked_add(ten), Ok(Money::new(2000, Currency::USD)));
+    assert_eq!(
+        ten.checked_sub(Money::new(1500, Currency::USD)),
+        Ok(Money::new(-500, Currency::USD))
+    );
+    assert_eq!(ten.checked_mul(3), Ok(Money::new(3000, Currency::USD)));
+    assert_eq!(
+        ten.checked_add(Money::new(1, Currency::EUR)),
+        Err(MoneyError::CurrencyMismatch(Currency::USD, Currency::EUR))
+    );
+    assert_eq!(
+        Money::new(i64::MAX, Currency::USD).checked_add(Money::new(1, Currency::USD)),
+        Err(MoneyError::Overflow)
+    );
+}
+
+#[test]
+fn serializes_currency_as_a_lowercase_code() {
+    let money = Money::new(1234, Currency::new("USD").unwrap());
+    let json = serde_json::to_value(money).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({ "minor_units": 1234, "currency": "usd" })
+    );
+    assert_eq!(serde_json::from_value::<Money>(json).unwrap(), money);
+    assert!(serde_json::from_value::<Currency>(serde_json::json!("us")).is_err());
+}
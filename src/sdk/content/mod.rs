@@ -161,6 +161,12 @@ pub enum SetContentRequest {
     Markdown(String),
 }
 
+impl From<DocumentView> for SetContentRequest {
+    fn from(value: DocumentView) -> Self {
+        Self::Document(value)
+    }
+}
+
 /// Handler to update content (author or admin)
 pub struct UpdateSummary {
     legislation_id: i32,
@@ -8,6 +8,11 @@ use serde::{Deserialize, Serialize};
 use std::{borrow::Cow, path::Path};
 use url::Url;
 
+#[cfg(feature = "map_preview")]
+mod preview;
+#[cfg(feature = "map_preview")]
+pub use preview::*;
+
 /// List all maps.
 pub struct ListMaps;
 
@@ -59,7 +64,7 @@ impl GetHandler for GetMapGeojson {
 /// Configuration for how to extract district metadata from map file records.
 ///
 /// Applies to both shapefiles and GeoJSON files.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct FieldMapping {
     /// Field name for district name (e.g., "NAME", "NAMELSAD")
     pub name_field: Option<String>,
@@ -0,0 +1,730 @@
+use std::io::{Cursor, Read};
+
+use ahash::{HashMap, HashSet};
+use geo::{Centroid, Validation};
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use crate::{
+    geojson::{GeoJson, Geometry},
+    sdk::{DistrictPreview, FieldMapping, MapPreviewResponse, UploadMap},
+};
+
+/// Field names commonly holding a district's name, in order of preference.
+const NAME_FIELDS: &[&str] = &["NAMELSAD", "NAME", "DISTRICT_NAME", "DIST_NAME", "LABEL"];
+
+/// Field names commonly holding a district's external id, in order of preference.
+const EXTERNAL_ID_FIELDS: &[&str] = &[
+    "AFFGEOID",
+    "GEOID",
+    "GEOID20",
+    "GEOID10",
+    "DISTRICT_ID",
+    "DISTRICTID",
+    "ID",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum MapFileError {
+    #[error("Unsupported map file {0}, expected .geojson, .json or a zipped shapefile")]
+    UnsupportedFormat(String),
+    #[error("Invalid GeoJSON: {0}")]
+    GeoJson(#[from] serde_json::Error),
+    #[error("Invalid zip archive: {0}")]
+    Zip(#[from] zip::result::ZipError),
+    #[error("Invalid shapefile: {0}")]
+    Shapefile(String),
+    #[error("Could not read map file: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MapFileFormat {
+    GeoJson,
+    /// A `.zip` holding a `.shp` and its `.dbf`.
+    Shapefile,
+}
+
+/// A problem in a map file that would give a broken or surprising map once uploaded.
+#[derive(Debug, Clone, PartialEq, thiserror::Error, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MapIssue {
+    #[error("The file has no features")]
+    Empty,
+    #[error("Field {0} does not exist")]
+    UnknownField(String),
+    #[error("Feature {feature} has no geometry")]
+    MissingGeometry { feature: usize },
+    #[error("Feature {feature} is a {kind}, districts must be polygons")]
+    UnsupportedGeometry { feature: usize, kind: &'static str },
+    #[error("Feature {feature} is invalid: {reason}")]
+    InvalidGeometry { feature: usize, reason: String },
+    #[error("Feature {feature} has no name")]
+    MissingName { feature: usize },
+    #[error("Feature {feature} has no external id")]
+    MissingExternalId { feature: usize },
+    #[error("External id {external_id} is used by features {features:?}")]
+    DuplicateExternalId {
+        external_id: String,
+        features: Vec<usize>,
+    },
+}
+
+/// A district read from a map file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InspectedDistrict {
+    pub name: Option<String>,
+    pub external_id: Option<String>,
+    #[serde(serialize_with = "serialize_position")]
+    pub centroid: Option<geo::Point>,
+}
+
+/// Serializes a point as a GeoJSON `[longitude, latitude]` position.
+fn serialize_position<S: Serializer>(
+    point: &Option<geo::Point>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    point
+        .map(|point| [point.x(), point.y()])
+        .serialize(serializer)
+}
+
+/// The result of reading a map file locally, mirroring what the server reports after
+/// [`UploadMap`], so mistakes can be fixed before uploading.
+///
+/// Features are numbered from zero in the order they appear in the file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MapInspection {
+    pub format: MapFileFormat,
+    /// Every attribute field found on the features, sorted by name.
+    pub fields: Vec<String>,
+    /// The mapping this file most likely needs.
+    pub suggested_mapping: FieldMapping,
+    /// The mapping used to read names and external ids: the one given, or the suggestion.
+    pub mapping: FieldMapping,
+    pub districts: Vec<InspectedDistrict>,
+    pub issues: Vec<MapIssue>,
+}
+
+struct Record {
+    geometry: Option<Geometry>,
+    attributes: Map<String, Value>,
+}
+
+impl UploadMap {
+    /// Reads the file this would upload. See [`MapInspection::inspect`].
+    pub fn inspect(&self) -> Result<MapInspection, MapFileError> {
+        MapInspection::inspect(
+            &self.file_name,
+            &self.file_data,
+            self.field_mapping.as_ref(),
+        )
+    }
+
+    /// What the server would preview for this upload, computed locally.
+    pub fn preview(&self) -> Result<MapPreviewResponse, MapFileError> {
+        Ok(self.inspect()?.preview(&self.name))
+    }
+}
+
+impl MapInspection {
+    /// Reads a GeoJSON or zipped shapefile, picking the format from `file_name`.
+    ///
+    /// Without a `mapping`, names and external ids are read with the suggested one.
+    pub fn inspect(
+        file_name: &str,
+        data: &[u8],
+        mapping: Option<&FieldMapping>,
+    ) -> Result<Self, MapFileError> {
+        let extension = file_name
+            .rsplit_once('.')
+            .map(|(_, extension)| extension.to_ascii_lowercase());
+        let (format, records) = match extension.as_deref() {
+            Some("geojson" | "json") => (MapFileFormat::GeoJson, read_geojson(data)?),
+            Some("zip") => (MapFileFormat::Shapefile, read_zipped_shapefile(data)?),
+            _ => return Err(MapFileError::UnsupportedFormat(file_name.to_string())),
+        };
+        Ok(Self::from_records(format, records, mapping))
+    }
+
+    fn from_records(
+        format: MapFileFormat,
+        records: Vec<Record>,
+        mapping: Option<&FieldMapping>,
+    ) -> Self {
+        let fields: Vec<String> = records
+            .iter()
+            .flat_map(|record| record.attributes.keys().cloned())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        let suggested_mapping = FieldMapping {
+            name_field: suggest_field(&fields, &records, NAME_FIELDS, false),
+            external_id_field: suggest_field(&fields, &records, EXTERNAL_ID_FIELDS, true),
+        };
+        let mapping = mapping.unwrap_or(&suggested_mapping).clone();
+
+        let mut issues = Vec::new();
+        if records.is_empty() {
+            issues.push(MapIssue::Empty);
+        }
+        for field in [&mapping.name_field, &mapping.external_id_field]
+            .into_iter()
+            .flatten()
+        {
+            if !fields.contains(field) {
+                issues.push(MapIssue::UnknownField(field.clone()));
+            }
+        }
+
+        let mut districts = Vec::with_capacity(records.len());
+        let mut external_ids: HashMap<String, Vec<usize>> = HashMap::default();
+        for (feature, record) in records.iter().enumerate() {
+            let attribute = |field: &Option<String>| {
+                field
+                    .as_ref()
+                    .and_then(|field| record.attributes.get(field))
+                    .and_then(value_text)
+            };
+            let name = attribute(&mapping.name_field);
+            let external_id = attribute(&mapping.external_id_field);
+            if name.is_none() && mapping.name_field.is_some() {
+                issues.push(MapIssue::MissingName { feature });
+            }
+            match &external_id {
+                Some(id) => external_ids.entry(id.clone()).or_default().push(feature),
+                None if mapping.external_id_field.is_some() => {
+                    issues.push(MapIssue::MissingExternalId { feature })
+                }
+                None => {}
+            }
+
+            districts.push(InspectedDistrict {
+                name,
+                external_id,
+                centroid: check_geometry(feature, record.geometry.as_ref(), &mut issues),
+            });
+        }
+
+        let mut duplicates: Vec<_> = external_ids
+            .into_iter()
+            .filter(|(_, features)| features.len() > 1)
+            .collect();
+        duplicates.sort_by_key(|(_, features)| features[0]);
+        issues.extend(duplicates.into_iter().map(|(external_id, features)| {
+            MapIssue::DuplicateExternalId {
+                external_id,
+                features,
+            }
+        }));
+
+        Self {
+            format,
+            fields,
+            suggested_mapping,
+            mapping,
+            districts,
+            issues,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Builds the report the server returns when previewing a map. Districts are numbered
+    /// from 1, and ones without a name are labelled by their external id or position.
+    pub fn preview(&self, name: impl Into<String>) -> MapPreviewResponse {
+        let districts: Vec<_> = self
+            .districts
+            .iter()
+            .enumerate()
+            .map(|(index, district)| DistrictPreview {
+                id: index as i32 + 1,
+                name: district
+                    .name
+                    .clone()
+                    .or_else(|| district.external_id.clone())
+                    .unwrap_or_else(|| format!("District {}", index + 1)),
+                centroid_lat: district.centroid.map_or(0., |point| point.y()),
+                centroid_lon: district.centroid.map_or(0., |point| point.x()),
+            })
+            .collect();
+        MapPreviewResponse {
+            name: name.into(),
+            district_count: districts.len(),
+            districts,
+        }
+    }
+}
+
+/// Records problems with a district's geometry, returning its centroid if it has one.
+fn check_geometry(
+    feature: usize,
+    geometry: Option<&Geometry>,
+    issues: &mut Vec<MapIssue>,
+) -> Option<geo::Point> {
+    let Some(geometry) = geometry else {
+        issues.push(MapIssue::MissingGeometry { feature });
+        return None;
+    };
+    if !matches!(geometry, Geometry::Polygon(_) | Geometry::MultiPolygon(_)) {
+        issues.push(MapIssue::UnsupportedGeometry {
+            feature,
+            kind: geometry.type_name(),
+        });
+    }
+    let geometry = match geo::Geometry::try_from(geometry) {
+        Ok(geometry) => geometry,
+        Err(error) => {
+            issues.push(MapIssue::InvalidGeometry {
+                feature,
+                reason: error.to_string(),
+            });
+            return None;
+        }
+    };
+    issues.extend(geometry.validation_errors().into_iter().map(|error| {
+        MapIssue::InvalidGeometry {
+            feature,
+            reason: error.to_string(),
+        }
+    }));
+    geometry.centroid()
+}
+
+fn value_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.trim().to_string()).filter(|text| !text.is_empty()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+/// Picks the first well-known field present on every record, falling back to the first field
+/// whose values are all present and distinct. Names must also contain more than digits.
+fn suggest_field(
+    fields: &[String],
+    records: &[Record],
+    candidates: &[&str],
+    numeric: bool,
+) -> Option<String> {
+    let values = |field: &str| {
+        records
+            .iter()
+            .map(|record| record.attributes.get(field).and_then(value_text))
+            .collect::<Option<Vec<_>>>()
+    };
+
+    let known = candidates.iter().find_map(|candidate| {
+        fields
+            .iter()
+            .find(|field| field.eq_ignore_ascii_case(candidate))
+            .filter(|field| values(field).is_some())
+    });
+    if let Some(field) = known {
+        return Some(field.clone());
+    }
+
+    fields
+        .iter()
+        .find(|field| {
+            values(field).is_some_and(|values| {
+                let distinct = values.iter().collect::<HashSet<_>>().len() == values.len();
+                let textual = numeric || values.iter().any(|value| value.parse::<f64>().is_err());
+                !values.is_empty() && distinct && textual
+            })
+        })
+        .cloned()
+}
+
+fn read_geojson(data: &[u8]) -> Result<Vec<Record>, MapFileError> {
+    let geojson: GeoJson<Option<Map<String, Value>>> = serde_json::from_slice(data)?;
+    let features = match geojson {
+        GeoJson::Feature(feature) => vec![feature],
+        GeoJson::FeatureCollection(collection) => collection.features,
+    };
+    Ok(features
+        .into_iter()
+        .map(|feature| Record {
+            geometry: Some(feature.geometry),
+            attributes: feature.properties.unwrap_or_default(),
+        })
+        .collect())
+}
+
+fn read_zipped_shapefile(data: &[u8]) -> Result<Vec<Record>, MapFileError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))?;
+    let shp_name = archive
+        .file_names()
+        .find(|name| name.to_ascii_lowercase().ends_with(".shp") && !name.starts_with("__MACOSX"))
+        .map(str::to_string)
+        .ok_or_else(|| MapFileError::Shapefile("archive has no .shp file".into()))?;
+    let stem = &shp_name[..shp_name.len() - 4];
+    let dbf_name = archive
+        .file_names()
+        .find(|name| {
+            name.len() == shp_name.len()
+                && name.starts_with(stem)
+                && name.to_ascii_lowercase().ends_with(".dbf")
+        })
+        .map(str::to_string);
+
+    let mut read = |name: &str| -> Result<Vec<u8>, MapFileError> {
+        let mut bytes = Vec::new();
+        archive.by_name(name)?.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    };
+    let geometries = shapefile::read_shapes(&read(&shp_name)?)?;
+    let mut attributes = match dbf_name {
+        Some(name) => shapefile::read_attributes(&read(&name)?)?,
+        None => Vec::new(),
+    };
+    attributes.resize_with(geometries.len(), Map::new);
+
+    Ok(geometries
+        .into_iter()
+        .zip(attributes)
+        .map(|(geometry, attributes)| Record {
+            geometry,
+            attributes,
+        })
+        .collect())
+}
+
+/// Minimal readers for the `.shp` and `.dbf` parts of an ESRI shapefile.
+mod shapefile {
+    use serde_json::{Map, Value};
+
+    use super::MapFileError;
+    use crate::geojson::Geometry;
+
+    fn error(msg: &str) -> MapFileError {
+        MapFileError::Shapefile(msg.to_string())
+    }
+
+    fn bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N], MapFileError> {
+        data.get(offset..offset + N)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| error("unexpected end of file"))
+    }
+
+    fn i32_be(data: &[u8], offset: usize) -> Result<i32, MapFileError> {
+        bytes(data, offset).map(i32::from_be_bytes)
+    }
+
+    fn i32_le(data: &[u8], offset: usize) -> Result<i32, MapFileError> {
+        bytes(data, offset).map(i32::from_le_bytes)
+    }
+
+    fn f64_le(data: &[u8], offset: usize) -> Result<f64, MapFileError> {
+        bytes(data, offset).map(f64::from_le_bytes)
+    }
+
+    fn count(data: &[u8], offset: usize) -> Result<usize, MapFileError> {
+        usize::try_from(i32_le(data, offset)?).map_err(|_| error("negative count"))
+    }
+
+    fn points(data: &[u8], offset: usize, count: usize) -> Result<Vec<Vec<f64>>, MapFileError> {
+        (0..count)
+            .map(|index| {
+                let offset = offset + index * 16;
+                Ok(vec![f64_le(data, offset)?, f64_le(data, offset + 8)?])
+            })
+            .collect()
+    }
+
+    /// Reads the parts of a polyline or polygon record.
+    fn parts(record: &[u8]) -> Result<Vec<Vec<Vec<f64>>>, MapFileError> {
+        let (num_parts, num_points) = (count(record, 36)?, count(record, 40)?);
+        let points = points(record, 44 + num_parts * 4, num_points)?;
+        let mut starts = (0..num_parts)
+            .map(|part| count(record, 44 + part * 4))
+            .collect::<Result<Vec<_>, _>>()?;
+        starts.push(num_points);
+        starts
+            .windows(2)
+            .map(|pair| {
+                points
+                    .get(pair[0]..pair[1])
+                    .map(<[_]>::to_vec)
+                    .ok_or_else(|| error("part index out of range"))
+            })
+            .collect()
+    }
+
+    /// Every shape in a `.shp` file, `None` for null shapes.
+    pub(super) fn read_shapes(data: &[u8]) -> Result<Vec<Option<Geometry>>, MapFileError> {
+        if i32_be(data, 0)? != 9994 {
+            return Err(error("missing file code"));
+        }
+        let mut shapes = Vec::new();
+        let mut offset = 100;
+        while offset + 8 <= data.len() {
+            let length = usize::try_from(i32_be(data, offset + 4)?)
+                .map_err(|_| error("negative record length"))?
+                * 2;
+            let record = data
+                .get(offset + 8..offset + 8 + length)
+                .ok_or_else(|| error("unexpected end of file"))?;
+            offset += 8 + length;
+
+            // Z and M variants only add data after the x and y coordinates.
+            shapes.push(match i32_le(record, 0)? {
+                0 => None,
+                1 | 11 | 21 => Some(Geometry::Point(vec![
+                    f64_le(record, 4)?,
+                    f64_le(record, 12)?,
+                ])),
+                3 | 13 | 23 => Some(Geometry::MultiLineString(parts(record)?)),
+                5 | 15 | 25 => Some(polygon(parts(record)?)),
+                8 | 18 | 28 => Some(Geometry::MultiPoint(points(
+                    record,
+                    40,
+                    count(record, 36)?,
+                )?)),
+                shape_type => return Err(error(&format!("unknown shape type {shape_type}"))),
+            });
+        }
+        Ok(shapes)
+    }
+
+    /// Twice the signed area of a ring, positive when counterclockwise.
+    fn signed_area(ring: &[Vec<f64>]) -> f64 {
+        ring.windows(2)
+            .map(|pair| pair[0][0] * pair[1][1] - pair[1][0] * pair[0][1])
+            .sum()
+    }
+
+    fn ring_contains(ring: &[Vec<f64>], point: &[f64]) -> bool {
+        let mut inside = false;
+        for pair in ring.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            if (a[1] > point[1]) != (b[1] > point[1])
+                && point[0] < (b[0] - a[0]) * (point[1] - a[1]) / (b[1] - a[1]) + a[0]
+            {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// Shapefiles list clockwise exterior rings, each followed by its counterclockwise holes.
+    /// GeoJSON expects the opposite winding, so rings are reversed.
+    fn polygon(rings: Vec<Vec<Vec<f64>>>) -> Geometry {
+        let mut polygons: Vec<Vec<Vec<Vec<f64>>>> = Vec::new();
+        for mut ring in rings {
+            let hole = signed_area(&ring) > 0.;
+            ring.reverse();
+            let owner = match ring.first() {
+                Some(point) if hole => polygons
+                    .iter_mut()
+                    .rev()
+                    .find(|polygon| ring_contains(&polygon[0], point)),
+                _ => None,
+            };
+            match owner {
+                Some(polygon) => polygon.push(ring),
+                None => polygons.push(vec![ring]),
+            }
+        }
+        if polygons.len() == 1 {
+            Geometry::Polygon(polygons.remove(0))
+        } else {
+            Geometry::MultiPolygon(polygons)
+        }
+    }
+
+    /// The attributes of every record in a `.dbf` file.
+    pub(super) fn read_attributes(data: &[u8]) -> Result<Vec<Map<String, Value>>, MapFileError> {
+        let records = u32::from_le_bytes(bytes(data, 4)?) as usize;
+        let header_length = u16::from_le_bytes(bytes(data, 8)?) as usize;
+        let record_length = u16::from_le_bytes(bytes(data, 10)?) as usize;
+
+        let mut fields = Vec::new();
+        let mut offset = 32;
+        while offset + 32 <= header_length && data.get(offset) != Some(&0x0d) {
+            let descriptor = data
+                .get(offset..offset + 32)
+                .ok_or_else(|| error("unexpected end of file"))?;
+            let name = descriptor[..11]
+                .split(|byte| *byte == 0)
+                .next()
+                .unwrap_or_default();
+            fields.push((
+                String::from_utf8_lossy(name).into_owned(),
+                descriptor[11],
+                descriptor[16] as usize,
+            ));
+            offset += 32;
+        }
+
+        (0..records)
+            .map(|index| {
+                let start = header_length + index * record_length;
+                let record = data
+                    .get(start..start + record_length)
+                    .ok_or_else(|| error("unexpected end of file"))?;
+                // the first byte marks deleted records, which still line up with a shape.
+                let mut position = 1;
+                let mut attributes = Map::new();
+                for (name, kind, length) in &fields {
+                    let raw = record
+                        .get(position..position + length)
+                        .ok_or_else(|| error("field extends past its record"))?;
+                    position += length;
+                    let text = String::from_utf8_lossy(raw);
+                    let text = text.trim();
+                    let value = match kind {
+                        _ if text.is_empty() => Value::Null,
+                        b'N' | b'F' => text
+                            .parse::<i64>()
+                            .map(Value::from)
+                            .or_else(|_| text.parse::<f64>().map(Value::from))
+                            .unwrap_or(Value::Null),
+                        b'L' => match text {
+                            "T" | "t" | "Y" | "y" => Value::Bool(true),
+                            "F" | "f" | "N" | "n" => Value::Bool(false),
+                            _ => Value::Null,
+                        },
+                        _ => Value::String(text.to_string()),
+                    };
+                    attributes.insert(name.clone(), value);
+                }
+                Ok(attributes)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+fn district(name: &str, geoid: &str, ring: &[[f64; 2]]) -> Value {
+    serde_json::json!({
+        "type": "Feature",
+        "geometry": { "type": "Polygon", "coordinates": [ring] },
+        "properties": { "NAMELSAD": name, "GEOID": geoid, "ALAND": 1000 },
+    })
+}
+
+#[test]
+fn inspects_geojson_districts() {
+    let square = [[0., 0.], [1., 0.], [1., 1.], [0., 1.], [0., 0.]];
+    let bowtie = [[2., 0.], [3., 1.], [3., 0.], [2., 1.], [2., 0.]];
+    let file = serde_json::json!({
+        "type": "FeatureCollection",
+        "features": [
+            district("District 1", "1301", &square),
+            district("District 2", "1302", &bowtie),
+            district("District 3", "1301", &square),
+        ],
+    });
+    let upload = UploadMap::new(
+        "Georgia",
+        "districts.geojson",
+        file.to_string().into_bytes(),
+    );
+    let inspection = upload.inspect().unwrap();
+
+    assert_eq!(inspection.format, MapFileFormat::GeoJson);
+    assert_eq!(inspection.fields, ["ALAND", "GEOID", "NAMELSAD"]);
+    assert_eq!(
+        inspection.suggested_mapping.name_field.as_deref(),
+        Some("NAMELSAD")
+    );
+    assert_eq!(
+        inspection.suggested_mapping.external_id_field.as_deref(),
+        Some("GEOID")
+    );
+    assert!(
+        inspection
+            .issues
+            .iter()
+            .any(|issue| matches!(issue, MapIssue::InvalidGeometry { feature: 1, .. }))
+    );
+    assert!(inspection.issues.contains(&MapIssue::DuplicateExternalId {
+        external_id: "1301".into(),
+        features: vec![0, 2],
+    }));
+
+    let json = serde_json::to_value(&inspection).unwrap();
+    assert_eq!(json["format"], "geo_json");
+    assert_eq!(
+        json["districts"][0]["centroid"],
+        serde_json::json!([0.5, 0.5])
+    );
+    assert_eq!(
+        json["issues"][1],
+        serde_json::json!({ "duplicate_external_id": { "external_id": "1301", "features": [0, 2] } })
+    );
+
+    let preview = upload.preview().unwrap();
+    assert_eq!(preview.name, "Georgia");
+    assert_eq!(preview.district_count, 3);
+    assert_eq!(preview.districts[0].name, "District 1");
+    assert_eq!(preview.districts[0].centroid_lat, 0.5);
+    assert_eq!(preview.districts[0].centroid_lon, 0.5);
+}
+
+#[test]
+fn inspects_zipped_shapefile() {
+    use std::io::Write;
+
+    // one polygon record: a clockwise square
+    let ring: [[f64; 2]; 5] = [[0., 0.], [0., 2.], [2., 2.], [2., 0.], [0., 0.]];
+    let mut record = Vec::new();
+    record.extend(5i32.to_le_bytes());
+    for value in [0., 0., 2., 2.] {
+        record.extend(f64::to_le_bytes(value));
+    }
+    record.extend(1i32.to_le_bytes());
+    record.extend((ring.len() as i32).to_le_bytes());
+    record.extend(0i32.to_le_bytes());
+    for [x, y] in ring {
+        record.extend(x.to_le_bytes());
+        record.extend(y.to_le_bytes());
+    }
+    let mut shp = vec![0; 100];
+    shp[..4].copy_from_slice(&9994i32.to_be_bytes());
+    shp.extend(1i32.to_be_bytes());
+    shp.extend((record.len() as i32 / 2).to_be_bytes());
+    shp.extend(record);
+
+    // a NAME character field and a GEOID numeric field
+    let mut dbf = vec![3, 0, 0, 0];
+    dbf.extend(1u32.to_le_bytes());
+    dbf.extend((32u16 * 3 + 1).to_le_bytes());
+    dbf.extend((1u16 + 10 + 4).to_le_bytes());
+    dbf.resize(32, 0);
+    for (name, kind, length) in [("NAME", b'C', 10u8), ("GEOID", b'N', 4)] {
+        let mut descriptor = [0u8; 32];
+        descriptor[..name.len()].copy_from_slice(name.as_bytes());
+        descriptor[11] = kind;
+        descriptor[16] = length;
+        dbf.extend(descriptor);
+    }
+    dbf.push(0x0d);
+    dbf.extend(b" Downtown  1304");
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, bytes) in [("wards/wards.shp", &shp), ("wards/wards.dbf", &dbf)] {
+        zip.start_file(name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(bytes).unwrap();
+    }
+    let zip = zip.finish().unwrap().into_inner();
+
+    let inspection = MapInspection::inspect("wards.zip", &zip, None).unwrap();
+    assert_eq!(inspection.format, MapFileFormat::Shapefile);
+    assert_eq!(inspection.fields, ["GEOID", "NAME"]);
+    assert!(inspection.is_valid(), "{:?}", inspection.issues);
+    assert_eq!(
+        inspection.districts,
+        [InspectedDistrict {
+            name: Some("Downtown".into()),
+            external_id: Some("1304".into()),
+            centroid: Some(geo::Point::new(1., 1.)),
+        }]
+    );
+}
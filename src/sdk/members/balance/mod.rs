@@ -1,18 +1,24 @@
+mod requests;
+pub use requests::*;
+
 use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
 use url::Url;
 use uuid::Uuid;
 
+use crate::money::Money;
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct AddFundsRequest {
-    pub amount: i32,
+    pub amount: Money,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct AccountBalanceResponse {
-    pub amount: i32,
+    pub amount: Money,
     pub created_at: DateTime<FixedOffset>,
     pub updated_at: DateTime<FixedOffset>,
 }
@@ -23,3 +29,55 @@ pub struct AddAccountFundsResponse {
     pub checkout_url: Url,
     pub transaction_id: Uuid,
 }
+
+impl AddAccountFundsResponse {
+    /// The handler to poll the status of the started transaction
+    pub fn transaction(&self) -> GetBalanceTransaction {
+        GetBalanceTransaction(self.transaction_id)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct BalanceTransactionView {
+    pub id: Uuid,
+    pub kind: BalanceTransactionKind,
+    pub status: BalanceTransactionStatus,
+    /// Positive when funds were added to the balance, negative when they were taken out
+    pub amount: Money,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+#[derive(Clone, Copy, EnumString, Display, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceTransactionKind {
+    /// Funds added through a checkout
+    Deposit,
+    /// Funds spent from the balance
+    Spend,
+    /// A deposit returned to the payment method
+    Refund,
+}
+
+#[derive(Clone, Copy, EnumString, Display, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceTransactionStatus {
+    /// Waiting on the checkout to be paid
+    Pending,
+    Succeeded,
+    Failed,
+    /// The checkout was abandoned
+    Expired,
+}
+
+impl BalanceTransactionStatus {
+    /// Whether the status can still change
+    pub fn is_final(&self) -> bool {
+        !matches!(self, Self::Pending)
+    }
+}
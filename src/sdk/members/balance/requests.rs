@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{money::Money, paginated, prelude::*};
+
+/// Get the balance of the current account
+pub struct GetAccountBalance;
+
+impl GetHandler for GetAccountBalance {
+    type ResponseBody = AccountBalanceResponse;
+
+    fn path(&self) -> Cow<'_, str> {
+        "/api/account/balance".into()
+    }
+}
+
+/// Start a checkout adding funds to the current account.
+///
+/// Send the member to `checkout_url`; the balance changes once the transaction succeeds,
+/// which [`AddAccountFundsResponse::transaction`] polls for.
+pub struct AddAccountFunds {
+    body: AddFundsRequest,
+}
+
+impl AddAccountFunds {
+    pub fn new(amount: Money) -> Self {
+        Self {
+            body: AddFundsRequest { amount },
+        }
+    }
+}
+
+impl Handler for AddAccountFunds {
+    type ResponseBody = AddAccountFundsResponse;
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn path(&self) -> Cow<'_, str> {
+        "/api/account/balance/funds".into()
+    }
+
+    fn request_body(&self, builder: BodyBuilder) -> BodyBuilder {
+        builder.json(&self.body)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "utoipa", into_params(parameter_in = Query))]
+pub struct BalanceTransactionParams {
+    /// Filter by status
+    pub status: Option<BalanceTransactionStatus>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+paginated!(BalanceTransactionParams);
+
+/// List the balance transactions of the current account, newest first
+#[derive(Default)]
+pub struct ListBalanceTransactions {
+    pub params: BalanceTransactionParams,
+}
+
+impl ListBalanceTransactions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn page(mut self, page: u64) -> Self {
+        self.params.page = Some(page);
+        self
+    }
+
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.params.page_size = Some(page_size);
+        self
+    }
+
+    pub fn with_status(mut self, status: BalanceTransactionStatus) -> Self {
+        self.params.status = Some(status);
+        self
+    }
+}
+
+impl GetHandler for ListBalanceTransactions {
+    type ResponseBody = Paginated<BalanceTransactionView>;
+
+    fn path(&self) -> Cow<'_, str> {
+        "/api/account/balance/transactions".into()
+    }
+
+    fn params(&self) -> impl SdkParams {
+        self.params.clone()
+    }
+}
+
+/// Get a balance transaction of the current account.
+///
+/// Request it until [`BalanceTransactionStatus::is_final`] to follow a checkout.
+pub struct GetBalanceTransaction(pub Uuid);
+
+impl GetHandler for GetBalanceTransaction {
+    type ResponseBody = BalanceTransactionView;
+
+    fn path(&self) -> Cow<'_, str> {
+        format!("/api/account/balance/transactions/{}", self.0).into()
+    }
+}
+
+#[test]
+fn balance_handlers_paths_and_bodies() {
+    use crate::money::Currency;
+
+    let add = AddAccountFunds::new(Money::new(2500, Currency::USD));
+    assert_eq!(Handler::path(&add), "/api/account/balance/funds");
+    assert_eq!(
+        serde_json::to_value(&add.body).unwrap(),
+        serde_json::json!({ "amount": { "minor_units": 2500, "currency": "usd" } })
+    );
+
+    let started = AddAccountFundsResponse {
+        checkout_url: "https://checkout.stripe.com/c/pay/cs_test_123"
+            .parse()
+            .unwrap(),
+        transaction_id: Uuid::nil(),
+    };
+    assert_eq!(
+        GetHandler::path(&started.transaction()),
+        "/api/account/balance/transactions/00000000-0000-0000-0000-000000000000"
+    );
+
+    let list = ListBalanceTransactions::new()
+        .with_status(BalanceTransactionStatus::Pending)
+        .page(2)
+        .page_size(50);
+    assert_eq!(
+        serde_qs::to_string(&list.params).unwrap(),
+        "status=pending&page=2&page_size=50"
+    );
+    assert!(!BalanceTransactionStatus::Pending.is_final());
+    assert!(BalanceTransactionStatus::Expired.is_final());
+}
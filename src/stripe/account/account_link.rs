@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+use crate::prelude::{BodyBuilder, Handler, Method};
+
+/// Parameters accepted by `POST /v1/account_links`.
+///
+/// Send the account holder to the returned [`AccountLink::url`]. They come back to
+/// `return_url` when done, or to `refresh_url` if the link expired or was already used,
+/// where a fresh link should be created.
+///
+/// See: <https://docs.stripe.com/api/account_links/create>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateAccountLink {
+    pub account: String,
+    pub refresh_url: String,
+    pub return_url: String,
+    #[serde(rename = "type")]
+    pub link_type: AccountLinkType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collect: Option<AccountLinkCollect>,
+}
+
+impl CreateAccountLink {
+    pub fn onboarding(
+        account: impl Into<String>,
+        refresh_url: impl Into<String>,
+        return_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            account: account.into(),
+            refresh_url: refresh_url.into(),
+            return_url: return_url.into(),
+            link_type: AccountLinkType::AccountOnboarding,
+            collect: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum AccountLinkType {
+    AccountOnboarding,
+    AccountUpdate,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum AccountLinkCollect {
+    CurrentlyDue,
+    EventuallyDue,
+}
+
+/// A single-use, short-lived URL to Stripe's hosted onboarding.
+///
+/// See: <https://docs.stripe.com/api/account_links/object>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AccountLink {
+    pub object: String,
+    pub created: i64,
+    pub expires_at: i64,
+    pub url: String,
+}
+
+impl Handler for CreateAccountLink {
+    type ResponseBody = AccountLink;
+    fn method(&self) -> Method {
+        Method::Post
+    }
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        "/v1/account_links".into()
+    }
+    fn request_body(&self, builder: BodyBuilder) -> BodyBuilder {
+        builder.qs(self)
+    }
+}
@@ -0,0 +1,88 @@
+use ahash::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{BodyBuilder, Handler, Method},
+    stripe::account::{Account, AccountType, BusinessProfile, BusinessType},
+};
+
+/// Parameters accepted by `POST /v1/accounts`.
+///
+/// See: <https://docs.stripe.com/api/accounts/create>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateAccount {
+    #[serde(rename = "type")]
+    pub account_type: AccountType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_profile: Option<BusinessProfile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_type: Option<BusinessType>,
+    /// Keyed by capability name, e.g. `card_payments` or `transfers`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub capabilities: HashMap<String, CapabilityRequest>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CapabilityRequest {
+    pub requested: bool,
+}
+
+impl CreateAccount {
+    pub fn new(account_type: AccountType) -> Self {
+        Self {
+            account_type,
+            business_profile: None,
+            business_type: None,
+            capabilities: HashMap::default(),
+            country: None,
+            default_currency: None,
+            email: None,
+            metadata: HashMap::default(),
+        }
+    }
+
+    pub fn with_email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    pub fn with_country(mut self, country: impl Into<String>) -> Self {
+        self.country = Some(country.into());
+        self
+    }
+
+    /// Requests a capability, e.g. `transfers` to receive payouts from the platform.
+    pub fn with_capability(mut self, capability: impl Into<String>) -> Self {
+        self.capabilities
+            .insert(capability.into(), CapabilityRequest { requested: true });
+        self
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+}
+
+impl Handler for CreateAccount {
+    type ResponseBody = Account;
+    fn method(&self) -> Method {
+        Method::Post
+    }
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        "/v1/accounts".into()
+    }
+    fn request_body(&self, builder: BodyBuilder) -> BodyBuilder {
+        builder.qs(self)
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{GetHandler, SdkParams},
+    stripe::{CreatedFilter, List, account::Account},
+};
+
+/// Parameters accepted by `GET /v1/accounts`, listing the platform's connected accounts.
+///
+/// See: <https://docs.stripe.com/api/accounts/list>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ListAccounts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<CreatedFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ending_before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starting_after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<Vec<String>>,
+}
+
+pub type AccountList = List<Account>;
+
+impl GetHandler for ListAccounts {
+    type ResponseBody = AccountList;
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        "/v1/accounts".into()
+    }
+    fn params(&self) -> impl SdkParams {
+        self.clone()
+    }
+}
@@ -0,0 +1,125 @@
+//! Types for Stripe [Connect](https://docs.stripe.com/connect) accounts.
+//!
+//! Campaign organizers are onboarded as connected [`Account`]s through an
+//! [`AccountLink`]; act on their behalf with
+//! [`StripeClient::for_account`](crate::stripe::client::StripeClient::for_account).
+
+mod create;
+pub use create::*;
+
+mod retrieve;
+pub use retrieve::*;
+
+mod list;
+pub use list::*;
+
+mod account_link;
+pub use account_link::*;
+
+use ahash::HashMap;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// A Stripe account: the platform itself or one of its connected accounts.
+///
+/// See: <https://docs.stripe.com/api/accounts/object>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Account {
+    pub id: String,
+    pub object: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_profile: Option<BusinessProfile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_type: Option<BusinessType>,
+    #[serde(default)]
+    pub capabilities: HashMap<String, CapabilityStatus>,
+    /// Whether the account can create live charges.
+    #[serde(default)]
+    pub charges_enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_currency: Option<String>,
+    /// Whether the account holder finished onboarding.
+    #[serde(default)]
+    pub details_submitted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// Whether Stripe can send payouts to the account.
+    #[serde(default)]
+    pub payouts_enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requirements: Option<AccountRequirements>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub account_type: Option<AccountType>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct BusinessProfile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mcc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub support_email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// What the account holder still has to provide, and by when.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AccountRequirements {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_deadline: Option<i64>,
+    #[serde(default, deserialize_with = "crate::stripe::null_as_default")]
+    pub currently_due: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled_reason: Option<String>,
+    #[serde(default, deserialize_with = "crate::stripe::null_as_default")]
+    pub eventually_due: Vec<String>,
+    #[serde(default, deserialize_with = "crate::stripe::null_as_default")]
+    pub past_due: Vec<String>,
+    #[serde(default, deserialize_with = "crate::stripe::null_as_default")]
+    pub pending_verification: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum AccountType {
+    Custom,
+    Express,
+    None,
+    Standard,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum BusinessType {
+    Company,
+    GovernmentEntity,
+    Individual,
+    NonProfit,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum CapabilityStatus {
+    Active,
+    Inactive,
+    Pending,
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{GetHandler, SdkParams},
+    stripe::account::Account,
+};
+
+/// Parameters accepted by `GET /v1/accounts/:id`, or `GET /v1/account` for the
+/// account making the request.
+///
+/// See: <https://docs.stripe.com/api/accounts/retrieve>
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RetrieveAccount {
+    pub id: Option<String>,
+    pub query: RetrieveAccountQuery,
+}
+
+impl RetrieveAccount {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: Some(id.into()),
+            query: RetrieveAccountQuery::default(),
+        }
+    }
+
+    /// The platform, or the connected account set on the client
+    pub fn current() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RetrieveAccountQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<Vec<String>>,
+}
+
+impl GetHandler for RetrieveAccount {
+    type ResponseBody = Account;
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        match &self.id {
+            Some(id) => format!("/v1/accounts/{id}").into(),
+            None => "/v1/account".into(),
+        }
+    }
+    fn params(&self) -> impl SdkParams {
+        self.query.clone()
+    }
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{GetHandler, SdkParams},
+    stripe::{CreatedFilter, List, charge::Charge},
+};
+
+/// Parameters accepted by `GET /v1/charges`.
+///
+/// See: <https://docs.stripe.com/api/charges/list>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ListCharges {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<CreatedFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ending_before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_intent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starting_after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<Vec<String>>,
+}
+
+pub type ChargeList = List<Charge>;
+
+impl GetHandler for ListCharges {
+    type ResponseBody = ChargeList;
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        "/v1/charges".into()
+    }
+    fn params(&self) -> impl SdkParams {
+        self.clone()
+    }
+}
@@ -0,0 +1,134 @@
+//! Types for the Stripe [Charges](https://docs.stripe.com/api/charges) API.
+//!
+//! Charges are created by PaymentIntents, so only reads are modeled here.
+
+mod retrieve;
+pub use retrieve::*;
+
+mod list;
+pub use list::*;
+
+use ahash::HashMap;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+use crate::{
+    money::{Currency, Money, MoneyError},
+    stripe::{Address, RefundList},
+};
+
+/// A single attempt to move money onto your Stripe account.
+///
+/// See: <https://docs.stripe.com/api/charges/object>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Charge {
+    pub id: String,
+    pub object: String,
+    pub amount: i64,
+    pub amount_captured: i64,
+    pub amount_refunded: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_fee_amount: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_transaction: Option<String>,
+    #[serde(default)]
+    pub billing_details: ChargeBillingDetails,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub calculated_statement_descriptor: Option<String>,
+    pub captured: bool,
+    pub created: i64,
+    pub currency: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub disputed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_message: Option<String>,
+    pub livemode: bool,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<ChargeOutcome>,
+    pub paid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_intent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_url: Option<String>,
+    pub refunded: bool,
+    /// Only present when expanded with `expand[]=refunds`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refunds: Option<RefundList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statement_descriptor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statement_descriptor_suffix: Option<String>,
+    pub status: ChargeStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_group: Option<String>,
+}
+
+impl Charge {
+    /// The amount of the charge
+    pub fn money(&self) -> Result<Money, MoneyError> {
+        Ok(Money::new(self.amount, self.currency.parse::<Currency>()?))
+    }
+
+    /// The amount refunded so far, which can be less than [`Charge::money`]
+    pub fn refunded_money(&self) -> Result<Money, MoneyError> {
+        Ok(Money::new(
+            self.amount_refunded,
+            self.currency.parse::<Currency>()?,
+        ))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ChargeBillingDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+}
+
+/// Details about whether the payment was accepted, and why.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ChargeOutcome {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_level: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_score: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seller_message: Option<String>,
+    #[serde(rename = "type")]
+    pub outcome_type: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum ChargeStatus {
+    Failed,
+    Pending,
+    Succeeded,
+}
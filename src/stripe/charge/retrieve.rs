@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{GetHandler, SdkParams},
+    stripe::charge::Charge,
+};
+
+/// Parameters accepted by `GET /v1/charges/:id`.
+///
+/// See: <https://docs.stripe.com/api/charges/retrieve>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetrieveCharge {
+    pub id: String,
+    pub query: RetrieveChargeQuery,
+}
+
+impl RetrieveCharge {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            query: RetrieveChargeQuery::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RetrieveChargeQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<Vec<String>>,
+}
+
+impl GetHandler for RetrieveCharge {
+    type ResponseBody = Charge;
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        format!("/v1/charges/{}", self.id).into()
+    }
+    fn params(&self) -> impl SdkParams {
+        self.query.clone()
+    }
+}
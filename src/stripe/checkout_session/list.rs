@@ -2,7 +2,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     prelude::{GetHandler, SdkParams},
-    stripe::checkout_session::{CheckoutSession, CheckoutSessionStatus},
+    stripe::{
+        List,
+        checkout_session::{CheckoutSession, CheckoutSessionStatus},
+    },
 };
 
 /// Parameters accepted by `GET /v1/checkout/sessions`.
@@ -41,14 +44,7 @@ pub struct ListCustomerDetailsFilter {
     pub email: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
-pub struct CheckoutSessionList {
-    pub object: String,
-    pub data: Vec<CheckoutSession>,
-    pub has_more: bool,
-    pub url: String,
-}
+pub type CheckoutSessionList = List<CheckoutSession>;
 
 impl GetHandler for ListCheckoutSessions {
     type ResponseBody = CheckoutSessionList;
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     prelude::{GetHandler, SdkParams},
-    stripe::checkout_session::LineItem,
+    stripe::{List, checkout_session::LineItem},
 };
 
 /// Parameters accepted by `GET /v1/checkout/sessions/:id/line_items`.
@@ -36,14 +36,7 @@ pub struct ListCheckoutSessionLineItemsQuery {
     pub expand: Option<Vec<String>>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
-pub struct LineItemList {
-    pub object: String,
-    pub data: Vec<LineItem>,
-    pub has_more: bool,
-    pub url: String,
-}
+pub type LineItemList = List<LineItem>;
 
 impl GetHandler for ListCheckoutSessionLineItems {
     type ResponseBody = LineItemList;
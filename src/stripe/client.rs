@@ -1,9 +1,14 @@
 use std::io::Write;
 
-use crate::prelude::*;
+use crate::{prelude::*, stripe::IDEMPOTENCY_KEY};
 use base64::{prelude::BASE64_STANDARD, write::EncoderWriter};
+use http::HeaderName;
 use reqwest::header::{AUTHORIZATION, HeaderValue};
 use url::Url;
+use uuid::Uuid;
+
+pub const STRIPE_ACCOUNT: HeaderName = HeaderName::from_static("stripe-account");
+pub const STRIPE_VERSION: HeaderName = HeaderName::from_static("stripe-version");
 
 #[derive(Clone)]
 pub struct StripeClient {
@@ -12,6 +17,10 @@ pub struct StripeClient {
     /// API key for bearer token authentication (required)
     pub secret: String,
     pub client: reqwest::Client,
+    /// Connected account to act as, sent as `Stripe-Account` unless a request sets its own
+    pub account: Option<String>,
+    /// API version to pin, sent as `Stripe-Version`. Defaults to the account's version.
+    pub version: Option<String>,
 }
 
 impl StripeClient {
@@ -24,8 +33,31 @@ impl StripeClient {
             base: Url::parse("https://api.stripe.com").unwrap(),
             secret: secret.into(),
             client,
+            account: None,
+            version: None,
         }
     }
+
+    /// Acts as the connected account `account` on every request.
+    ///
+    /// Use on a clone to keep the platform client around:
+    /// `stripe.clone().with_account("acct_123")`.
+    pub fn with_account(mut self, account: impl Into<String>) -> Self {
+        self.account = Some(account.into());
+        self
+    }
+
+    /// A clone of this client acting as `account`, for requests on behalf of one connected
+    /// account. Clones share the connection pool.
+    pub fn for_account(&self, account: impl Into<String>) -> Self {
+        self.clone().with_account(account)
+    }
+
+    /// Pins the API version (e.g. `2025-08-27.basil`), overriding the account default.
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
 }
 
 impl Client for StripeClient {
@@ -51,6 +83,30 @@ impl Client for StripeClient {
             .expect("base64 is always valid HeaderValue");
         header.set_sensitive(true);
         request.headers_mut().insert(AUTHORIZATION, header);
-        self.client.execute(request).await
+
+        // Like Stripe's own libraries, key every POST. The key is new on every call, so it
+        // only guards against duplicates within this single attempt; wrap the handler in
+        // `Idempotent` to retry safely. Keys set by `Idempotent` take precedence.
+        if request.method() == reqwest::Method::POST
+            && !request.headers().contains_key(IDEMPOTENCY_KEY)
+        {
+            let key = HeaderValue::from_str(&Uuid::new_v4().to_string())
+                .expect("uuids are valid header values");
+            request.headers_mut().insert(IDEMPOTENCY_KEY, key);
+        }
+
+        // Going through the builder turns an invalid account or version into an error,
+        // rather than a request sent as the platform or on the wrong version.
+        let has_account = request.headers().contains_key(STRIPE_ACCOUNT);
+        let mut builder = reqwest::RequestBuilder::from_parts(self.client.clone(), request);
+        if let Some(account) = &self.account
+            && !has_account
+        {
+            builder = builder.header(STRIPE_ACCOUNT, account.as_str());
+        }
+        if let Some(version) = &self.version {
+            builder = builder.header(STRIPE_VERSION, version.as_str());
+        }
+        builder.send().await
     }
 }
@@ -0,0 +1,70 @@
+use ahash::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{BodyBuilder, Handler, Method},
+    stripe::{
+        Address, CreateShipping,
+        customer::{Customer, TaxExempt},
+    },
+};
+
+/// Parameters accepted by `POST /v1/customers`. Every field is optional.
+///
+/// See: <https://docs.stripe.com/api/customers/create>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateCustomer {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_locales: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipping: Option<CreateShipping>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tax_exempt: Option<TaxExempt>,
+}
+
+impl CreateCustomer {
+    pub fn with_email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+}
+
+impl Handler for CreateCustomer {
+    type ResponseBody = Customer;
+    fn method(&self) -> Method {
+        Method::Post
+    }
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        "/v1/customers".into()
+    }
+    fn request_body(&self, builder: BodyBuilder) -> BodyBuilder {
+        builder.qs(self)
+    }
+}
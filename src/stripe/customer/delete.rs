@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::{Handler, Method};
+
+/// Parameters accepted by `DELETE /v1/customers/:id`.
+///
+/// See: <https://docs.stripe.com/api/customers/delete>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteCustomer {
+    pub id: String,
+}
+
+impl DeleteCustomer {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+/// What Stripe returns in place of a customer once it has been deleted.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DeletedCustomer {
+    pub id: String,
+    pub object: String,
+    pub deleted: bool,
+}
+
+impl Handler for DeleteCustomer {
+    type ResponseBody = DeletedCustomer;
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        format!("/v1/customers/{}", self.id).into()
+    }
+}
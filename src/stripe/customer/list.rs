@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{GetHandler, SdkParams},
+    stripe::{CreatedFilter, List, customer::Customer},
+};
+
+/// Parameters accepted by `GET /v1/customers`.
+///
+/// See: <https://docs.stripe.com/api/customers/list>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ListCustomers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<CreatedFilter>,
+    /// Case-sensitive exact match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ending_before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starting_after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_clock: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<Vec<String>>,
+}
+
+pub type CustomerList = List<Customer>;
+
+impl GetHandler for ListCustomers {
+    type ResponseBody = CustomerList;
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        "/v1/customers".into()
+    }
+    fn params(&self) -> impl SdkParams {
+        self.clone()
+    }
+}
@@ -0,0 +1,80 @@
+//! Types for the Stripe [Customers](https://docs.stripe.com/api/customers) API.
+//!
+//! [`Customer`] mirrors the object Stripe returns on every endpoint; the
+//! endpoint-specific request types live in their own submodules.
+
+mod create;
+pub use create::*;
+
+mod retrieve;
+pub use retrieve::*;
+
+mod update;
+pub use update::*;
+
+mod list;
+pub use list::*;
+
+mod delete;
+pub use delete::*;
+
+use ahash::HashMap;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+use crate::stripe::{Address, Shipping};
+
+/// A customer of your business, used to track payments and stored payment methods.
+///
+/// See: <https://docs.stripe.com/api/customers/object>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Customer {
+    pub id: String,
+    pub object: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// Credit balance in the smallest currency unit; negative values are credit.
+    #[serde(default)]
+    pub balance: i64,
+    pub created: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delinquent: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invoice_prefix: Option<String>,
+    pub livemode: bool,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "crate::stripe::null_as_default",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub preferred_locales: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipping: Option<Shipping>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tax_exempt: Option<TaxExempt>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum TaxExempt {
+    Exempt,
+    None,
+    Reverse,
+}
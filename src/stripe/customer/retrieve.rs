@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{GetHandler, SdkParams},
+    stripe::customer::Customer,
+};
+
+/// Parameters accepted by `GET /v1/customers/:id`.
+///
+/// See: <https://docs.stripe.com/api/customers/retrieve>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetrieveCustomer {
+    pub id: String,
+    pub query: RetrieveCustomerQuery,
+}
+
+impl RetrieveCustomer {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            query: RetrieveCustomerQuery::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RetrieveCustomerQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<Vec<String>>,
+}
+
+impl GetHandler for RetrieveCustomer {
+    type ResponseBody = Customer;
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        format!("/v1/customers/{}", self.id).into()
+    }
+    fn params(&self) -> impl SdkParams {
+        self.query.clone()
+    }
+}
@@ -0,0 +1,69 @@
+use ahash::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{BodyBuilder, Handler, Method},
+    stripe::{
+        Address, CreateShipping,
+        customer::{Customer, TaxExempt},
+    },
+};
+
+/// Parameters accepted by `POST /v1/customers/:id`.
+///
+/// See: <https://docs.stripe.com/api/customers/update>
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdateCustomer {
+    pub id: String,
+    pub body: UpdateCustomerBody,
+}
+
+impl UpdateCustomer {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            body: UpdateCustomerBody::default(),
+        }
+    }
+}
+
+/// Metadata keys are merged with the existing ones; send an empty value to remove a key.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UpdateCustomerBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_locales: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipping: Option<CreateShipping>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tax_exempt: Option<TaxExempt>,
+}
+
+impl Handler for UpdateCustomer {
+    type ResponseBody = Customer;
+    fn method(&self) -> Method {
+        Method::Post
+    }
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        format!("/v1/customers/{}", self.id).into()
+    }
+    fn request_body(&self, builder: BodyBuilder) -> BodyBuilder {
+        builder.qs(&self.body)
+    }
+}
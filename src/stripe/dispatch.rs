@@ -0,0 +1,327 @@
+//! Routes [`WebhookEvent`]s to handlers registered per event type.
+//!
+//! ```ignore
+//! let dispatcher = WebhookDispatcher::default()
+//!     .on("charge.refunded", |charge: Charge, _| async move {
+//!         balances.refund(&charge).await
+//!     })
+//!     .on_unknown(|type_name, _, event| async move {
+//!         tracing::debug!("ignoring {type_name} ({})", event.id);
+//!         Ok(())
+//!     });
+//!
+//! dispatcher.dispatch(&event).await?;
+//! ```
+
+use std::{
+    collections::{HashSet, VecDeque},
+    convert::Infallible,
+    pin::Pin,
+    sync::Mutex,
+};
+
+use ahash::HashMap;
+use thiserror::Error;
+
+use crate::stripe::{
+    Charge, CheckoutSession, Customer, PaymentIntent, Refund, WebhookEvent, WebhookEventKind,
+};
+
+/// Remembers which event ids were processed, so Stripe's redeliveries are skipped.
+///
+/// An event is only recorded once its handlers succeed, so a failed event is retried
+/// when Stripe sends it again. Two deliveries of one event running at the same time
+/// can both be handled; stores shared between processes should make `insert` atomic
+/// and handlers should tolerate the rare repeat.
+pub trait EventStore {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    fn contains(&self, event_id: &str) -> impl Future<Output = Result<bool, Self::Error>>;
+
+    fn insert(&self, event_id: &str) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+/// An [`EventStore`] for a single process, forgetting everything on restart.
+#[derive(Debug, Default)]
+pub struct InMemoryEventStore {
+    inner: Mutex<Seen>,
+    max_events: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+struct Seen {
+    ids: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl InMemoryEventStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forgets the oldest ids past `max_events`. Stripe retries for up to three days.
+    pub fn with_max_events(mut self, max_events: usize) -> Self {
+        self.max_events = Some(max_events);
+        self
+    }
+}
+
+impl EventStore for InMemoryEventStore {
+    type Error = Infallible;
+
+    async fn contains(&self, event_id: &str) -> Result<bool, Infallible> {
+        Ok(self.inner.lock().unwrap().ids.contains(event_id))
+    }
+
+    async fn insert(&self, event_id: &str) -> Result<(), Infallible> {
+        let mut seen = self.inner.lock().unwrap();
+        if seen.ids.insert(event_id.to_string()) {
+            seen.order.push_back(event_id.to_string());
+        }
+        while self.max_events.is_some_and(|max| seen.order.len() > max) {
+            if let Some(oldest) = seen.order.pop_front() {
+                seen.ids.remove(&oldest);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A resource that webhook events carry, handed to typed handlers.
+pub trait EventObject: Sized {
+    /// The event types whose payload is this resource
+    const EVENT_TYPES: &'static [&'static str];
+
+    fn from_kind(kind: WebhookEventKind) -> Option<Self>;
+}
+
+impl EventObject for CheckoutSession {
+    const EVENT_TYPES: &'static [&'static str] = &[
+        "checkout.session.completed",
+        "checkout.session.expired",
+        "checkout.session.async_payment_failed",
+        "checkout.session.async_payment_succeeded",
+    ];
+
+    fn from_kind(kind: WebhookEventKind) -> Option<Self> {
+        match kind {
+            WebhookEventKind::CheckoutSessionCompleted(session)
+            | WebhookEventKind::CheckoutSessionExpired(session)
+            | WebhookEventKind::CheckoutSessionAsyncPaymentFailed(session)
+            | WebhookEventKind::CheckoutSessionAsyncPaymentSucceeded(session) => Some(session),
+            _ => None,
+        }
+    }
+}
+
+impl EventObject for PaymentIntent {
+    const EVENT_TYPES: &'static [&'static str] = &[
+        "payment_intent.created",
+        "payment_intent.succeeded",
+        "payment_intent.payment_failed",
+        "payment_intent.canceled",
+        "payment_intent.processing",
+        "payment_intent.requires_action",
+    ];
+
+    fn from_kind(kind: WebhookEventKind) -> Option<Self> {
+        match kind {
+            WebhookEventKind::PaymentIntentCreated(intent)
+            | WebhookEventKind::PaymentIntentSucceeded(intent)
+            | WebhookEventKind::PaymentIntentPaymentFailed(intent)
+            | WebhookEventKind::PaymentIntentCanceled(intent)
+            | WebhookEventKind::PaymentIntentProcessing(intent)
+            | WebhookEventKind::PaymentIntentRequiresAction(intent) => Some(intent),
+            _ => None,
+        }
+    }
+}
+
+impl EventObject for Charge {
+    const EVENT_TYPES: &'static [&'static str] = &["charge.refunded"];
+
+    fn from_kind(kind: WebhookEventKind) -> Option<Self> {
+        match kind {
+            WebhookEventKind::ChargeRefunded(charge) => Some(charge),
+            _ => None,
+        }
+    }
+}
+
+impl EventObject for Refund {
+    const EVENT_TYPES: &'static [&'static str] = &["refund.updated"];
+
+    fn from_kind(kind: WebhookEventKind) -> Option<Self> {
+        match kind {
+            WebhookEventKind::RefundUpdated(refund) => Some(refund),
+            _ => None,
+        }
+    }
+}
+
+impl EventObject for Customer {
+    const EVENT_TYPES: &'static [&'static str] =
+        &["customer.created", "customer.updated", "customer.deleted"];
+
+    fn from_kind(kind: WebhookEventKind) -> Option<Self> {
+        match kind {
+            WebhookEventKind::CustomerCreated(customer)
+            | WebhookEventKind::CustomerUpdated(customer)
+            | WebhookEventKind::CustomerDeleted(customer) => Some(customer),
+            _ => None,
+        }
+    }
+}
+
+/// What [`WebhookDispatcher::dispatch`] did with an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dispatched {
+    /// At least one handler ran and all of them succeeded
+    Handled,
+    /// The event was processed before
+    Duplicate,
+    /// Nothing is registered for the event type
+    Ignored,
+}
+
+#[derive(Debug, Error)]
+pub enum DispatchError {
+    #[error("event store failed: {0}")]
+    Store(Box<dyn std::error::Error + Send + Sync>),
+    #[error("handler for `{type_name}` failed on event {event_id}: {source}")]
+    Handler {
+        event_id: String,
+        type_name: String,
+        source: anyhow::Error,
+    },
+}
+
+type HandlerFuture = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>;
+type BoxedHandler = Box<dyn Fn(&WebhookEvent) -> HandlerFuture + Send + Sync>;
+
+/// Runs the handlers registered for each event's type, once per event id.
+pub struct WebhookDispatcher<S = InMemoryEventStore> {
+    store: S,
+    handlers: HashMap<String, Vec<BoxedHandler>>,
+    unknown: Option<BoxedHandler>,
+}
+
+impl Default for WebhookDispatcher {
+    fn default() -> Self {
+        Self::new(InMemoryEventStore::new())
+    }
+}
+
+impl<S: EventStore> WebhookDispatcher<S> {
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            handlers: HashMap::default(),
+            unknown: None,
+        }
+    }
+
+    /// Runs `handler` with the payload of every `event_type` event.
+    ///
+    /// Handlers of one type run in the order they were added.
+    ///
+    /// # Panics
+    /// If `event_type` does not carry a `T`, e.g. `on::<Charge>("customer.created", ..)`.
+    pub fn on<T, F, Fut>(mut self, event_type: &str, handler: F) -> Self
+    where
+        T: EventObject + 'static,
+        F: Fn(T, &WebhookEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        assert!(
+            T::EVENT_TYPES.contains(&event_type),
+            "`{event_type}` events do not carry a {}",
+            std::any::type_name::<T>()
+        );
+        let handler: BoxedHandler = Box::new(move |event| match T::from_kind(event.kind.clone()) {
+            Some(object) => Box::pin(handler(object, event)),
+            None => Box::pin(async { Ok(()) }),
+        });
+        self.handlers
+            .entry(event_type.to_string())
+            .or_default()
+            .push(handler);
+        self
+    }
+
+    /// Runs `handler` for event types without a typed [`WebhookEventKind`] variant, with
+    /// the type name and the raw object.
+    pub fn on_unknown<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(String, serde_json::Value, &WebhookEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        self.unknown = Some(Box::new(move |event| match &event.kind {
+            WebhookEventKind::Unknown { type_name, object } => {
+                Box::pin(handler(type_name.clone(), object.clone(), event))
+            }
+            _ => Box::pin(async { Ok(()) }),
+        }));
+        self
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Handles one event, unless its id was already handled.
+    pub async fn dispatch(&self, event: &WebhookEvent) -> Result<Dispatched, DispatchError> {
+        if self
+            .store
+            .contains(&event.id)
+            .await
+            .map_err(|e| DispatchError::Store(Box::new(e)))?
+        {
+            return Ok(Dispatched::Duplicate);
+        }
+
+        let handlers: Vec<&BoxedHandler> = match &event.kind {
+            WebhookEventKind::Unknown { .. } => self.unknown.iter().collect(),
+            kind => self
+                .handlers
+                .get(kind.type_name())
+                .map(|handlers| handlers.iter().collect())
+                .unwrap_or_default(),
+        };
+        if handlers.is_empty() {
+            return Ok(Dispatched::Ignored);
+        }
+
+        for handler in handlers {
+            handler(event)
+                .await
+                .map_err(|source| DispatchError::Handler {
+                    event_id: event.id.clone(),
+                    type_name: event.kind.type_name().to_string(),
+                    source,
+                })?;
+        }
+
+        self.store
+            .insert(&event.id)
+            .await
+            .map_err(|e| DispatchError::Store(Box::new(e)))?;
+        Ok(Dispatched::Handled)
+    }
+
+    /// Handles `events` oldest first by `created`, stopping at the first failure.
+    ///
+    /// Stripe does not deliver events in order; use this when replaying a batch, such as
+    /// events fetched after downtime.
+    pub async fn dispatch_all(
+        &self,
+        mut events: Vec<WebhookEvent>,
+    ) -> Result<Vec<Dispatched>, DispatchError> {
+        events.sort_by_key(|event| event.created);
+        let mut dispatched = Vec::with_capacity(events.len());
+        for event in &events {
+            dispatched.push(self.dispatch(event).await?);
+        }
+        Ok(dispatched)
+    }
+}
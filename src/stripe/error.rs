@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{peanut::query::QueryError, prelude::*, stripe::ErrorType};
+
+/// The error object in the body of a failed Stripe response.
+///
+/// See: <https://docs.stripe.com/api/errors>
+#[derive(Serialize, Deserialize, Error, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[error("Stripe {error_type}: {}", message.as_deref().unwrap_or("no message"))]
+pub struct StripeError {
+    #[serde(rename = "type")]
+    pub error_type: ErrorType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decline_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub param: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_url: Option<String>,
+    /// Link to the failed request in the Stripe dashboard.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_log_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charge: Option<String>,
+}
+
+impl StripeError {
+    /// Parses a response body of the form `{"error": {...}}`.
+    pub fn from_body(body: &str) -> Option<Self> {
+        #[derive(Deserialize)]
+        struct Body {
+            error: StripeError,
+        }
+        serde_json::from_str::<Body>(body)
+            .ok()
+            .map(|body| body.error)
+    }
+
+    pub fn is_card_error(&self) -> bool {
+        self.error_type == ErrorType::CardError
+    }
+
+    /// The idempotency key was reused with different parameters.
+    pub fn is_idempotency_error(&self) -> bool {
+        self.error_type == ErrorType::IdempotencyError
+    }
+}
+
+/// Reads the [`StripeError`] out of a request's error, if Stripe sent one.
+pub trait StripeErrorExt {
+    fn stripe_error(&self) -> Option<StripeError>;
+}
+
+impl StripeErrorExt for SdkError {
+    fn stripe_error(&self) -> Option<StripeError> {
+        match self {
+            SdkError::Status(_, body) => StripeError::from_body(body),
+            _ => None,
+        }
+    }
+}
+
+impl<C> StripeErrorExt for QueryError<C> {
+    fn stripe_error(&self) -> Option<StripeError> {
+        match self {
+            QueryError::Status(_, body) => StripeError::from_body(body),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::borrow::Cow;
+
+use http::{HeaderMap, HeaderName, HeaderValue};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::prelude::*;
+
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Longest key Stripe accepts
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum IdempotencyKeyError {
+    #[error("idempotency key is empty")]
+    Empty,
+    #[error("idempotency key is {0} characters, Stripe accepts at most 255")]
+    TooLong(usize),
+    #[error("idempotency key {0:?} is not a valid header value")]
+    InvalidHeader(String),
+}
+
+/// Sends a handler with an `Idempotency-Key` header, so Stripe performs it at most once.
+///
+/// Clone and resend the same value to retry safely; Stripe replays the first result.
+/// Without a key, [`StripeClient`](crate::stripe::client::StripeClient) adds a fresh one to
+/// every `POST`, which only guards against duplicates within that single attempt.
+///
+/// See: <https://docs.stripe.com/api/idempotent_requests>
+#[derive(Debug, Clone, PartialEq)]
+pub struct Idempotent<H> {
+    pub handler: H,
+    key: HeaderValue,
+}
+
+impl<H> Idempotent<H> {
+    /// Wraps `handler` with a random UUID key
+    pub fn new(handler: H) -> Self {
+        Self {
+            handler,
+            key: HeaderValue::from_str(&Uuid::new_v4().to_string())
+                .expect("uuids are valid header values"),
+        }
+    }
+
+    /// Wraps `handler` with `key`, which must be non-empty printable ASCII of at most
+    /// [`MAX_IDEMPOTENCY_KEY_LEN`] characters.
+    pub fn with_key(handler: H, key: impl Into<String>) -> Result<Self, IdempotencyKeyError> {
+        let key = key.into();
+        if key.is_empty() {
+            return Err(IdempotencyKeyError::Empty);
+        }
+        if key.chars().count() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(IdempotencyKeyError::TooLong(key.chars().count()));
+        }
+        // `HeaderValue` also takes bytes past ASCII, which `key()` could not hand back
+        if !key.bytes().all(|b| b.is_ascii_graphic() || b == b' ') {
+            return Err(IdempotencyKeyError::InvalidHeader(key));
+        }
+        let key =
+            HeaderValue::from_str(&key).map_err(|_| IdempotencyKeyError::InvalidHeader(key))?;
+        Ok(Self { handler, key })
+    }
+
+    pub fn key(&self) -> &str {
+        self.key
+            .to_str()
+            .expect("keys are checked to be printable ascii")
+    }
+}
+
+impl<H: Handler> Handler for Idempotent<H> {
+    type ResponseBody = H::ResponseBody;
+
+    fn method(&self) -> Method {
+        self.handler.method()
+    }
+    fn path(&self) -> Cow<'_, str> {
+        self.handler.path()
+    }
+    fn params(&self) -> impl SdkParams {
+        self.handler.params()
+    }
+    fn request_body(&self, builder: BodyBuilder) -> BodyBuilder {
+        self.handler.request_body(builder)
+    }
+    fn headers(&self, headers: &mut HeaderMap) {
+        self.handler.headers(headers);
+        headers.insert(IDEMPOTENCY_KEY, self.key.clone());
+    }
+    fn after_response(&self) -> impl Future<Output = SdkResult<()>> {
+        self.handler.after_response()
+    }
+}
+
+/// Adds [`Idempotent`] constructors to every handler.
+pub trait IdempotentExt: Handler + Sized {
+    /// Uses a random UUID as the idempotency key
+    fn idempotent(self) -> Idempotent<Self> {
+        Idempotent::new(self)
+    }
+
+    fn with_idempotency_key(
+        self,
+        key: impl Into<String>,
+    ) -> Result<Idempotent<Self>, IdempotencyKeyError> {
+        Idempotent::with_key(self, key)
+    }
+}
+
+impl<H: Handler> IdempotentExt for H {}
@@ -0,0 +1,356 @@
+use std::collections::VecDeque;
+
+use futures_core::Stream;
+use futures_util::{TryStreamExt, stream};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{
+    prelude::*,
+    stripe::{
+        Account, Charge, CheckoutSession, Customer, LineItem, ListAccounts, ListCharges,
+        ListCheckoutSessionLineItems, ListCheckoutSessions, ListCustomers, ListPaymentIntents,
+        ListRefunds, PaymentIntent, Refund,
+    },
+};
+
+/// One page of a Stripe list endpoint.
+///
+/// See: <https://docs.stripe.com/api/pagination>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct List<T> {
+    pub object: String,
+    pub data: Vec<T>,
+    pub has_more: bool,
+    pub url: String,
+}
+
+/// A Stripe object, whose `id` can be used as a list cursor.
+pub trait StripeObject {
+    fn id(&self) -> &str;
+}
+
+/// The cursor fields of a Stripe list request.
+pub trait ListParams {
+    fn starting_after(&self) -> Option<&str>;
+    fn ending_before(&self) -> Option<&str>;
+    fn set_starting_after(&mut self, id: String);
+    fn set_ending_before(&mut self, id: String);
+}
+
+/// Implements [`ListParams`] for a type with `starting_after`/`ending_before` fields,
+/// optionally nested in a field of the type.
+macro_rules! list_params {
+    ($name:ty $(, $field:ident)?) => {
+        impl ListParams for $name {
+            fn starting_after(&self) -> Option<&str> {
+                self$(.$field)?.starting_after.as_deref()
+            }
+            fn ending_before(&self) -> Option<&str> {
+                self$(.$field)?.ending_before.as_deref()
+            }
+            fn set_starting_after(&mut self, id: String) {
+                self$(.$field)?.starting_after = Some(id);
+            }
+            fn set_ending_before(&mut self, id: String) {
+                self$(.$field)?.ending_before = Some(id);
+            }
+        }
+    };
+}
+
+list_params!(ListCheckoutSessions);
+list_params!(ListCheckoutSessionLineItems, query);
+list_params!(ListPaymentIntents);
+list_params!(ListRefunds);
+list_params!(ListCustomers);
+list_params!(ListCharges);
+list_params!(ListAccounts);
+
+macro_rules! stripe_object {
+    ($($name:ty),+) => {
+        $(
+            impl StripeObject for $name {
+                fn id(&self) -> &str {
+                    &self.id
+                }
+            }
+        )+
+    };
+}
+
+stripe_object!(
+    CheckoutSession,
+    LineItem,
+    PaymentIntent,
+    Refund,
+    Customer,
+    Charge,
+    Account
+);
+
+/// Requests every page of a list endpoint in turn. See [`AutoPaginate::paginate`].
+#[derive(Debug, Clone)]
+pub struct Paginate<H> {
+    handler: H,
+    max_items: Option<usize>,
+}
+
+/// Auto-pagination for every Stripe list handler.
+pub trait AutoPaginate: Sized {
+    /// Follows the list cursors until Stripe has no more items.
+    ///
+    /// Pages forward with `starting_after`, unless the request sets `ending_before`, in
+    /// which case it pages backward to newer items. Items keep Stripe's order within a page.
+    ///
+    /// ```ignore
+    /// let refunds = ListRefunds::default()
+    ///     .paginate()
+    ///     .with_max_items(1000)
+    ///     .collect(&stripe)
+    ///     .await?;
+    /// ```
+    fn paginate(self) -> Paginate<Self>;
+}
+
+impl<H, T> AutoPaginate for H
+where
+    H: GetHandler<ResponseBody = List<T>> + ListParams + Clone,
+    T: StripeObject + DeserializeOwned,
+{
+    fn paginate(self) -> Paginate<Self> {
+        Paginate {
+            handler: self,
+            max_items: None,
+        }
+    }
+}
+
+impl<H, T> Paginate<H>
+where
+    H: GetHandler<ResponseBody = List<T>> + ListParams + Clone,
+    T: StripeObject + DeserializeOwned,
+{
+    /// Stops after this many items, across all pages
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Yields items one at a time, requesting the next page when the current one runs out.
+    ///
+    /// A failed request ends the stream after yielding its error.
+    pub fn stream<C: Client>(self, client: &C) -> impl Stream<Item = SdkResult<T>> {
+        let state = PageState {
+            backward: self.handler.ending_before().is_some(),
+            handler: self.handler,
+            items: VecDeque::new(),
+            remaining: self.max_items,
+            has_more: true,
+        };
+
+        stream::unfold(Some(state), move |state| async move {
+            let mut state = state?;
+            loop {
+                if state.remaining == Some(0) {
+                    return None;
+                }
+                if let Some(item) = state.items.pop_front() {
+                    if let Some(remaining) = &mut state.remaining {
+                        *remaining -= 1;
+                    }
+                    return Some((Ok(item), Some(state)));
+                }
+                if !state.has_more {
+                    return None;
+                }
+
+                let page = match state.handler.clone().request(client).await {
+                    Ok(page) => page,
+                    Err(e) => return Some((Err(e), None)),
+                };
+                state.next_page(page);
+            }
+        })
+    }
+
+    /// Collects every item of [`Paginate::stream`].
+    pub async fn collect<C: Client>(self, client: &C) -> SdkResult<Vec<T>> {
+        self.stream(client).try_collect().await
+    }
+}
+
+struct PageState<H, T> {
+    handler: H,
+    items: VecDeque<T>,
+    remaining: Option<usize>,
+    backward: bool,
+    has_more: bool,
+}
+
+impl<H: ListParams, T: StripeObject> PageState<H, T> {
+    /// Queues the items of `page` and points the request at the page after it.
+    fn next_page(&mut self, page: List<T>) {
+        let cursor = if self.backward {
+            page.data.first()
+        } else {
+            page.data.last()
+        };
+        match cursor {
+            Some(item) if page.has_more => {
+                let id = item.id().to_string();
+                if self.backward {
+                    self.handler.set_ending_before(id);
+                } else {
+                    self.handler.set_starting_after(id);
+                }
+            }
+            _ => self.has_more = false,
+        }
+        self.items.extend(page.data);
+    }
+}
+
+#[cfg(test)]
+fn refund_page(ids: &[&str], has_more: bool) -> List<Refund> {
+    let data = ids
+        .iter()
+        .map(|id| {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "object": "refund",
+                "amount": 100,
+                "created": 1692942318,
+                "currency": "usd",
+                "metadata": {},
+                "status": "succeeded"
+            }))
+            .unwrap()
+        })
+        .collect();
+    List {
+        object: "list".into(),
+        data,
+        has_more,
+        url: "/v1/refunds".into(),
+    }
+}
+
+#[test]
+fn next_page_follows_the_cursor_direction() {
+    let mut forward = PageState {
+        handler: ListRefunds::default(),
+        items: VecDeque::new(),
+        remaining: None,
+        backward: false,
+        has_more: true,
+    };
+    forward.next_page(refund_page(&["re_3", "re_2"], true));
+    assert_eq!(forward.handler.starting_after(), Some("re_2"));
+    assert!(forward.has_more);
+    forward.next_page(refund_page(&["re_1"], false));
+    assert!(!forward.has_more);
+    assert_eq!(forward.items.len(), 3);
+
+    let mut backward = PageState {
+        handler: ListRefunds {
+            ending_before: Some("re_1".into()),
+            ..Default::default()
+        },
+        items: VecDeque::new(),
+        remaining: None,
+        backward: true,
+        has_more: true,
+    };
+    backward.next_page(refund_page(&["re_5", "re_4"], true));
+    assert_eq!(backward.handler.ending_before(), Some("re_5"));
+    assert_eq!(backward.handler.starting_after(), None);
+
+    // an empty page ends the listing even if Stripe claims there's more
+    backward.next_page(refund_page(&[], true));
+    assert!(!backward.has_more);
+}
+
+#[test]
+fn line_item_cursors_live_in_the_query() {
+    let mut list = ListCheckoutSessionLineItems::new("cs_test_123");
+    list.set_starting_after("li_1".into());
+    assert_eq!(list.query.starting_after.as_deref(), Some("li_1"));
+    assert_eq!(
+        serde_qs::to_string(&list.query).unwrap(),
+        "starting_after=li_1"
+    );
+}
+
+/// Answers each request with the next queued page, and records the query it was sent.
+#[cfg(test)]
+struct PagesClient {
+    pages: std::sync::Mutex<VecDeque<List<Refund>>>,
+    queries: std::sync::Mutex<Vec<String>>,
+}
+
+#[cfg(test)]
+impl Client for PagesClient {
+    type Error = std::convert::Infallible;
+    type Response = reqwest::Response;
+
+    fn endpoint(&self, path: &str) -> Result<url::Url, Self::Error> {
+        Ok(url::Url::parse("https://api.stripe.com")
+            .unwrap()
+            .join(path)
+            .unwrap())
+    }
+
+    async fn execute(&self, request: reqwest::Request) -> Result<reqwest::Response, Self::Error> {
+        self.queries
+            .lock()
+            .unwrap()
+            .push(request.url().query().unwrap_or_default().to_string());
+        let page = self.pages.lock().unwrap().pop_front().expect("a page left");
+        let body = serde_json::to_vec(&page).unwrap();
+        Ok(http::Response::new(body).into())
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn stream_follows_pages_up_to_max_items() {
+    let client = PagesClient {
+        pages: std::sync::Mutex::new(VecDeque::from([
+            refund_page(&["re_5", "re_4"], true),
+            refund_page(&["re_3", "re_2"], true),
+            refund_page(&["re_1"], false),
+        ])),
+        queries: Default::default(),
+    };
+    let list = ListRefunds {
+        limit: Some(2),
+        ..Default::default()
+    };
+
+    let refunds = list.clone().paginate().collect(&client).await.unwrap();
+    let ids: Vec<&str> = refunds.iter().map(|refund| refund.id()).collect();
+    assert_eq!(ids, ["re_5", "re_4", "re_3", "re_2", "re_1"]);
+    assert_eq!(
+        *client.queries.lock().unwrap(),
+        [
+            "limit=2",
+            "limit=2&starting_after=re_4",
+            "limit=2&starting_after=re_2"
+        ]
+    );
+
+    client.queries.lock().unwrap().clear();
+    client.pages.lock().unwrap().extend([
+        refund_page(&["re_5", "re_4"], true),
+        refund_page(&["re_3", "re_2"], true),
+    ]);
+    let refunds = list
+        .paginate()
+        .with_max_items(3)
+        .collect(&client)
+        .await
+        .unwrap();
+    assert_eq!(refunds.len(), 3);
+    assert_eq!(client.queries.lock().unwrap().len(), 2);
+}
@@ -4,9 +4,33 @@ pub use payment_intent::*;
 mod checkout_session;
 pub use checkout_session::*;
 
+mod refund;
+pub use refund::*;
+
+mod customer;
+pub use customer::*;
+
+mod charge;
+pub use charge::*;
+
+mod account;
+pub use account::*;
+
+mod list;
+pub use list::*;
+
+mod error;
+pub use error::*;
+
+mod idempotency;
+pub use idempotency::*;
+
 mod webhook;
 pub use webhook::*;
 
+mod dispatch;
+pub use dispatch::*;
+
 pub mod client;
 
 #[cfg(test)]
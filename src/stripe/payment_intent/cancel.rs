@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+use crate::{
+    prelude::{BodyBuilder, Handler, Method},
+    stripe::PaymentIntent,
+};
+
+/// Parameters accepted by `POST /v1/payment_intents/:id/cancel`.
+///
+/// See: <https://docs.stripe.com/api/payment_intents/cancel>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CancelPaymentIntent {
+    pub id: String,
+    pub body: CancelPaymentIntentBody,
+}
+
+impl CancelPaymentIntent {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            body: CancelPaymentIntentBody::default(),
+        }
+    }
+
+    pub fn with_reason(mut self, reason: CancelReason) -> Self {
+        self.body.cancellation_reason = Some(reason);
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CancelPaymentIntentBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancellation_reason: Option<CancelReason>,
+}
+
+/// The subset of [`CancellationReason`](crate::stripe::CancellationReason) a caller may give.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum CancelReason {
+    Abandoned,
+    Duplicate,
+    Fraudulent,
+    RequestedByCustomer,
+}
+
+impl Handler for CancelPaymentIntent {
+    type ResponseBody = PaymentIntent;
+    fn method(&self) -> Method {
+        Method::Post
+    }
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        format!("/v1/payment_intents/{}/cancel", self.id).into()
+    }
+    fn request_body(&self, builder: BodyBuilder) -> BodyBuilder {
+        builder.qs(&self.body)
+    }
+}
@@ -0,0 +1,67 @@
+use ahash::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{BodyBuilder, Handler, Method},
+    stripe::{PaymentIntent, payment_intent::PaymentDetails},
+};
+
+/// Parameters accepted by `POST /v1/payment_intents/:id/capture`.
+///
+/// See: <https://docs.stripe.com/api/payment_intents/capture>
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturePaymentIntent {
+    pub id: String,
+    pub body: CapturePaymentIntentBody,
+}
+
+impl CapturePaymentIntent {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            body: CapturePaymentIntentBody::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CapturePaymentIntentBody {
+    /// Defaults to the full `amount_capturable`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_to_capture: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_fee_amount: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub final_capture: Option<bool>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_details: Option<PaymentDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statement_descriptor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statement_descriptor_suffix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_data: Option<CaptureTransferData>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CaptureTransferData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<i64>,
+}
+
+impl Handler for CapturePaymentIntent {
+    type ResponseBody = PaymentIntent;
+    fn method(&self) -> Method {
+        Method::Post
+    }
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        format!("/v1/payment_intents/{}/capture", self.id).into()
+    }
+    fn request_body(&self, builder: BodyBuilder) -> BodyBuilder {
+        builder.qs(&self.body)
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{BodyBuilder, Handler, Method},
+    stripe::{
+        PaymentIntent,
+        payment_intent::{
+            CaptureMethod, CreatePaymentMethodData, CreatePaymentMethodOptions, CreateRadarOptions,
+            CreateShipping, MandateData, OffSession, PaymentDetails, PaymentMethodType,
+            SetupFutureUsage,
+        },
+    },
+};
+
+/// Parameters accepted by `POST /v1/payment_intents/:id/confirm`.
+///
+/// See: <https://docs.stripe.com/api/payment_intents/confirm>
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmPaymentIntent {
+    pub id: String,
+    pub body: ConfirmPaymentIntentBody,
+}
+
+impl ConfirmPaymentIntent {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            body: ConfirmPaymentIntentBody::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ConfirmPaymentIntentBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capture_method: Option<CaptureMethod>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_on_requires_action: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_payment_method_types: Option<Vec<PaymentMethodType>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mandate: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mandate_data: Option<MandateData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub off_session: Option<OffSession>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_details: Option<PaymentDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method_data: Option<CreatePaymentMethodData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method_options: Option<CreatePaymentMethodOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method_types: Option<Vec<PaymentMethodType>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub radar_options: Option<CreateRadarOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub setup_future_usage: Option<SetupFutureUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipping: Option<CreateShipping>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_stripe_sdk: Option<bool>,
+}
+
+impl Handler for ConfirmPaymentIntent {
+    type ResponseBody = PaymentIntent;
+    fn method(&self) -> Method {
+        Method::Post
+    }
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        format!("/v1/payment_intents/{}/confirm", self.id).into()
+    }
+    fn request_body(&self, builder: BodyBuilder) -> BodyBuilder {
+        builder.qs(&self.body)
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString};
 
 use crate::{
+    money::Money,
     prelude::{BodyBuilder, Handler, Method},
     stripe::{
         PaymentIntent,
@@ -135,6 +136,11 @@ impl CreatePaymentIntent {
             use_stripe_sdk: None,
         }
     }
+
+    /// Collects `money`, in its currency.
+    pub fn from_money(money: Money) -> Self {
+        Self::new(money.minor_units, money.currency)
+    }
 }
 
 impl Handler for CreatePaymentIntent {
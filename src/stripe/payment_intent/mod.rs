@@ -20,9 +20,32 @@ pub use create_pm_data::*;
 mod create_pm_options;
 pub use create_pm_options::*;
 
+mod retrieve;
+pub use retrieve::*;
+
+mod update;
+pub use update::*;
+
+mod confirm;
+pub use confirm::*;
+
+mod capture;
+pub use capture::*;
+
+mod cancel;
+pub use cancel::*;
+
+mod list;
+pub use list::*;
+
+mod search;
+pub use search::*;
+
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString};
 
+use crate::money::{Currency, Money, MoneyError};
+
 /// A PaymentIntent guides you through the process of collecting a payment from your customer.
 ///
 /// See: <https://docs.stripe.com/api/payment_intents/object>
@@ -107,6 +130,21 @@ pub struct PaymentIntent {
     pub transfer_group: Option<String>,
 }
 
+impl PaymentIntent {
+    /// The amount intended to be collected
+    pub fn money(&self) -> Result<Money, MoneyError> {
+        Ok(Money::new(self.amount, self.currency.parse::<Currency>()?))
+    }
+
+    /// The amount that was collected
+    pub fn received_money(&self) -> Result<Money, MoneyError> {
+        Ok(Money::new(
+            self.amount_received,
+            self.currency.parse::<Currency>()?,
+        ))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct LastPaymentError {
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{GetHandler, SdkParams},
+    stripe::PaymentIntent,
+};
+
+/// Parameters accepted by `GET /v1/payment_intents/:id`.
+///
+/// See: <https://docs.stripe.com/api/payment_intents/retrieve>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetrievePaymentIntent {
+    pub id: String,
+    pub query: RetrievePaymentIntentQuery,
+}
+
+impl RetrievePaymentIntent {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            query: RetrievePaymentIntentQuery::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RetrievePaymentIntentQuery {
+    /// Required when retrieving with a publishable key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<Vec<String>>,
+}
+
+impl GetHandler for RetrievePaymentIntent {
+    type ResponseBody = PaymentIntent;
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        format!("/v1/payment_intents/{}", self.id).into()
+    }
+    fn params(&self) -> impl SdkParams {
+        self.query.clone()
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{GetHandler, SdkParams},
+    stripe::PaymentIntent,
+};
+
+/// Parameters accepted by `GET /v1/payment_intents/search`.
+///
+/// `query` uses the [Search Query Language](https://docs.stripe.com/search#search-query-language),
+/// e.g. `status:'succeeded' AND metadata['order_id']:'6735'`.
+///
+/// See: <https://docs.stripe.com/api/payment_intents/search>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SearchPaymentIntents {
+    pub query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    /// The `next_page` cursor of a previous result.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<Vec<String>>,
+}
+
+impl SearchPaymentIntents {
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct PaymentIntentSearchResult {
+    pub object: String,
+    pub data: Vec<PaymentIntent>,
+    pub has_more: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<i64>,
+    pub url: String,
+}
+
+impl GetHandler for SearchPaymentIntents {
+    type ResponseBody = PaymentIntentSearchResult;
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        "/v1/payment_intents/search".into()
+    }
+    fn params(&self) -> impl SdkParams {
+        self.clone()
+    }
+}
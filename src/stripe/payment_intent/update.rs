@@ -0,0 +1,91 @@
+use ahash::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{BodyBuilder, Handler, Method},
+    stripe::{
+        PaymentIntent,
+        payment_intent::{
+            CaptureMethod, CreatePaymentMethodData, CreatePaymentMethodOptions, CreateShipping,
+            PaymentDetails, PaymentMethodType, SetupFutureUsage,
+        },
+    },
+};
+
+/// Parameters accepted by `POST /v1/payment_intents/:id`.
+///
+/// See: <https://docs.stripe.com/api/payment_intents/update>
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdatePaymentIntent {
+    pub id: String,
+    pub body: UpdatePaymentIntentBody,
+}
+
+impl UpdatePaymentIntent {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            body: UpdatePaymentIntentBody::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UpdatePaymentIntentBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_fee_amount: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capture_method: Option<CaptureMethod>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_account: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_payment_method_types: Option<Vec<PaymentMethodType>>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_details: Option<PaymentDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method_configuration: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method_data: Option<CreatePaymentMethodData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method_options: Option<CreatePaymentMethodOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method_types: Option<Vec<PaymentMethodType>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub setup_future_usage: Option<SetupFutureUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipping: Option<CreateShipping>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statement_descriptor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statement_descriptor_suffix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_group: Option<String>,
+}
+
+impl Handler for UpdatePaymentIntent {
+    type ResponseBody = PaymentIntent;
+    fn method(&self) -> Method {
+        Method::Post
+    }
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        format!("/v1/payment_intents/{}", self.id).into()
+    }
+    fn request_body(&self, builder: BodyBuilder) -> BodyBuilder {
+        builder.qs(&self.body)
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{
+    prelude::{Handler, Method},
+    stripe::refund::Refund,
+};
+
+/// Parameters accepted by `POST /v1/refunds/:id/cancel`.
+///
+/// Only refunds in the `requires_action` state can be canceled.
+///
+/// See: <https://docs.stripe.com/api/refunds/cancel>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CancelRefund {
+    pub id: String,
+}
+
+impl CancelRefund {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+impl Handler for CancelRefund {
+    type ResponseBody = Refund;
+    fn method(&self) -> Method {
+        Method::Post
+    }
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        format!("/v1/refunds/{}/cancel", self.id).into()
+    }
+}
@@ -0,0 +1,77 @@
+use ahash::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{BodyBuilder, Handler, Method},
+    stripe::refund::{Refund, RefundReason},
+};
+
+/// Parameters accepted by `POST /v1/refunds`.
+///
+/// Set exactly one of `charge` or `payment_intent`; `amount` defaults to the
+/// whole remaining amount.
+///
+/// See: <https://docs.stripe.com/api/refunds/create>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateRefund {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charge: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions_email: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_intent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<RefundReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_application_fee: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse_transfer: Option<bool>,
+}
+
+impl CreateRefund {
+    pub fn for_payment_intent(id: impl Into<String>) -> Self {
+        Self {
+            payment_intent: Some(id.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn for_charge(id: impl Into<String>) -> Self {
+        Self {
+            charge: Some(id.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_amount(mut self, amount: i64) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn with_reason(mut self, reason: RefundReason) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+}
+
+impl Handler for CreateRefund {
+    type ResponseBody = Refund;
+    fn method(&self) -> Method {
+        Method::Post
+    }
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        "/v1/refunds".into()
+    }
+    fn request_body(&self, builder: BodyBuilder) -> BodyBuilder {
+        builder.qs(self)
+    }
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{GetHandler, SdkParams},
+    stripe::{CreatedFilter, List, refund::Refund},
+};
+
+/// Parameters accepted by `GET /v1/refunds`.
+///
+/// See: <https://docs.stripe.com/api/refunds/list>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ListRefunds {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charge: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<CreatedFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ending_before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_intent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starting_after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<Vec<String>>,
+}
+
+pub type RefundList = List<Refund>;
+
+impl GetHandler for ListRefunds {
+    type ResponseBody = RefundList;
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        "/v1/refunds".into()
+    }
+    fn params(&self) -> impl SdkParams {
+        self.clone()
+    }
+}
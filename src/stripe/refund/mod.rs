@@ -0,0 +1,106 @@
+//! Types for the Stripe [Refunds](https://docs.stripe.com/api/refunds) API.
+//!
+//! [`Refund`] mirrors the object Stripe returns on every endpoint; the
+//! endpoint-specific request types live in their own submodules.
+
+mod create;
+pub use create::*;
+
+mod retrieve;
+pub use retrieve::*;
+
+mod list;
+pub use list::*;
+
+mod cancel;
+pub use cancel::*;
+
+use ahash::HashMap;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+use crate::money::{Currency, Money, MoneyError};
+
+/// A refund of a previously created charge or PaymentIntent.
+///
+/// See: <https://docs.stripe.com/api/refunds/object>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Refund {
+    pub id: String,
+    pub object: String,
+    pub amount: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_transaction: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charge: Option<String>,
+    pub created: i64,
+    pub currency: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_balance_transaction: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<RefundFailureReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions_email: Option<String>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_intent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<RefundReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_transfer_reversal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<RefundStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_reversal: Option<String>,
+}
+
+impl Refund {
+    /// The amount refunded
+    pub fn money(&self) -> Result<Money, MoneyError> {
+        Ok(Money::new(self.amount, self.currency.parse::<Currency>()?))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum RefundStatus {
+    Pending,
+    RequiresAction,
+    Succeeded,
+    Failed,
+    Canceled,
+}
+
+/// Why a refund was issued. `expired_uncaptured_charge` is only ever set by Stripe.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum RefundReason {
+    Duplicate,
+    ExpiredUncapturedCharge,
+    Fraudulent,
+    RequestedByCustomer,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum RefundFailureReason {
+    ChargeForPendingRefundDisputed,
+    Declined,
+    ExpiredOrCanceledCard,
+    InsufficientFunds,
+    LostOrStolenCard,
+    MerchantRequest,
+    Unknown,
+}
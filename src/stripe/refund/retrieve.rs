@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{GetHandler, SdkParams},
+    stripe::refund::Refund,
+};
+
+/// Parameters accepted by `GET /v1/refunds/:id`.
+///
+/// See: <https://docs.stripe.com/api/refunds/retrieve>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetrieveRefund {
+    pub id: String,
+    pub query: RetrieveRefundQuery,
+}
+
+impl RetrieveRefund {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            query: RetrieveRefundQuery::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RetrieveRefundQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<Vec<String>>,
+}
+
+impl GetHandler for RetrieveRefund {
+    type ResponseBody = Refund;
+    fn path(&self) -> std::borrow::Cow<'_, str> {
+        format!("/v1/refunds/{}", self.id).into()
+    }
+    fn params(&self) -> impl SdkParams {
+        self.query.clone()
+    }
+}
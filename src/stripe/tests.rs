@@ -1,5 +1,5 @@
 use super::*;
-use crate::prelude::{GetHandler, Handler, Method};
+use crate::prelude::{GetHandler, Handler, Method, SdkError};
 use ahash::HashMap;
 
 #[test]
@@ -428,6 +428,158 @@ fn list_checkout_session_line_items_path_has_id() {
     );
 }
 
+fn payment_intent_json(id: &str, status: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "object": "payment_intent",
+        "amount": 2000,
+        "amount_capturable": 0,
+        "amount_received": 0,
+        "capture_method": "manual",
+        "confirmation_method": "automatic",
+        "created": 1680800504,
+        "currency": "usd",
+        "livemode": false,
+        "metadata": {},
+        "payment_method_types": ["card"],
+        "status": status
+    })
+}
+
+#[test]
+fn payment_intent_lifecycle_paths() {
+    let retrieve = RetrievePaymentIntent::new("pi_123");
+    assert_eq!(GetHandler::path(&retrieve), "/v1/payment_intents/pi_123");
+
+    let update = UpdatePaymentIntent::new("pi_123");
+    assert_eq!(update.path(), "/v1/payment_intents/pi_123");
+    assert_eq!(update.method(), Method::Post);
+
+    let confirm = ConfirmPaymentIntent::new("pi_123");
+    assert_eq!(confirm.path(), "/v1/payment_intents/pi_123/confirm");
+    assert_eq!(confirm.method(), Method::Post);
+
+    let capture = CapturePaymentIntent::new("pi_123");
+    assert_eq!(capture.path(), "/v1/payment_intents/pi_123/capture");
+    assert_eq!(capture.method(), Method::Post);
+
+    let cancel = CancelPaymentIntent::new("pi_123");
+    assert_eq!(cancel.path(), "/v1/payment_intents/pi_123/cancel");
+    assert_eq!(cancel.method(), Method::Post);
+
+    assert_eq!(
+        GetHandler::path(&ListPaymentIntents::default()),
+        "/v1/payment_intents"
+    );
+    assert_eq!(
+        GetHandler::path(&SearchPaymentIntents::new("status:'succeeded'")),
+        "/v1/payment_intents/search"
+    );
+}
+
+#[test]
+fn confirm_body_reuses_create_payment_method_options() {
+    let mut confirm = ConfirmPaymentIntent::new("pi_123");
+    confirm.body.payment_method = Some("pm_card_visa".into());
+    confirm.body.return_url = Some("https://example.com/return".into());
+    confirm.body.payment_method_options = Some(
+        serde_json::from_value(serde_json::json!({
+            "card": { "capture_method": "manual" }
+        }))
+        .expect("card options deserialize"),
+    );
+
+    let encoded = serde_qs::to_string(&confirm.body).expect("serializes as form-qs");
+    assert!(
+        encoded.contains("payment_method=pm_card_visa"),
+        "got: {encoded}"
+    );
+    assert!(
+        encoded.contains("payment_method_options[card][capture_method]=manual"),
+        "got: {encoded}"
+    );
+    assert!(encoded.contains("return_url="), "got: {encoded}");
+    assert!(!encoded.contains("off_session"), "got: {encoded}");
+}
+
+#[test]
+fn update_capture_and_cancel_bodies_skip_unset_fields() {
+    let mut update = UpdatePaymentIntent::new("pi_123");
+    update.body.amount = Some(1500);
+    update
+        .body
+        .metadata
+        .insert("order_id".into(), "6735".into());
+    let encoded = serde_qs::to_string(&update.body).expect("serializes as form-qs");
+    assert_eq!(encoded, "amount=1500&metadata[order_id]=6735");
+
+    let mut capture = CapturePaymentIntent::new("pi_123");
+    capture.body.amount_to_capture = Some(750);
+    capture.body.final_capture = Some(false);
+    let encoded = serde_qs::to_string(&capture.body).expect("serializes as form-qs");
+    assert_eq!(encoded, "amount_to_capture=750&final_capture=false");
+
+    let cancel = CancelPaymentIntent::new("pi_123").with_reason(CancelReason::RequestedByCustomer);
+    let encoded = serde_qs::to_string(&cancel.body).expect("serializes as form-qs");
+    assert_eq!(encoded, "cancellation_reason=requested_by_customer");
+
+    let encoded = serde_qs::to_string(&CancelPaymentIntent::new("pi_123").body).unwrap();
+    assert_eq!(encoded, "");
+}
+
+#[test]
+fn list_payment_intents_query_and_response() {
+    let list = ListPaymentIntents {
+        customer: Some("cus_123".into()),
+        created: Some(CreatedFilter {
+            gte: Some(1680000000),
+            ..Default::default()
+        }),
+        limit: Some(3),
+        ..Default::default()
+    };
+    let query = serde_qs::to_string(&list).expect("serializes as query");
+    assert_eq!(query, "created[gte]=1680000000&customer=cus_123&limit=3");
+
+    let json = serde_json::json!({
+        "object": "list",
+        "url": "/v1/payment_intents",
+        "has_more": true,
+        "data": [
+            payment_intent_json("pi_1", "requires_capture"),
+            payment_intent_json("pi_2", "succeeded"),
+        ]
+    });
+    let page: PaymentIntentList = serde_json::from_value(json).expect("should deserialize");
+    assert!(page.has_more);
+    assert_eq!(page.data.len(), 2);
+    assert_eq!(page.data[0].status, PaymentIntentStatus::RequiresCapture);
+    assert_eq!(page.data[1].id, "pi_2");
+}
+
+#[test]
+fn search_payment_intents_query_and_response() {
+    let mut search =
+        SearchPaymentIntents::new("status:'succeeded' AND metadata['order_id']:'6735'");
+    search.page = Some("page_abc".into());
+    let query = serde_qs::to_string(&search).expect("serializes as query");
+    assert!(query.starts_with("query=status"), "got: {query}");
+    assert!(query.ends_with("&page=page_abc"), "got: {query}");
+
+    let json = serde_json::json!({
+        "object": "search_result",
+        "url": "/v1/payment_intents/search",
+        "has_more": false,
+        "next_page": null,
+        "data": [payment_intent_json("pi_1", "succeeded")]
+    });
+    let result: PaymentIntentSearchResult =
+        serde_json::from_value(json).expect("should deserialize");
+    assert!(!result.has_more);
+    assert_eq!(result.next_page, None);
+    assert_eq!(result.data[0].status, PaymentIntentStatus::Succeeded);
+}
+
 #[test]
 fn webhook_event_deserializes_checkout_session_completed() {
     let raw = r#"{
@@ -531,17 +683,17 @@ fn webhook_event_preserves_unknown_event_type_as_raw_object() {
         "id": "evt_other",
         "object": "event",
         "created": 1679600215,
-        "data": { "object": { "id": "ch_test", "object": "charge" } },
+        "data": { "object": { "id": "in_test", "object": "invoice" } },
         "livemode": false,
         "pending_webhooks": 0,
-        "type": "charge.refunded"
+        "type": "invoice.paid"
     }"#;
 
     let event: WebhookEvent = serde_json::from_str(raw).expect("should deserialize");
     match event.kind {
         WebhookEventKind::Unknown { type_name, object } => {
-            assert_eq!(type_name, "charge.refunded");
-            assert_eq!(object["id"].as_str(), Some("ch_test"));
+            assert_eq!(type_name, "invoice.paid");
+            assert_eq!(object["id"].as_str(), Some("in_test"));
         }
         other => panic!("expected Unknown, got {:?}", other),
     }
@@ -569,3 +721,559 @@ fn webhook_event_surfaces_nested_parse_errors() {
         "error should name the event type, got: {msg}"
     );
 }
+
+#[test]
+fn create_refund_body_targets_payment_intent() {
+    let refund = CreateRefund::for_payment_intent("pi_123")
+        .with_amount(500)
+        .with_reason(RefundReason::RequestedByCustomer);
+    assert_eq!(refund.path(), "/v1/refunds");
+    assert_eq!(refund.method(), Method::Post);
+
+    let encoded = serde_qs::to_string(&refund).expect("serializes as form-qs");
+    assert_eq!(
+        encoded,
+        "amount=500&payment_intent=pi_123&reason=requested_by_customer"
+    );
+}
+
+#[test]
+fn refund_handler_paths_and_list_response() {
+    assert_eq!(
+        GetHandler::path(&RetrieveRefund::new("re_123")),
+        "/v1/refunds/re_123"
+    );
+    let cancel = CancelRefund::new("re_123");
+    assert_eq!(cancel.path(), "/v1/refunds/re_123/cancel");
+    assert_eq!(cancel.method(), Method::Post);
+
+    let json = serde_json::json!({
+        "object": "list",
+        "url": "/v1/refunds",
+        "has_more": false,
+        "data": [{
+            "id": "re_1Nispe2eZvKYlo2Cd31jOCgZ",
+            "object": "refund",
+            "amount": 1000,
+            "balance_transaction": "txn_1Nispe2eZvKYlo2CYezqFhEx",
+            "charge": "ch_1NirD82eZvKYlo2CIvbtLWuY",
+            "created": 1692942318,
+            "currency": "usd",
+            "destination_details": { "card": { "type": "refund" }, "type": "card" },
+            "metadata": {},
+            "payment_intent": "pi_1GszsK2eZvKYlo2CfhZyoZLp",
+            "reason": null,
+            "receipt_number": null,
+            "source_transfer_reversal": null,
+            "status": "succeeded",
+            "transfer_reversal": null
+        }]
+    });
+    let list: RefundList = serde_json::from_value(json).expect("should deserialize");
+    let refund = &list.data[0];
+    assert_eq!(refund.amount, 1000);
+    assert_eq!(refund.status, Some(RefundStatus::Succeeded));
+    assert_eq!(refund.reason, None);
+}
+
+#[test]
+fn customer_handlers_carry_metadata_and_delete() {
+    let create = CreateCustomer::default()
+        .with_email("jo@example.com")
+        .with_metadata("member_id", "42");
+    let encoded = serde_qs::to_string(&create).expect("serializes as form-qs");
+    assert_eq!(encoded, "email=jo@example.com&metadata[member_id]=42");
+
+    let mut update = UpdateCustomer::new("cus_123");
+    update.body.name = Some("Jo".into());
+    assert_eq!(update.path(), "/v1/customers/cus_123");
+    assert_eq!(
+        serde_qs::to_string(&update.body).expect("serializes as form-qs"),
+        "name=Jo"
+    );
+
+    let delete = DeleteCustomer::new("cus_123");
+    assert_eq!(delete.method(), Method::Delete);
+    assert_eq!(delete.path(), "/v1/customers/cus_123");
+    let deleted: DeletedCustomer = serde_json::from_value(serde_json::json!({
+        "id": "cus_123", "object": "customer", "deleted": true
+    }))
+    .expect("should deserialize");
+    assert!(deleted.deleted);
+
+    let list = ListCustomers {
+        email: Some("jo@example.com".into()),
+        ..Default::default()
+    };
+    assert_eq!(GetHandler::path(&list), "/v1/customers");
+    assert_eq!(
+        GetHandler::path(&RetrieveCustomer::new("cus_123")),
+        "/v1/customers/cus_123"
+    );
+}
+
+fn charge_json() -> serde_json::Value {
+    serde_json::json!({
+        "id": "ch_3MmlLrLkdIwHu7ix0snN0B15",
+        "object": "charge",
+        "amount": 1099,
+        "amount_captured": 1099,
+        "amount_refunded": 1099,
+        "application_fee_amount": null,
+        "balance_transaction": "txn_3MmlLrLkdIwHu7ix0uke3Ezy",
+        "billing_details": {
+            "address": {
+                "city": null,
+                "country": null,
+                "line1": null,
+                "line2": null,
+                "postal_code": null,
+                "state": null
+            },
+            "email": null,
+            "name": null,
+            "phone": null
+        },
+        "calculated_statement_descriptor": "Stripe",
+        "captured": true,
+        "created": 1679090539,
+        "currency": "usd",
+        "customer": "cus_123",
+        "description": null,
+        "disputed": false,
+        "failure_code": null,
+        "failure_message": null,
+        "livemode": false,
+        "metadata": {},
+        "outcome": {
+            "network_status": "approved_by_network",
+            "reason": null,
+            "risk_level": "normal",
+            "risk_score": 32,
+            "seller_message": "Payment complete.",
+            "type": "authorized"
+        },
+        "paid": true,
+        "payment_intent": "pi_123",
+        "payment_method": "card_1MmlLrLkdIwHu7ixIJwEWSNR",
+        "receipt_email": null,
+        "receipt_number": null,
+        "receipt_url": "https://pay.stripe.com/receipts/payment/abc",
+        "refunded": true,
+        "status": "succeeded"
+    })
+}
+
+#[test]
+fn charge_handlers_and_payload() {
+    assert_eq!(
+        GetHandler::path(&RetrieveCharge::new("ch_123")),
+        "/v1/charges/ch_123"
+    );
+    let list = ListCharges {
+        payment_intent: Some("pi_123".into()),
+        ..Default::default()
+    };
+    assert_eq!(GetHandler::path(&list), "/v1/charges");
+    assert_eq!(
+        serde_qs::to_string(&list).expect("serializes as query"),
+        "payment_intent=pi_123"
+    );
+
+    let charge: Charge = serde_json::from_value(charge_json()).expect("should deserialize");
+    assert_eq!(charge.status, ChargeStatus::Succeeded);
+    assert_eq!(charge.amount_refunded, 1099);
+    assert!(charge.refunded);
+    assert_eq!(charge.outcome.unwrap().outcome_type, "authorized");
+    assert_eq!(charge.refunds, None);
+}
+
+#[test]
+fn stripe_amounts_convert_to_money() {
+    use crate::money::{Currency, Money};
+
+    let yen = Money::new(500, Currency::JPY);
+    let create = CreatePaymentIntent::from_money(yen);
+    assert_eq!((create.amount, create.currency.as_str()), (500, "jpy"));
+
+    let mut json = payment_intent_json("pi_123", "succeeded");
+    json["amount_received"] = 2000.into();
+    let intent: PaymentIntent = serde_json::from_value(json).unwrap();
+    assert_eq!(intent.money().unwrap().to_string(), "20.00 USD");
+    assert_eq!(intent.received_money(), intent.money());
+
+    let charge: Charge = serde_json::from_value(charge_json()).unwrap();
+    assert_eq!(
+        charge.refunded_money().unwrap(),
+        Money::new(1099, Currency::USD)
+    );
+}
+
+#[test]
+fn webhook_event_deserializes_charge_refunded_and_customer_events() {
+    let event: WebhookEvent = serde_json::from_value(serde_json::json!({
+        "id": "evt_refunded",
+        "object": "event",
+        "created": 1679090600,
+        "data": { "object": charge_json() },
+        "livemode": false,
+        "pending_webhooks": 1,
+        "type": "charge.refunded"
+    }))
+    .expect("should deserialize");
+    assert_eq!(event.kind.type_name(), "charge.refunded");
+    match event.kind {
+        WebhookEventKind::ChargeRefunded(charge) => assert_eq!(charge.amount_refunded, 1099),
+        other => panic!("expected ChargeRefunded, got {:?}", other),
+    }
+
+    let event: WebhookEvent = serde_json::from_value(serde_json::json!({
+        "id": "evt_customer",
+        "object": "event",
+        "created": 1680064028,
+        "data": {
+            "object": {
+                "id": "cus_NffrFeUfNV2Hib",
+                "object": "customer",
+                "address": null,
+                "balance": 0,
+                "created": 1680893993,
+                "currency": null,
+                "default_source": null,
+                "delinquent": false,
+                "description": null,
+                "email": "jennyrosen@example.com",
+                "invoice_prefix": "0759376C",
+                "livemode": false,
+                "metadata": { "member_id": "42" },
+                "name": "Jenny Rosen",
+                "phone": null,
+                "preferred_locales": null,
+                "shipping": null,
+                "tax_exempt": "none"
+            }
+        },
+        "livemode": false,
+        "pending_webhooks": 0,
+        "type": "customer.deleted"
+    }))
+    .expect("should deserialize");
+    match event.kind {
+        WebhookEventKind::CustomerDeleted(customer) => {
+            assert_eq!(
+                customer.metadata.get("member_id").map(String::as_str),
+                Some("42")
+            );
+            assert_eq!(customer.tax_exempt, Some(TaxExempt::None));
+            assert!(customer.preferred_locales.is_empty());
+        }
+        other => panic!("expected CustomerDeleted, got {:?}", other),
+    }
+}
+
+#[test]
+fn idempotent_handlers_send_their_key() {
+    let create = CreatePaymentIntent::new(2000, "usd")
+        .with_idempotency_key("order-6735")
+        .unwrap();
+    assert_eq!(create.path(), "/v1/payment_intents");
+    assert_eq!(create.method(), Method::Post);
+
+    let mut headers = http::HeaderMap::new();
+    create.headers(&mut headers);
+    assert_eq!(headers[IDEMPOTENCY_KEY], "order-6735");
+
+    // the generated key is fixed once wrapped, so a clone retries with the same key
+    let refund = CreateRefund::for_charge("ch_123").idempotent();
+    let retry = refund.clone();
+    assert_eq!(refund.key(), retry.key());
+    assert_ne!(
+        refund.key(),
+        CreateRefund::for_charge("ch_123").idempotent().key()
+    );
+    assert!(uuid::Uuid::parse_str(refund.key()).is_ok());
+
+    // a key that can't be sent is refused up front, instead of the request going out unkeyed
+    let refund = CreateRefund::for_charge("ch_123");
+    assert_eq!(
+        refund.clone().with_idempotency_key(""),
+        Err(IdempotencyKeyError::Empty)
+    );
+    assert_eq!(
+        refund.clone().with_idempotency_key("k".repeat(256)),
+        Err(IdempotencyKeyError::TooLong(256))
+    );
+    assert!(refund.clone().with_idempotency_key("k".repeat(255)).is_ok());
+    assert_eq!(
+        refund.clone().with_idempotency_key("café-6735"),
+        Err(IdempotencyKeyError::InvalidHeader("café-6735".into()))
+    );
+    assert_eq!(
+        refund.with_idempotency_key("order\n6735"),
+        Err(IdempotencyKeyError::InvalidHeader("order\n6735".into()))
+    );
+}
+
+#[test]
+fn stripe_error_parses_from_failed_responses() {
+    let body = r#"{
+        "error": {
+            "charge": "ch_3Lz8Wq2eZvKYlo2C0oJd2pO2",
+            "code": "card_declined",
+            "decline_code": "insufficient_funds",
+            "doc_url": "https://stripe.com/docs/error-codes/card-declined",
+            "message": "Your card has insufficient funds.",
+            "request_log_url": "https://dashboard.stripe.com/test/logs/req_abc?t=1667000000",
+            "type": "card_error"
+        }
+    }"#;
+
+    let error = SdkError::status(http::StatusCode::PAYMENT_REQUIRED, body)
+        .stripe_error()
+        .expect("is a Stripe error");
+    assert!(error.is_card_error());
+    assert_eq!(error.decline_code.as_deref(), Some("insufficient_funds"));
+    assert_eq!(error.param, None);
+    assert_eq!(
+        error.request_log_url.as_deref(),
+        Some("https://dashboard.stripe.com/test/logs/req_abc?t=1667000000")
+    );
+    assert_eq!(
+        error.to_string(),
+        "Stripe card_error: Your card has insufficient funds."
+    );
+
+    let idempotency = StripeError::from_body(
+        r#"{"error": {"type": "idempotency_error", "message": "Keys for idempotent requests can only be used with the same parameters they were first used with."}}"#,
+    )
+    .expect("parses");
+    assert!(idempotency.is_idempotency_error());
+
+    assert_eq!(StripeError::from_body("<html>Bad Gateway</html>"), None);
+    assert_eq!(SdkError::message("timed out").stripe_error(), None);
+}
+
+#[test]
+fn account_handlers_and_onboarding_link() {
+    let create = CreateAccount::new(AccountType::Express)
+        .with_country("US")
+        .with_capability("transfers");
+    assert_eq!(create.path(), "/v1/accounts");
+    assert_eq!(
+        serde_qs::to_string(&create).expect("serializes as form-qs"),
+        "type=express&capabilities[transfers][requested]=true&country=US"
+    );
+
+    assert_eq!(GetHandler::path(&RetrieveAccount::current()), "/v1/account");
+    assert_eq!(
+        GetHandler::path(&RetrieveAccount::new("acct_123")),
+        "/v1/accounts/acct_123"
+    );
+    assert_eq!(GetHandler::path(&ListAccounts::default()), "/v1/accounts");
+
+    let link = CreateAccountLink::onboarding(
+        "acct_123",
+        "https://example.com/reauth",
+        "https://example.com/return",
+    );
+    assert_eq!(link.path(), "/v1/account_links");
+    let encoded = serde_qs::to_string(&link).expect("serializes as form-qs");
+    assert!(
+        encoded.contains("type=account_onboarding"),
+        "got: {encoded}"
+    );
+
+    let account: Account = serde_json::from_value(serde_json::json!({
+        "id": "acct_1Nv0FGQ9RKHgCVdK",
+        "object": "account",
+        "business_profile": { "name": null, "url": null, "mcc": null },
+        "capabilities": { "transfers": "pending" },
+        "charges_enabled": false,
+        "country": "US",
+        "created": 1695830751,
+        "default_currency": "usd",
+        "details_submitted": false,
+        "email": "jenny.rosen@example.com",
+        "metadata": {},
+        "payouts_enabled": false,
+        "requirements": {
+            "current_deadline": null,
+            "currently_due": ["business_profile.url", "external_account"],
+            "disabled_reason": "requirements.past_due",
+            "eventually_due": null,
+            "past_due": [],
+            "pending_verification": []
+        },
+        "type": "express"
+    }))
+    .expect("should deserialize");
+    assert_eq!(account.account_type, Some(AccountType::Express));
+    assert_eq!(account.capabilities["transfers"], CapabilityStatus::Pending);
+    assert_eq!(account.requirements.unwrap().currently_due.len(), 2);
+}
+
+#[tokio::test]
+async fn stripe_client_sends_account_version_and_idempotency_headers() {
+    use crate::prelude::HandlerExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = vec![0; 8192];
+        let read = socket.read(&mut request).await.unwrap();
+        let body = r#"{"object":"account_link","created":1,"expires_at":2,"url":"https://connect.stripe.com/setup/e/acct_123/abc"}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&request[..read]).to_lowercase()
+    });
+
+    let mut stripe = client::StripeClient::new("sk_test_123").with_version("2025-08-27.basil");
+    stripe.base = base.parse().unwrap();
+    let link = CreateAccountLink::onboarding("acct_123", "https://a.test", "https://b.test")
+        .request(&stripe.for_account("acct_123"))
+        .await
+        .expect("request succeeds");
+    assert_eq!(link.expires_at, 2);
+
+    let request = server.await.unwrap();
+    assert!(request.contains("stripe-account: acct_123"), "{request}");
+    assert!(
+        request.contains("stripe-version: 2025-08-27.basil"),
+        "{request}"
+    );
+    assert!(request.contains("idempotency-key: "), "{request}");
+}
+
+fn customer_event(id: &str, event_type: &str, created: i64) -> WebhookEvent {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "object": "event",
+        "created": created,
+        "data": {
+            "object": {
+                "id": "cus_123",
+                "object": "customer",
+                "created": 1680893993,
+                "livemode": false,
+                "metadata": {}
+            }
+        },
+        "livemode": false,
+        "pending_webhooks": 1,
+        "type": event_type
+    }))
+    .expect("should deserialize")
+}
+
+#[tokio::test]
+async fn dispatcher_routes_by_type_in_created_order_once() {
+    use std::sync::{Arc, Mutex};
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let customers = seen.clone();
+    let unknown = seen.clone();
+    let dispatcher = WebhookDispatcher::default()
+        .on("customer.created", move |customer: Customer, event| {
+            customers
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", event.id, customer.id));
+            async { Ok(()) }
+        })
+        .on_unknown(move |type_name, object, _| {
+            unknown
+                .lock()
+                .unwrap()
+                .push(format!("{type_name} {}", object["id"].as_str().unwrap()));
+            async { Ok(()) }
+        });
+
+    let invoice: WebhookEvent = serde_json::from_value(serde_json::json!({
+        "id": "evt_invoice",
+        "object": "event",
+        "created": 20,
+        "data": { "object": { "id": "in_123", "object": "invoice" } },
+        "livemode": false,
+        "type": "invoice.paid"
+    }))
+    .unwrap();
+    let events = vec![
+        invoice,
+        customer_event("evt_created", "customer.created", 10),
+        customer_event("evt_updated", "customer.updated", 30),
+        customer_event("evt_created", "customer.created", 10),
+    ];
+
+    let dispatched = dispatcher.dispatch_all(events).await.unwrap();
+    assert_eq!(
+        dispatched,
+        [
+            Dispatched::Handled,
+            Dispatched::Duplicate,
+            Dispatched::Handled,
+            Dispatched::Ignored,
+        ]
+    );
+    assert_eq!(
+        *seen.lock().unwrap(),
+        ["evt_created cus_123", "invoice.paid in_123"]
+    );
+}
+
+#[tokio::test]
+async fn dispatcher_retries_events_whose_handler_failed() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let counter = attempts.clone();
+    let dispatcher = WebhookDispatcher::new(InMemoryEventStore::new().with_max_events(10)).on(
+        "customer.deleted",
+        move |_: Customer, _| {
+            let attempt = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                anyhow::ensure!(attempt > 0, "database unavailable");
+                Ok(())
+            }
+        },
+    );
+
+    let event = customer_event("evt_deleted", "customer.deleted", 10);
+    let err = dispatcher.dispatch(&event).await.unwrap_err();
+    assert!(
+        matches!(&err, DispatchError::Handler { event_id, .. } if event_id == "evt_deleted"),
+        "{err}"
+    );
+    assert!(!dispatcher.store().contains("evt_deleted").await.unwrap());
+
+    assert_eq!(
+        dispatcher.dispatch(&event).await.unwrap(),
+        Dispatched::Handled
+    );
+    assert_eq!(
+        dispatcher.dispatch(&event).await.unwrap(),
+        Dispatched::Duplicate
+    );
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn in_memory_event_store_forgets_the_oldest_ids() {
+    let store = InMemoryEventStore::new().with_max_events(2);
+    for id in ["evt_1", "evt_2", "evt_3"] {
+        store.insert(id).await.unwrap();
+    }
+    assert!(!store.contains("evt_1").await.unwrap());
+    assert!(store.contains("evt_3").await.unwrap());
+}
+
+#[test]
+#[should_panic(expected = "do not carry")]
+fn dispatcher_rejects_handlers_for_the_wrong_payload() {
+    let _ = WebhookDispatcher::default().on("customer.created", |_: Charge, _| async { Ok(()) });
+}
@@ -8,7 +8,7 @@
 use serde::Deserialize;
 use serde::de::{self, Deserializer};
 
-use crate::stripe::{CheckoutSession, PaymentIntent};
+use crate::stripe::{Charge, CheckoutSession, Customer, PaymentIntent, Refund};
 
 /// A Stripe webhook event.
 ///
@@ -51,6 +51,12 @@ pub enum WebhookEventKind {
 	PaymentIntentCanceled(PaymentIntent),
 	PaymentIntentProcessing(PaymentIntent),
 	PaymentIntentRequiresAction(PaymentIntent),
+	ChargeRefunded(Charge),
+	RefundUpdated(Refund),
+	CustomerCreated(Customer),
+	CustomerUpdated(Customer),
+	/// Carries the customer as it was just before deletion.
+	CustomerDeleted(Customer),
 	/// An event type we don't model as a typed variant yet.
 	Unknown {
 		type_name: String,
@@ -74,6 +80,11 @@ impl WebhookEventKind {
 			Self::PaymentIntentCanceled(_) => "payment_intent.canceled",
 			Self::PaymentIntentProcessing(_) => "payment_intent.processing",
 			Self::PaymentIntentRequiresAction(_) => "payment_intent.requires_action",
+			Self::ChargeRefunded(_) => "charge.refunded",
+			Self::RefundUpdated(_) => "refund.updated",
+			Self::CustomerCreated(_) => "customer.created",
+			Self::CustomerUpdated(_) => "customer.updated",
+			Self::CustomerDeleted(_) => "customer.deleted",
 			Self::Unknown { type_name, .. } => type_name,
 		}
 	}
@@ -178,6 +189,31 @@ impl<'de> Deserialize<'de> for WebhookEvent {
 				raw.data.object,
 				WebhookEventKind::PaymentIntentRequiresAction,
 			)?,
+			"charge.refunded" => typed::<Charge, D::Error, _>(
+				&raw.event_type,
+				raw.data.object,
+				WebhookEventKind::ChargeRefunded,
+			)?,
+			"refund.updated" => typed::<Refund, D::Error, _>(
+				&raw.event_type,
+				raw.data.object,
+				WebhookEventKind::RefundUpdated,
+			)?,
+			"customer.created" => typed::<Customer, D::Error, _>(
+				&raw.event_type,
+				raw.data.object,
+				WebhookEventKind::CustomerCreated,
+			)?,
+			"customer.updated" => typed::<Customer, D::Error, _>(
+				&raw.event_type,
+				raw.data.object,
+				WebhookEventKind::CustomerUpdated,
+			)?,
+			"customer.deleted" => typed::<Customer, D::Error, _>(
+				&raw.event_type,
+				raw.data.object,
+				WebhookEventKind::CustomerDeleted,
+			)?,
 			other => WebhookEventKind::Unknown {
 				type_name: other.to_string(),
 				object: raw.data.object,
@@ -4,9 +4,10 @@ use ahash::HashMap;
 use tracing::info;
 
 use crate::prelude::*;
+use serde::Serialize;
 
 /// Result of syncing jurisdiction and its chambers
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct JurisdictionAndChambersSyncResult {
     pub jurisdiction_id: i32,
     pub jurisdiction_name: String,
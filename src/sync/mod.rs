@@ -20,17 +20,18 @@ pub use vote_utility::*;
 
 use crate::prelude::*;
 use chrono::NaiveDate;
+use serde::Serialize;
 use url::Url;
 
 /// Result of syncing members
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MembersSyncResult {
     pub created: Vec<Arc<MemberWithPartyView>>,
     pub updated: Vec<Arc<MemberWithPartyView>>,
 }
 
 /// Result of syncing legislation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct LegislationSyncResult {
     pub created: Vec<LegislationView>,
     pub updated: Vec<LegislationView>,
@@ -38,14 +39,14 @@ pub struct LegislationSyncResult {
     pub stopped_early: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct LegislationDetailSyncResult {
     pub legislation: LegislationView,
     pub votes: Option<VotesSyncResult>,
 }
 
 /// Result of syncing votes
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct VotesSyncResult {
     pub created: Vec<i32>, // Vote IDs
     pub unchanged: Vec<i32>,
@@ -74,7 +74,7 @@ impl<'caller, 'client, E: ExternalClient, P: Client> LegislationSync<'caller, 'c
         let mut stopped_early = false;
 
         loop {
-            println!(
+            info!(
                 "External Fetch Legislation Request for {}, (page: {ext_page}, page_size: {page_size})",
                 self.session
             );
@@ -11,9 +11,10 @@ use ahash::HashMap;
 use tracing::info;
 
 use crate::prelude::*;
+use serde::Serialize;
 
 /// Result of syncing sessions
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SessionsSyncResult {
     pub created: Vec<SessionView>,
     pub updated: Vec<SessionView>,
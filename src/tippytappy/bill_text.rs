@@ -0,0 +1,589 @@
+use crate::tippytappy::*;
+
+/// Sections become level 2 headings, leaving level 1 for the bill's title.
+const SECTION_HEADING_LEVEL: u32 = 2;
+
+/// Section titles longer than this are split into a heading and a paragraph.
+const MAX_SECTION_TITLE: usize = 80;
+
+impl DocumentView {
+    /// Parses plain bill text.
+    ///
+    /// Blank lines separate paragraphs, and hard-wrapped lines are joined back together.
+    /// A line starting with a section label (`SECTION 1.`, `Sec. 2.`, `§ 3.`) or a
+    /// subsection number (`(a)`, `(1)`, `(iv)`, `2.`) always starts a new paragraph.
+    /// The result is then passed through [`DocumentView::structure_bill_text`].
+    pub fn parse_bill_text(text: &str) -> Self {
+        let mut paragraphs = Vec::new();
+        let mut current = String::new();
+        for line in text.lines() {
+            let line = line.trim();
+            let starts_block =
+                parse_section_label(line).is_some() || parse_enumerator(line).is_some();
+            if (line.is_empty() || starts_block) && !current.is_empty() {
+                paragraphs.push(std::mem::take(&mut current));
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(line);
+        }
+        if !current.is_empty() {
+            paragraphs.push(current);
+        }
+
+        Self::from_nodes(paragraphs.into_iter().map(|text| Node::Paragraph {
+            content: vec![TextNodeView::Text(Text {
+                text,
+                marks: vec![],
+            })],
+        }))
+        .structure_bill_text()
+    }
+
+    /// Recognizes the structure of bill text in a flat run of paragraphs.
+    ///
+    /// - paragraphs starting with a section label become headings
+    /// - runs of numbered paragraphs become [`OrderedList`]s, nested by numbering
+    ///   style, e.g. `(a)` > `(1)` > `(A)` > `(i)`
+    ///
+    /// Blockquotes are structured as well. Everything else is left untouched.
+    pub fn structure_bill_text(self) -> Self {
+        Self::from_nodes(structure_nodes(self.into_nodes()))
+    }
+}
+
+fn structure_nodes(nodes: Vec<Node<View>>) -> Vec<Node<View>> {
+    let mut structurer = Structurer::default();
+    for node in nodes {
+        structurer.push(node);
+    }
+    structurer.finish()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Numbering {
+    Decimal,
+    LowerAlpha,
+    UpperAlpha,
+    LowerRoman,
+    UpperRoman,
+}
+
+impl Numbering {
+    /// The html `type` of an ordered list using this numbering.
+    fn list_type(self) -> Option<String> {
+        let list_type = match self {
+            Numbering::Decimal => return None,
+            Numbering::LowerAlpha => "a",
+            Numbering::UpperAlpha => "A",
+            Numbering::LowerRoman => "i",
+            Numbering::UpperRoman => "I",
+        };
+        Some(list_type.to_string())
+    }
+}
+
+/// A subsection number such as `(b)` at the start of a paragraph.
+#[derive(Debug, PartialEq, Eq)]
+struct Enumerator {
+    /// `(i)` may be the ninth letter or the first roman numeral, so a token can have several readings.
+    candidates: Vec<(Numbering, u32)>,
+    /// Bytes taken up by the enumerator and the whitespace after it.
+    len: usize,
+}
+
+fn parse_enumerator(text: &str) -> Option<Enumerator> {
+    let trimmed = text.trim_start();
+    let offset = text.len() - trimmed.len();
+
+    let (token, end, parenthesized) = match trimmed.strip_prefix('(') {
+        Some(rest) => {
+            let close = rest.find(')')?;
+            (&rest[..close], close + 2, true)
+        }
+        None => {
+            let dot = trimmed.find('.')?;
+            (&trimmed[..dot], dot + 1, false)
+        }
+    };
+
+    let after = &trimmed[end..];
+    if token.is_empty()
+        || token.len() > 8
+        || !(after.is_empty() || after.starts_with(char::is_whitespace))
+    {
+        return None;
+    }
+
+    let candidates = enumerator_candidates(token, parenthesized);
+    if candidates.is_empty() {
+        return None;
+    }
+    let whitespace = after.len() - after.trim_start().len();
+
+    Some(Enumerator {
+        candidates,
+        len: offset + end + whitespace,
+    })
+}
+
+fn enumerator_candidates(token: &str, parenthesized: bool) -> Vec<(Numbering, u32)> {
+    if token.bytes().all(|b| b.is_ascii_digit()) {
+        return token
+            .parse()
+            .map(|value| vec![(Numbering::Decimal, value)])
+            .unwrap_or_default();
+    }
+
+    let mut candidates = Vec::new();
+    let mut chars = token.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_lowercase() {
+            candidates.push((Numbering::LowerAlpha, c as u32 - 'a' as u32 + 1));
+        } else if c.is_ascii_uppercase() {
+            candidates.push((Numbering::UpperAlpha, c as u32 - 'A' as u32 + 1));
+        }
+    }
+
+    // `(iv)` and `IV.` are common, but a lowercase word followed by a period is just prose.
+    let is_upper = token.bytes().all(|b| b.is_ascii_uppercase());
+    if (parenthesized || is_upper)
+        && let Some(value) = parse_roman(token)
+    {
+        let numbering = if is_upper {
+            Numbering::UpperRoman
+        } else {
+            Numbering::LowerRoman
+        };
+        candidates.push((numbering, value));
+    }
+    candidates
+}
+
+fn parse_roman(token: &str) -> Option<u32> {
+    let is_lower = token.bytes().all(|b| b.is_ascii_lowercase());
+    let is_upper = token.bytes().all(|b| b.is_ascii_uppercase());
+    if token.is_empty() || !(is_lower || is_upper) {
+        return None;
+    }
+
+    let digit = |c: u8| match c.to_ascii_lowercase() {
+        b'i' => Some(1),
+        b'v' => Some(5),
+        b'x' => Some(10),
+        b'l' => Some(50),
+        b'c' => Some(100),
+        b'd' => Some(500),
+        b'm' => Some(1000),
+        _ => None,
+    };
+    let digits = token.bytes().map(digit).collect::<Option<Vec<u32>>>()?;
+
+    let mut value = 0;
+    for (i, digit) in digits.iter().enumerate() {
+        match digits.get(i + 1) {
+            Some(next) if next > digit => value -= *digit as i64,
+            _ => value += *digit as i64,
+        }
+    }
+    let value = u32::try_from(value).ok().filter(|v| *v > 0)?;
+
+    // reject malformed numerals like `iiii` or `vx` by round-tripping.
+    (to_roman(value).eq_ignore_ascii_case(token)).then_some(value)
+}
+
+fn to_roman(mut value: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+    let mut roman = String::new();
+    for (amount, numeral) in NUMERALS {
+        while value >= amount {
+            roman.push_str(numeral);
+            value -= amount;
+        }
+    }
+    roman
+}
+
+/// Returns the number of bytes taken up by a section label like `SECTION 1.` and the whitespace after it.
+fn parse_section_label(text: &str) -> Option<usize> {
+    let trimmed = text.trim_start();
+    let offset = text.len() - trimmed.len();
+
+    let prefix = ["section", "sec.", "§§", "§"].into_iter().find(|prefix| {
+        trimmed
+            .get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+    })?;
+    let rest = &trimmed[prefix.len()..];
+    let number = rest.trim_start();
+    if !number.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let token_len = number
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '-'))
+        .unwrap_or(number.len());
+    let after = &number[token_len..];
+    // "Section 5 of the code" is prose, "Section 5." is a label.
+    if !(number[..token_len].ends_with('.') || after.trim().is_empty()) {
+        return None;
+    }
+    let whitespace = after.len() - after.trim_start().len();
+
+    Some(offset + (trimmed.len() - number.len()) + token_len + whitespace)
+}
+
+/// The text at the start of a paragraph, up to the first mention.
+fn leading_text(content: &[TextNodeView]) -> String {
+    content
+        .iter()
+        .map_while(|node| match node {
+            TextNodeView::Text(text) => Some(text.text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Splits inline content `at` a byte offset into its [`leading_text`].
+fn split_inline(content: Vec<TextNodeView>, at: usize) -> (Vec<TextNodeView>, Vec<TextNodeView>) {
+    let mut head = Vec::new();
+    let mut tail = Vec::new();
+    let mut remaining = at;
+    for node in content {
+        match node {
+            TextNodeView::Text(text) if remaining >= text.text.len() => {
+                remaining -= text.text.len();
+                head.push(TextNodeView::Text(text));
+            }
+            TextNodeView::Text(mut text) if remaining > 0 => {
+                let rest = text.text.split_off(remaining);
+                remaining = 0;
+                tail.push(TextNodeView::Text(Text {
+                    text: rest,
+                    marks: text.marks.clone(),
+                }));
+                head.push(TextNodeView::Text(text));
+            }
+            node => {
+                remaining = 0;
+                tail.push(node);
+            }
+        }
+    }
+    (trim_inline(head), trim_inline(tail))
+}
+
+fn trim_inline(mut content: Vec<TextNodeView>) -> Vec<TextNodeView> {
+    if let Some(TextNodeView::Text(text)) = content.first_mut() {
+        text.text = text.text.trim_start().to_string();
+    }
+    if let Some(TextNodeView::Text(text)) = content.last_mut() {
+        text.text.truncate(text.text.trim_end().len());
+    }
+    content.retain(|node| !matches!(node, TextNodeView::Text(text) if text.text.is_empty()));
+    content
+}
+
+struct OpenList {
+    numbering: Numbering,
+    start: u32,
+    last: u32,
+    items: Vec<ListChild<View>>,
+}
+
+#[derive(Default)]
+struct Structurer {
+    out: Vec<Node<View>>,
+    /// Lists that are still receiving items, outermost first.
+    stack: Vec<OpenList>,
+}
+
+impl Structurer {
+    fn push(&mut self, node: Node<View>) {
+        let content = match node {
+            Node::Paragraph { content } => content,
+            Node::Blockquote { content } => {
+                self.close_to(0);
+                self.out.push(Node::Blockquote {
+                    content: structure_nodes(content),
+                });
+                return;
+            }
+            node => {
+                self.close_to(0);
+                self.out.push(node);
+                return;
+            }
+        };
+
+        let text = leading_text(&content);
+        if let Some(label_len) = parse_section_label(&text) {
+            self.close_to(0);
+            self.section(content, &text, label_len);
+        } else if let Some(enumerator) = parse_enumerator(&text) {
+            let (_, content) = split_inline(content, enumerator.len);
+            self.subsection(enumerator.candidates, Node::Paragraph { content });
+        } else {
+            self.close_to(0);
+            self.out.push(Node::Paragraph { content });
+        }
+    }
+
+    fn section(&mut self, content: Vec<TextNodeView>, text: &str, label_len: usize) {
+        let rest = text[label_len..].trim();
+        let is_title =
+            rest.chars().count() <= MAX_SECTION_TITLE && !rest.trim_end_matches('.').contains(". ");
+        if is_title {
+            self.out.push(Node::Heading {
+                attrs: HeadingAttributes {
+                    level: SECTION_HEADING_LEVEL,
+                },
+                content: trim_inline(content),
+            });
+            return;
+        }
+
+        let (label, body) = split_inline(content, label_len);
+        self.out.push(Node::Heading {
+            attrs: HeadingAttributes {
+                level: SECTION_HEADING_LEVEL,
+            },
+            content: label,
+        });
+        self.out.push(Node::Paragraph { content: body });
+    }
+
+    fn subsection(&mut self, candidates: Vec<(Numbering, u32)>, paragraph: Node<View>) {
+        // the next number in any open list, innermost first.
+        for level in (0..self.stack.len()).rev() {
+            let list = &self.stack[level];
+            if let Some(&(_, value)) = candidates
+                .iter()
+                .find(|(numbering, value)| *numbering == list.numbering && *value == list.last + 1)
+            {
+                self.close_to(level + 1);
+                self.add_item(value, paragraph);
+                return;
+            }
+        }
+
+        // the first number of a new, nested list.
+        let first = candidates.iter().find(|(_, value)| *value == 1);
+        if let Some(&(numbering, value)) = first.or(self.stack.is_empty().then(|| &candidates[0])) {
+            self.open(numbering, value, paragraph);
+            return;
+        }
+
+        // out of sequence: keep it with a list of the same style if there is one.
+        let level = self.stack.iter().rposition(|list| {
+            candidates
+                .iter()
+                .any(|(numbering, _)| *numbering == list.numbering)
+        });
+        match level {
+            Some(level) => {
+                let numbering = self.stack[level].numbering;
+                let (_, value) = candidates
+                    .into_iter()
+                    .find(|(n, _)| *n == numbering)
+                    .unwrap();
+                self.close_to(level + 1);
+                self.add_item(value, paragraph);
+            }
+            None => {
+                let (numbering, value) = candidates[0];
+                self.open(numbering, value, paragraph);
+            }
+        }
+    }
+
+    fn open(&mut self, numbering: Numbering, value: u32, paragraph: Node<View>) {
+        self.stack.push(OpenList {
+            numbering,
+            start: value,
+            last: value,
+            items: vec![ListChild::new(vec![paragraph])],
+        });
+    }
+
+    fn add_item(&mut self, value: u32, paragraph: Node<View>) {
+        let list = self.stack.last_mut().expect("a list is open");
+        list.last = value;
+        list.items.push(ListChild::new(vec![paragraph]));
+    }
+
+    /// Closes lists until only `len` remain open.
+    fn close_to(&mut self, len: usize) {
+        while self.stack.len() > len {
+            let list = self.stack.pop().unwrap();
+            let node = Node::OrderedList(OrderedList {
+                attrs: ListAttributes {
+                    start: list.start,
+                    attr_type: list.numbering.list_type(),
+                },
+                content: list.items,
+            });
+            match self
+                .stack
+                .last_mut()
+                .and_then(|parent| parent.items.last_mut())
+            {
+                Some(ListChild::ListItem { content }) => content.push(node),
+                None => self.out.push(node),
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<Node<View>> {
+        self.close_to(0);
+        self.out
+    }
+}
+
+#[cfg(test)]
+use crate::tippytappy::node::plain;
+
+#[cfg(test)]
+fn item(text: &str) -> ListChild<View> {
+    ListChild::new(vec![Node::Paragraph {
+        content: vec![plain(text)],
+    }])
+}
+
+#[test]
+fn enumerators() {
+    let lower_i = parse_enumerator("(i) text").unwrap();
+    assert_eq!(
+        lower_i.candidates,
+        vec![(Numbering::LowerAlpha, 9), (Numbering::LowerRoman, 1)]
+    );
+    assert_eq!(lower_i.len, 4);
+
+    assert_eq!(
+        parse_enumerator("12. text").unwrap().candidates,
+        vec![(Numbering::Decimal, 12)]
+    );
+    assert_eq!(
+        parse_enumerator("(iv) text").unwrap().candidates,
+        vec![(Numbering::LowerRoman, 4)]
+    );
+    assert!(parse_enumerator("Mr. Smith").is_none());
+    assert!(parse_enumerator("(iiii) text").is_none());
+    assert!(parse_enumerator("1.5 percent").is_none());
+}
+
+#[test]
+fn section_labels() {
+    assert_eq!(parse_section_label("SECTION 1. Short title."), Some(11));
+    assert_eq!(parse_section_label("Sec. 12-101. Definitions."), Some(13));
+    assert_eq!(parse_section_label("§ 3."), Some(5));
+    assert_eq!(parse_section_label("Section 5 of the code"), None);
+}
+
+#[test]
+fn plain_bill_text() {
+    let text = "\
+SECTION 1. Short title.
+
+SECTION 2. The department shall:
+(a) maintain roads; and
+(b) report annually on:
+(1) road
+conditions; and
+(2) spending.
+(c) publish the report.";
+
+    let document = DocumentView::parse_bill_text(text);
+    assert_eq!(
+        document.nodes(),
+        &[
+            Node::Heading {
+                attrs: HeadingAttributes { level: 2 },
+                content: vec![plain("SECTION 1. Short title.")],
+            },
+            Node::Heading {
+                attrs: HeadingAttributes { level: 2 },
+                content: vec![plain("SECTION 2. The department shall:")],
+            },
+            Node::OrderedList(OrderedList {
+                attrs: ListAttributes {
+                    start: 1,
+                    attr_type: Some("a".into()),
+                },
+                content: vec![
+                    item("maintain roads; and"),
+                    ListChild::new(vec![
+                        Node::Paragraph {
+                            content: vec![plain("report annually on:")],
+                        },
+                        Node::OrderedList(OrderedList::new(
+                            1,
+                            vec![item("road conditions; and"), item("spending.")],
+                        )),
+                    ]),
+                    item("publish the report."),
+                ],
+            }),
+        ]
+    );
+}
+
+#[test]
+fn roman_numerals_continue_alpha_lists() {
+    let text = "(g) seventh\n(h) eighth\n(i) ninth\n(1) nested\n(i) roman";
+    let document = DocumentView::parse_bill_text(text);
+    let [Node::OrderedList(outer)] = document.nodes() else {
+        panic!("expected a single list, got {:?}", document.nodes());
+    };
+    assert_eq!(outer.attrs.start, 7);
+    assert_eq!(outer.content.len(), 3);
+
+    let ListChild::ListItem { content } = &outer.content[2];
+    let Node::OrderedList(decimal) = &content[1] else {
+        panic!("expected a nested list");
+    };
+    let ListChild::ListItem { content } = &decimal.content[0];
+    let Node::OrderedList(roman) = &content[1] else {
+        panic!("expected a nested roman list");
+    };
+    assert_eq!(roman.attrs.attr_type.as_deref(), Some("i"));
+}
+
+#[test]
+fn long_sections_split_label_from_body() {
+    let document = DocumentView::parse_bill_text(
+        "Sec. 4. Section 12 of the code is amended to read as follows. The fee is ten dollars.",
+    );
+    assert_eq!(
+        document.nodes(),
+        &[
+            Node::Heading {
+                attrs: HeadingAttributes { level: 2 },
+                content: vec![plain("Sec. 4.")],
+            },
+            Node::Paragraph {
+                content: vec![plain(
+                    "Section 12 of the code is amended to read as follows. The fee is ten dollars."
+                )],
+            },
+        ]
+    );
+}
@@ -0,0 +1,380 @@
+use url::Url;
+use uuid::Uuid;
+
+use crate::tippytappy::*;
+
+/// Fluent builder for a [`DocumentView`].
+///
+/// Structural mistakes, like an empty list or a heading level outside of `1..=6`,
+/// are recorded as they happen and returned by [`DocumentBuilder::build`].
+///
+/// ```
+/// # use peacher_sdk::tippytappy::*;
+/// let document = DocumentView::builder()
+///     .heading(1, |h| h.text("Summary"))
+///     .paragraph(|p| p.text("This bill ").bold("funds").text(" road repairs."))
+///     .bullet_list(|l| l.item_text("Section 1").item_text("Section 2"))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct DocumentBuilder {
+    nodes: Vec<Node<View>>,
+    error: Option<ParseError>,
+}
+
+impl DocumentBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node as-is.
+    pub fn node(mut self, node: Node<View>) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    pub fn paragraph(mut self, f: impl FnOnce(InlineBuilder) -> InlineBuilder) -> Self {
+        let Some(content) = self.merge_inline(f(InlineBuilder::default())) else {
+            return self;
+        };
+        self.node(Node::Paragraph { content })
+    }
+
+    /// Adds a paragraph of unmarked text.
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.paragraph(|p| p.text(text))
+    }
+
+    pub fn heading(mut self, level: u32, f: impl FnOnce(InlineBuilder) -> InlineBuilder) -> Self {
+        if !(1..=6).contains(&level) {
+            return self.fail(format!("heading level {level} must be between 1 and 6"));
+        }
+        let Some(content) = self.merge_inline(f(InlineBuilder::default())) else {
+            return self;
+        };
+        if content.is_empty() {
+            return self.fail("headings must have text");
+        }
+        self.node(Node::Heading {
+            attrs: HeadingAttributes { level },
+            content,
+        })
+    }
+
+    pub fn bullet_list(mut self, f: impl FnOnce(ListBuilder) -> ListBuilder) -> Self {
+        let Some(items) = self.merge_list(f(ListBuilder::default())) else {
+            return self;
+        };
+        self.node(Node::BulletList(BulletListNode::new(items)))
+    }
+
+    pub fn ordered_list(mut self, start: u32, f: impl FnOnce(ListBuilder) -> ListBuilder) -> Self {
+        let Some(items) = self.merge_list(f(ListBuilder::default())) else {
+            return self;
+        };
+        self.node(Node::OrderedList(OrderedList::new(start, items)))
+    }
+
+    pub fn blockquote(mut self, f: impl FnOnce(DocumentBuilder) -> DocumentBuilder) -> Self {
+        let Some(content) = self.merge_blocks(f(DocumentBuilder::default())) else {
+            return self;
+        };
+        if content.is_empty() {
+            return self.fail("blockquotes must have content");
+        }
+        self.node(Node::Blockquote { content })
+    }
+
+    pub fn details(
+        mut self,
+        open: bool,
+        summary: impl FnOnce(InlineBuilder) -> InlineBuilder,
+        content: impl FnOnce(DocumentBuilder) -> DocumentBuilder,
+    ) -> Self {
+        let Some(summary) = self.merge_inline(summary(InlineBuilder::default())) else {
+            return self;
+        };
+        if summary.is_empty() {
+            return self.fail("details must have a summary");
+        }
+        let Some(content) = self.merge_blocks(content(DocumentBuilder::default())) else {
+            return self;
+        };
+        self.node(Node::Details {
+            attrs: DetailAttributes::new(open),
+            content: vec![
+                DetailNode::DetailsSummary { content: summary },
+                DetailNode::DetailsContent { content },
+            ],
+        })
+    }
+
+    pub fn image(self, attrs: ImageAttributes) -> Self {
+        self.node(Node::Image { attrs })
+    }
+
+    pub fn horizontal_rule(self) -> Self {
+        self.node(Node::HorizontalRule)
+    }
+
+    pub fn build(self) -> Result<DocumentView, ParseError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(DocumentView::from_nodes(self.nodes)),
+        }
+    }
+
+    /// Builds the document and [validates](DocumentView::validate) it against `policy`.
+    pub fn build_with_policy(self, policy: &DocumentPolicy) -> Result<DocumentView, ParseError> {
+        let document = self.build()?;
+        document.validate(policy)?;
+        Ok(document)
+    }
+
+    fn fail(mut self, msg: impl Into<String>) -> Self {
+        self.error
+            .get_or_insert_with(|| ParseError::Structure(msg.into()));
+        self
+    }
+
+    fn take_error(&mut self, error: Option<ParseError>) -> bool {
+        match error {
+            Some(error) => {
+                self.error.get_or_insert(error);
+                false
+            }
+            None => true,
+        }
+    }
+
+    fn merge_inline(&mut self, inline: InlineBuilder) -> Option<Vec<TextNodeView>> {
+        self.take_error(inline.error).then_some(inline.nodes)
+    }
+
+    fn merge_blocks(&mut self, blocks: DocumentBuilder) -> Option<Vec<Node<View>>> {
+        self.take_error(blocks.error).then_some(blocks.nodes)
+    }
+
+    fn merge_list(&mut self, list: ListBuilder) -> Option<Vec<ListChild<View>>> {
+        if !self.take_error(list.error) {
+            return None;
+        }
+        if list.items.is_empty() {
+            self.error
+                .get_or_insert_with(|| ParseError::Structure("lists must have items".into()));
+            return None;
+        }
+        Some(list.items)
+    }
+}
+
+/// Builds the items of an ordered or bullet list.
+#[derive(Debug, Default)]
+pub struct ListBuilder {
+    items: Vec<ListChild<View>>,
+    error: Option<ParseError>,
+}
+
+impl ListBuilder {
+    pub fn item(mut self, f: impl FnOnce(DocumentBuilder) -> DocumentBuilder) -> Self {
+        let content = f(DocumentBuilder::default());
+        if let Some(error) = content.error {
+            self.error.get_or_insert(error);
+        } else if content.nodes.is_empty() {
+            self.error.get_or_insert_with(|| {
+                ParseError::Structure("list items must have content".into())
+            });
+        } else {
+            self.items.push(ListChild::new(content.nodes));
+        }
+        self
+    }
+
+    /// Adds an item holding a single paragraph of unmarked text.
+    pub fn item_text(self, text: impl Into<String>) -> Self {
+        self.item(|b| b.text(text))
+    }
+}
+
+/// Builds the text and mentions inside a paragraph, heading or details summary.
+#[derive(Debug, Default)]
+pub struct InlineBuilder {
+    nodes: Vec<TextNodeView>,
+    marks: Vec<Mark>,
+    error: Option<ParseError>,
+}
+
+impl InlineBuilder {
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        let text = text.into();
+        if !text.is_empty() {
+            self.nodes.push(TextNodeView::Text(Text {
+                text,
+                marks: self.marks.clone(),
+            }));
+        }
+        self
+    }
+
+    /// Applies `mark` to everything added inside `f`, on top of any marks already applied.
+    pub fn marked(mut self, mark: Mark, f: impl FnOnce(InlineBuilder) -> InlineBuilder) -> Self {
+        let mut marks = self.marks.clone();
+        marks.push(mark);
+        let inner = f(InlineBuilder {
+            nodes: Vec::new(),
+            marks,
+            error: None,
+        });
+        if let Some(error) = inner.error {
+            self.error.get_or_insert(error);
+        }
+        self.nodes.extend(inner.nodes);
+        self
+    }
+
+    pub fn bold(self, text: impl Into<String>) -> Self {
+        self.marked(Mark::Bold, |t| t.text(text))
+    }
+
+    pub fn italic(self, text: impl Into<String>) -> Self {
+        self.marked(Mark::Italic, |t| t.text(text))
+    }
+
+    pub fn underline(self, text: impl Into<String>) -> Self {
+        self.marked(Mark::Underline, |t| t.text(text))
+    }
+
+    pub fn highlight(self, text: impl Into<String>) -> Self {
+        self.marked(Mark::Highlight, |t| t.text(text))
+    }
+
+    pub fn code(self, text: impl Into<String>) -> Self {
+        self.marked(Mark::Code, |t| t.text(text))
+    }
+
+    pub fn link(self, text: impl Into<String>, href: &Url) -> Self {
+        self.link_with(href, |t| t.text(text))
+    }
+
+    /// Links everything added inside `f` to `href`.
+    pub fn link_with(self, href: &Url, f: impl FnOnce(InlineBuilder) -> InlineBuilder) -> Self {
+        let attrs = LinkAttributes {
+            href: href.to_string(),
+            target: None,
+            rel: None,
+            class: None,
+            title: None,
+        };
+        self.marked(Mark::Link { attrs }, f)
+    }
+
+    pub fn line_break(self) -> Self {
+        self.text("\n")
+    }
+
+    pub fn member(self, id: i32, label: impl Into<String>) -> Self {
+        self.mention(label.into(), |label| TextNodeView::MemberMention {
+            attrs: Mention { id, label },
+        })
+    }
+
+    pub fn legislation(self, id: i32, label: impl Into<String>) -> Self {
+        self.mention(label.into(), |label| TextNodeView::LegislationMention {
+            attrs: Mention { id, label },
+        })
+    }
+
+    pub fn post(self, id: Uuid, label: impl Into<String>) -> Self {
+        self.mention(label.into(), |label| TextNodeView::PostMention {
+            attrs: Mention { id, label },
+        })
+    }
+
+    fn mention(mut self, label: String, node: impl FnOnce(String) -> TextNodeView) -> Self {
+        if label.trim().is_empty() {
+            self.error
+                .get_or_insert_with(|| ParseError::Structure("mentions must have a label".into()));
+        } else {
+            self.nodes.push(node(label));
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+use crate::tippytappy::node::text;
+
+#[test]
+fn builds_document_matching_markdown() {
+    let built = DocumentView::builder()
+        .heading(1, |h| h.text("Welcome"))
+        .text("Here is a paragraph.")
+        .blockquote(|b| b.text("A wise quote"))
+        .horizontal_rule()
+        .ordered_list(1, |l| l.item_text("First").item_text("Second"))
+        .bullet_list(|l| l.item_text("bullet a").item_text("bullet b"))
+        .build()
+        .unwrap();
+
+    let parsed = DocumentView::parse_markdown(
+        "# Welcome\n\nHere is a paragraph.\n\n> A wise quote\n\n---\n\n1. First\n2. Second\n\n- bullet a\n- bullet b",
+    )
+    .unwrap();
+    assert_eq!(built, parsed);
+}
+
+#[test]
+fn nested_marks_and_mentions() {
+    let href = Url::parse("https://peacher.app").unwrap();
+    let document = DocumentView::builder()
+        .paragraph(|p| {
+            p.text("See ")
+                .marked(Mark::Bold, |b| b.link("this", &href).italic(" now"))
+                .member(4, "@alice")
+        })
+        .build()
+        .unwrap();
+
+    let link = Mark::Link {
+        attrs: LinkAttributes {
+            href: "https://peacher.app/".into(),
+            target: None,
+            rel: None,
+            class: None,
+            title: None,
+        },
+    };
+    assert_eq!(
+        document.nodes(),
+        &[Node::Paragraph {
+            content: vec![
+                text("See ", vec![]),
+                text("this", vec![Mark::Bold, link]),
+                text(" now", vec![Mark::Bold, Mark::Italic]),
+                TextNodeView::MemberMention {
+                    attrs: Mention {
+                        id: 4,
+                        label: "@alice".into(),
+                    },
+                },
+            ],
+        }]
+    );
+}
+
+#[test]
+fn rejects_invalid_structure() {
+    let result = DocumentView::builder()
+        .heading(7, |h| h.text("Too deep"))
+        .build();
+    assert!(matches!(result, Err(ParseError::Structure(_))));
+
+    let result = DocumentView::builder().bullet_list(|l| l).build();
+    assert!(matches!(result, Err(ParseError::Structure(_))));
+
+    let result = DocumentView::builder()
+        .blockquote(|b| b.paragraph(|p| p.member(1, "")))
+        .build();
+    assert!(matches!(result, Err(ParseError::Structure(_))));
+}
@@ -1,7 +1,7 @@
 use crate::tippytappy::{node_kind::iter_node_children_text, *};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 pub struct Compiled;
 impl State for Compiled {
     type TextNode = CompiledTextNode;
@@ -13,7 +13,7 @@ pub struct CompilationResult {
     pub document: CompiledDocument,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[serde(tag = "type", rename = "doc")]
 pub struct CompiledDocument {
     content: Vec<Node<Compiled>>,
@@ -26,6 +26,18 @@ impl CompiledDocument {
         }
     }
 
+    pub fn nodes(&self) -> &[Node<Compiled>] {
+        &self.content
+    }
+
+    pub fn into_nodes(self) -> Vec<Node<Compiled>> {
+        self.content
+    }
+
+    pub fn summarize(&self, options: &SummaryOptions) -> DocumentSummary {
+        DocumentSummary::from_nodes(&self.content, options)
+    }
+
     pub fn parse_json(value: serde_json::Value) -> Result<Self, ParseError> {
         let value = serde_json::from_value(value).map_err(|e| {
             tracing::error!("Invalid value passed for compiled document. Error: {e}");
@@ -2,7 +2,7 @@ use crate::tippytappy::{node_kind::iter_node_children_text, *};
 use markdown::{ParseOptions, mdast::Node as MdNode};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct View;
 
@@ -10,7 +10,7 @@ impl State for View {
     type TextNode = TextNodeView;
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[serde(tag = "type", rename = "doc")]
 pub struct DocumentView {
     content: Vec<Node<View>>,
@@ -30,6 +30,19 @@ impl DocumentView {
             content: nodes.into_iter().collect(),
         }
     }
+    pub fn builder() -> DocumentBuilder {
+        DocumentBuilder::new()
+    }
+    pub fn nodes(&self) -> &[Node<View>] {
+        &self.content
+    }
+    pub fn into_nodes(self) -> Vec<Node<View>> {
+        self.content
+    }
+
+    pub fn summarize(&self, options: &SummaryOptions) -> DocumentSummary {
+        DocumentSummary::from_nodes(&self.content, options)
+    }
     pub fn parse_json(value: serde_json::Value) -> Result<Self, ParseError> {
         let value = serde_json::from_value(value).map_err(|e| {
             tracing::error!("Invalid value passed for document view. Error: {e}");
@@ -40,11 +53,24 @@ impl DocumentView {
     }
 
     pub fn parse_markdown(markdown: &str) -> Result<Self, ParseError> {
-        let parse_options = ParseOptions::gfm();
+        let root = markdown::to_mdast(markdown, &ParseOptions::gfm())?;
+        Self::from_mdast_root(root)
+    }
 
-        let markdown = markdown::to_mdast(markdown, &parse_options)?;
+    /// Parses markdown, applying the policy's [`HtmlPolicy`] to raw HTML before conversion
+    /// and [sanitizing](DocumentPolicy::sanitize) the result.
+    pub fn parse_markdown_with_policy(
+        markdown: &str,
+        policy: &DocumentPolicy,
+    ) -> Result<Self, ParseError> {
+        let mut root = markdown::to_mdast(markdown, &ParseOptions::gfm())?;
+        policy.html().apply(&mut root)?;
+        let document = Self::from_mdast_root(root)?;
+        Ok(policy.sanitize(document))
+    }
 
-        let MdNode::Root(root) = markdown else {
+    fn from_mdast_root(root: MdNode) -> Result<Self, ParseError> {
+        let MdNode::Root(root) = root else {
             return Err(ParseError::other("root element is not a root node!"));
         };
 
@@ -57,6 +83,21 @@ impl DocumentView {
         Ok(Self { content })
     }
 
+    /// Checks this document against `policy`, returning every broken rule in [`ParseError::Policy`].
+    pub fn validate(&self, policy: &DocumentPolicy) -> Result<(), ParseError> {
+        let violations = policy.violations(self);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ParseError::Policy(violations))
+        }
+    }
+
+    /// Rewrites this document so that it satisfies `policy`.
+    pub fn sanitize(self, policy: &DocumentPolicy) -> Self {
+        policy.sanitize(self)
+    }
+
     pub fn compile(self) -> CompilationResult {
         let mut carriage = CompileCarriage::default();
 
@@ -2,12 +2,18 @@ use std::fmt;
 
 use thiserror::Error;
 
+use crate::tippytappy::PolicyViolation;
+
 #[derive(Error, Debug)]
 pub enum ParseError {
     #[error("Invalid Json: {0}")]
     Json(#[from] serde_json::Error),
     #[error("Invalid markdown: {0}")]
     Markdown(markdown::message::Message),
+    #[error("Document violates policy: {}", PolicyViolation::join(.0))]
+    Policy(Vec<PolicyViolation>),
+    #[error("Invalid document structure: {0}")]
+    Structure(String),
     #[error("Something went wrong: {0}")]
     Msg(String),
 }
@@ -0,0 +1,562 @@
+use scraper::{ElementRef, Html, Node as HtmlNode};
+use url::Url;
+
+use crate::tippytappy::*;
+
+/// Elements that start a new block. Text around them is split into separate paragraphs.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "center",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "html",
+    "img",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Elements whose content is never shown.
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "head", "iframe", "math", "noscript", "object", "script", "style", "svg", "template", "title",
+];
+
+impl DocumentView {
+    /// Converts HTML into a document.
+    ///
+    /// Headings, paragraphs, lists, blockquotes, details, tables, images and rules are kept.
+    /// `<b>`, `<i>`, `<u>`/`<ins>`, `<s>`/`<del>`, `<code>`, `<mark>` and links become marks,
+    /// as do underline and line-through `style`s. Other elements are unwrapped, and
+    /// scripts and styles are dropped.
+    pub fn parse_html(html: &str) -> Self {
+        let html = Html::parse_document(html);
+        let mut nodes = Vec::new();
+        blocks(html.root_element(), &mut nodes);
+        Self::from_nodes(nodes)
+    }
+
+    /// Converts bill text HTML into a document, recognizing sections and numbered subsections.
+    ///
+    /// See [`DocumentView::parse_html`] and [`DocumentView::structure_bill_text`].
+    pub fn parse_bill_html(html: &str) -> Self {
+        Self::parse_html(html).structure_bill_text()
+    }
+}
+
+/// Collects text and marks for a single paragraph, collapsing whitespace like a browser would.
+struct Inline {
+    nodes: Vec<TextNodeView>,
+    marks: Vec<Mark>,
+    preserve_whitespace: bool,
+    after_space: bool,
+}
+
+impl Inline {
+    fn new(preserve_whitespace: bool) -> Self {
+        Self {
+            nodes: Vec::new(),
+            marks: Vec::new(),
+            preserve_whitespace,
+            after_space: true,
+        }
+    }
+
+    fn text(&mut self, raw: &str) {
+        if self.preserve_whitespace {
+            self.push(raw.to_string());
+            return;
+        }
+        let mut text = String::with_capacity(raw.len());
+        for c in raw.chars() {
+            if !c.is_whitespace() {
+                text.push(c);
+                self.after_space = false;
+            } else if !self.after_space {
+                text.push(' ');
+                self.after_space = true;
+            }
+        }
+        self.push(text);
+    }
+
+    fn line_break(&mut self) {
+        if let Some(TextNodeView::Text(last)) = self.nodes.last_mut() {
+            last.text.truncate(last.text.trim_end_matches(' ').len());
+        }
+        self.push("\n".to_string());
+        self.after_space = true;
+    }
+
+    fn push(&mut self, text: String) {
+        if text.is_empty() {
+            return;
+        }
+        match self.nodes.last_mut() {
+            Some(TextNodeView::Text(last)) if last.marks == self.marks => last.text.push_str(&text),
+            _ => self.nodes.push(TextNodeView::Text(Text {
+                text,
+                marks: self.marks.clone(),
+            })),
+        }
+    }
+
+    /// Adds marks that aren't applied yet, returning how many were added.
+    fn push_marks(&mut self, marks: Vec<Mark>) -> usize {
+        let before = self.marks.len();
+        for mark in marks {
+            if !self.marks.contains(&mark) {
+                self.marks.push(mark);
+            }
+        }
+        self.marks.len() - before
+    }
+
+    fn pop_marks(&mut self, count: usize) {
+        self.marks.truncate(self.marks.len() - count);
+    }
+
+    fn take(&mut self) -> Vec<TextNodeView> {
+        self.after_space = true;
+        let mut nodes = std::mem::take(&mut self.nodes);
+        if !self.preserve_whitespace {
+            while let Some(TextNodeView::Text(last)) = nodes.last_mut() {
+                last.text.truncate(last.text.trim_end().len());
+                if !last.text.is_empty() {
+                    break;
+                }
+                nodes.pop();
+            }
+        }
+        nodes
+    }
+
+    fn flush_into(&mut self, out: &mut Vec<Node<View>>) {
+        let content = self.take();
+        if !content.is_empty() {
+            out.push(Node::Paragraph { content });
+        }
+    }
+}
+
+fn element_name<'a>(element: &ElementRef<'a>) -> &'a str {
+    element.value().name()
+}
+
+/// Converts the children of `parent` into blocks.
+fn blocks(parent: ElementRef<'_>, out: &mut Vec<Node<View>>) {
+    let mut inline = Inline::new(false);
+    for child in parent.children() {
+        match child.value() {
+            HtmlNode::Text(text) => inline.text(text),
+            HtmlNode::Element(_) => {
+                let Some(element) = ElementRef::wrap(child) else {
+                    continue;
+                };
+                let name = element_name(&element);
+                if SKIPPED_ELEMENTS.contains(&name) {
+                    continue;
+                }
+                if BLOCK_ELEMENTS.contains(&name) {
+                    inline.flush_into(out);
+                    block(element, out);
+                } else {
+                    inline_element(element, &mut inline);
+                }
+            }
+            _ => {}
+        }
+    }
+    inline.flush_into(out);
+}
+
+fn block(element: ElementRef<'_>, out: &mut Vec<Node<View>>) {
+    match element_name(&element) {
+        name @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+            let content = inline_content(element, false);
+            if !content.is_empty() {
+                out.push(Node::Heading {
+                    attrs: HeadingAttributes {
+                        level: name[1..].parse().unwrap(),
+                    },
+                    content,
+                });
+            }
+        }
+        "pre" => {
+            let mut inline = Inline::new(true);
+            inline.push_marks(vec![Mark::Code]);
+            inline_children(element, &mut inline);
+            inline.flush_into(out);
+        }
+        "blockquote" => {
+            let mut content = Vec::new();
+            blocks(element, &mut content);
+            if !content.is_empty() {
+                out.push(Node::Blockquote { content });
+            }
+        }
+        "ul" => {
+            let items = list_items(element);
+            if !items.is_empty() {
+                out.push(Node::BulletList(BulletListNode::new(items)));
+            }
+        }
+        "ol" => {
+            let items = list_items(element);
+            if !items.is_empty() {
+                out.push(Node::OrderedList(OrderedList {
+                    attrs: ListAttributes {
+                        start: element
+                            .attr("start")
+                            .and_then(|start| start.trim().parse().ok())
+                            .unwrap_or(1),
+                        attr_type: element
+                            .attr("type")
+                            .filter(|t| ["a", "A", "i", "I"].contains(t))
+                            .map(str::to_string),
+                    },
+                    content: items,
+                }));
+            }
+        }
+        "details" => details(element, out),
+        "table" => table(element, out),
+        "hr" => out.push(Node::HorizontalRule),
+        "img" => {
+            if let Some(attrs) = image(element) {
+                out.push(Node::Image { attrs });
+            }
+        }
+        _ => blocks(element, out),
+    }
+}
+
+fn list_items(list: ElementRef<'_>) -> Vec<ListChild<View>> {
+    let mut items = Vec::new();
+    for child in list.child_elements() {
+        let mut content = Vec::new();
+        if element_name(&child) == "li" {
+            blocks(child, &mut content);
+        } else {
+            block(child, &mut content);
+        }
+        if !content.is_empty() {
+            items.push(ListChild::new(content));
+        }
+    }
+    items
+}
+
+fn details(element: ElementRef<'_>, out: &mut Vec<Node<View>>) {
+    let mut summary = Vec::new();
+    let mut content = Vec::new();
+    let mut inline = Inline::new(false);
+    for child in element.children() {
+        match child.value() {
+            HtmlNode::Text(text) => inline.text(text),
+            HtmlNode::Element(_) => {
+                let Some(child) = ElementRef::wrap(child) else {
+                    continue;
+                };
+                match element_name(&child) {
+                    "summary" => summary.extend(inline_content(child, false)),
+                    name if SKIPPED_ELEMENTS.contains(&name) => {}
+                    name if BLOCK_ELEMENTS.contains(&name) => {
+                        inline.flush_into(&mut content);
+                        block(child, &mut content);
+                    }
+                    _ => inline_element(child, &mut inline),
+                }
+            }
+            _ => {}
+        }
+    }
+    inline.flush_into(&mut content);
+
+    if summary.is_empty() {
+        out.extend(content);
+        return;
+    }
+    out.push(Node::Details {
+        attrs: DetailAttributes::new(element.attr("open").is_some()),
+        content: vec![
+            DetailNode::DetailsSummary { content: summary },
+            DetailNode::DetailsContent { content },
+        ],
+    });
+}
+
+fn table(element: ElementRef<'_>, out: &mut Vec<Node<View>>) {
+    let mut rows = Vec::new();
+    for child in element.child_elements() {
+        match element_name(&child) {
+            "caption" => blocks(child, out),
+            "thead" | "tbody" | "tfoot" => {
+                for row in child.child_elements() {
+                    if element_name(&row) == "tr" {
+                        rows.extend(table_row(row));
+                    }
+                }
+            }
+            "tr" => rows.extend(table_row(child)),
+            _ => {}
+        }
+    }
+    if !rows.is_empty() {
+        out.push(Node::Table(TableNode::new(rows)));
+    }
+}
+
+fn table_row(row: ElementRef<'_>) -> Option<TableRow<View>> {
+    let span = |cell: &ElementRef<'_>, name: &str| {
+        cell.attr(name)
+            .and_then(|span| span.trim().parse().ok())
+            .filter(|span| *span > 0)
+            .unwrap_or(1)
+    };
+
+    let cells: Vec<_> = row
+        .child_elements()
+        .filter(|cell| matches!(element_name(cell), "td" | "th"))
+        .map(|cell| {
+            let mut content = Vec::new();
+            blocks(cell, &mut content);
+            // cells always hold at least one block.
+            if content.is_empty() {
+                content.push(Node::Paragraph { content: vec![] });
+            }
+            let attrs = CellAttributes {
+                colspan: span(&cell, "colspan"),
+                rowspan: span(&cell, "rowspan"),
+                colwidth: None,
+            };
+            TableCell::from_parts(attrs, content, element_name(&cell) == "th")
+        })
+        .collect();
+
+    (!cells.is_empty()).then(|| TableRow::new(cells))
+}
+
+/// Images without an absolute `src` are dropped, since there is no base url to resolve them against.
+fn image(element: ElementRef<'_>) -> Option<ImageAttributes> {
+    let src = Url::parse(element.attr("src")?.trim()).ok()?;
+    let mut attrs = ImageAttributes::new(src);
+    if let Some(alt) = element.attr("alt").filter(|alt| !alt.trim().is_empty()) {
+        attrs = attrs.with_alt(alt.trim());
+    }
+    if let Some(title) = element
+        .attr("title")
+        .filter(|title| !title.trim().is_empty())
+    {
+        attrs = attrs.with_title(title.trim());
+    }
+    let size = |name| element.attr(name).and_then(|v| v.trim().parse().ok());
+    if let (Some(width), Some(height)) = (size("width"), size("height")) {
+        attrs = attrs.with_size(width, height);
+    }
+    Some(attrs)
+}
+
+fn inline_content(element: ElementRef<'_>, preserve_whitespace: bool) -> Vec<TextNodeView> {
+    let mut inline = Inline::new(preserve_whitespace);
+    inline_children(element, &mut inline);
+    inline.take()
+}
+
+fn inline_children(element: ElementRef<'_>, inline: &mut Inline) {
+    for child in element.children() {
+        match child.value() {
+            HtmlNode::Text(text) => inline.text(text),
+            HtmlNode::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    inline_element(child, inline);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn inline_element(element: ElementRef<'_>, inline: &mut Inline) {
+    match element_name(&element) {
+        "br" => inline.line_break(),
+        "img" => {
+            if let Some(alt) = element.attr("alt") {
+                inline.text(alt);
+            }
+        }
+        name if SKIPPED_ELEMENTS.contains(&name) => {}
+        _ => {
+            let count = inline.push_marks(element_marks(&element));
+            inline_children(element, inline);
+            inline.pop_marks(count);
+        }
+    }
+}
+
+fn element_marks(element: &ElementRef<'_>) -> Vec<Mark> {
+    let mut marks = Vec::new();
+    match element_name(element) {
+        "b" | "strong" => marks.push(Mark::Bold),
+        "i" | "em" | "cite" | "var" => marks.push(Mark::Italic),
+        "u" | "ins" => marks.push(Mark::Underline),
+        "s" | "strike" | "del" => marks.push(Mark::Strike),
+        "code" | "kbd" | "samp" | "tt" => marks.push(Mark::Code),
+        "mark" => marks.push(Mark::Highlight),
+        "a" => {
+            if let Some(href) = element
+                .attr("href")
+                .map(str::trim)
+                .filter(|h| !h.is_empty())
+            {
+                marks.push(Mark::Link {
+                    attrs: LinkAttributes {
+                        href: href.to_string(),
+                        target: element.attr("target").map(str::to_string),
+                        rel: element.attr("rel").map(str::to_string),
+                        class: None,
+                        title: element.attr("title").map(str::to_string),
+                    },
+                });
+            }
+        }
+        _ => {}
+    }
+
+    // bill drafting tools often mark insertions and deletions with inline styles.
+    if let Some(style) = element.attr("style") {
+        let style = style.to_ascii_lowercase().replace(' ', "");
+        if style.contains("line-through") {
+            marks.push(Mark::Strike);
+        }
+        if style.contains("underline") {
+            marks.push(Mark::Underline);
+        }
+        if style.contains("font-weight:bold") || style.contains("font-weight:700") {
+            marks.push(Mark::Bold);
+        }
+        if style.contains("font-style:italic") {
+            marks.push(Mark::Italic);
+        }
+    }
+    marks
+}
+
+#[cfg(test)]
+use crate::tippytappy::node::text;
+
+#[test]
+fn html_marks_and_whitespace() {
+    let document = DocumentView::parse_html(
+        "<p>The fee is\n   <del>five</del> <ins>ten</ins> <span style=\"text-decoration: underline\">dollars</span>.<br>Next line</p>",
+    );
+    assert_eq!(
+        document.nodes(),
+        &[Node::Paragraph {
+            content: vec![
+                text("The fee is ", vec![]),
+                text("five", vec![Mark::Strike]),
+                text(" ", vec![]),
+                text("ten", vec![Mark::Underline]),
+                text(" ", vec![]),
+                text("dollars", vec![Mark::Underline]),
+                text(".\nNext line", vec![]),
+            ],
+        }]
+    );
+}
+
+#[test]
+fn html_tables_are_preserved() {
+    let document = DocumentView::parse_html(
+        "<table><thead><tr><th>Fund</th><th>Amount</th></tr></thead>\
+         <tbody><tr><td>Roads</td><td colspan=\"2\"><b>$5</b></td></tr></tbody></table>",
+    );
+    let [Node::Table(table)] = document.nodes() else {
+        panic!("expected a table, got {:?}", document.nodes());
+    };
+    assert_eq!(table.content.len(), 2);
+    assert!(matches!(
+        table.content[0].cells()[0],
+        TableCell::TableHeader { .. }
+    ));
+    assert_eq!(
+        table.content[1].cells()[1],
+        TableCell::TableCell {
+            attrs: CellAttributes {
+                colspan: 2,
+                rowspan: 1,
+                colwidth: None,
+            },
+            content: vec![Node::Paragraph {
+                content: vec![text("$5", vec![Mark::Bold])],
+            }],
+        }
+    );
+}
+
+#[test]
+fn bill_html_structure() {
+    let document = DocumentView::parse_bill_html(
+        "<html><head><style>p { margin: 0 }</style></head><body>\
+         <div><p>SECTION 1. Fees.</p>\
+         <p>(a) The fee is <s>five</s> <u>ten</u> dollars.</p>\
+         <p>(b) Fees are due annually.</p></div>\
+         <script>alert(1)</script></body></html>",
+    );
+    assert_eq!(
+        document.nodes(),
+        &[
+            Node::Heading {
+                attrs: HeadingAttributes { level: 2 },
+                content: vec![text("SECTION 1. Fees.", vec![])],
+            },
+            Node::OrderedList(OrderedList {
+                attrs: ListAttributes {
+                    start: 1,
+                    attr_type: Some("a".into()),
+                },
+                content: vec![
+                    ListChild::new(vec![Node::Paragraph {
+                        content: vec![
+                            text("The fee is ", vec![]),
+                            text("five", vec![Mark::Strike]),
+                            text(" ", vec![]),
+                            text("ten", vec![Mark::Underline]),
+                            text(" dollars.", vec![]),
+                        ],
+                    }]),
+                    ListChild::new(vec![Node::Paragraph {
+                        content: vec![text("Fees are due annually.", vec![])],
+                    }]),
+                ],
+            }),
+        ]
+    );
+}
@@ -0,0 +1,344 @@
+use std::hash::Hash;
+
+use ahash::{HashMap, RandomState};
+
+use crate::tippytappy::*;
+
+/// Content hash of a single top-level block.
+///
+/// Hashes are only comparable within a single build of this crate, so they should not be persisted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlockHash(u64);
+
+impl BlockHash {
+    pub fn of<S: State>(node: &Node<S>) -> Self
+    where
+        Node<S>: Hash,
+    {
+        // fixed seeds so that separate compilers agree on hashes.
+        Self(RandomState::with_seeds(0x7065, 0x6163, 0x6865, 0x72).hash_one(node))
+    }
+}
+
+impl DocumentView {
+    pub fn block_hashes(&self) -> impl Iterator<Item = BlockHash> {
+        self.nodes().iter().map(BlockHash::of)
+    }
+}
+
+impl CompiledDocument {
+    pub fn block_hashes(&self) -> impl Iterator<Item = BlockHash> {
+        self.nodes().iter().map(BlockHash::of)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Blocks reused from a previous call.
+    pub hits: usize,
+    /// Blocks that had to be processed.
+    pub misses: usize,
+}
+
+/// Compiles and decompiles documents, reusing the work done for every top-level block
+/// that is unchanged since the previous call.
+///
+/// Produces the same output as [`DocumentView::compile`] and
+/// [`CompiledDocument::visit_and_decompile`], but only clones and processes the blocks
+/// that changed. Unchanged blocks are moved out of the previous result, so only blocks
+/// from the most recent call are kept.
+pub struct IncrementalCompiler {
+    compiled: Vec<CompiledBlock>,
+    result: CompilationResult,
+    decompiled: Vec<DecompiledBlock>,
+    view: DocumentView,
+    stats: CacheStats,
+}
+
+struct CompiledBlock {
+    hash: BlockHash,
+    /// Compared on a hash match, so a collision can't serve another block's output.
+    source: Node<View>,
+    searchable_text: String,
+    relationships: ContentRelationships,
+}
+
+struct DecompiledBlock {
+    hash: BlockHash,
+    /// Compared on a hash match, so a collision can't serve another block's output.
+    source: Node<Compiled>,
+    /// Every mention in the block and the label it was given.
+    labels: Vec<(CompiledTextNode, String)>,
+}
+
+impl Default for IncrementalCompiler {
+    fn default() -> Self {
+        Self {
+            compiled: Vec::new(),
+            result: CompilationResult {
+                relationships: ContentRelationships::default(),
+                searchable_text: String::new(),
+                document: CompiledDocument::from_nodes([]),
+            },
+            decompiled: Vec::new(),
+            view: DocumentView::from_nodes([]),
+            stats: CacheStats::default(),
+        }
+    }
+}
+
+impl IncrementalCompiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hits and misses accumulated over every call.
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// The result of the last call to [`IncrementalCompiler::compile`].
+    pub fn into_result(self) -> CompilationResult {
+        self.result
+    }
+
+    pub fn compile(&mut self, document: &DocumentView) -> &CompilationResult {
+        let nodes = std::mem::replace(&mut self.result.document, CompiledDocument::from_nodes([]));
+        let mut previous = Previous::new(
+            std::mem::take(&mut self.compiled)
+                .into_iter()
+                .zip(nodes.into_nodes())
+                .map(|(block, node)| (block.hash, (block, node))),
+        );
+
+        let mut nodes = Vec::with_capacity(document.nodes().len());
+        for node in document.nodes() {
+            let hash = BlockHash::of(node);
+            let (block, node) = match previous.take(hash) {
+                Some((block, compiled)) if block.source == *node => {
+                    self.stats.hits += 1;
+                    (block, compiled)
+                }
+                _ => {
+                    self.stats.misses += 1;
+                    compile_block(hash, node)
+                }
+            };
+            self.compiled.push(block);
+            nodes.push(node);
+        }
+
+        let result = &mut self.result;
+        result.searchable_text.clear();
+        result.relationships = ContentRelationships::default();
+        for block in &self.compiled {
+            result.searchable_text.push_str(&block.searchable_text);
+            result.relationships.merge(&block.relationships);
+        }
+        result.document = CompiledDocument::from_nodes(nodes);
+        result
+    }
+
+    /// Turns a compiled document back into a view.
+    ///
+    /// Previous blocks are only reused if every mention in them still resolves to the same label.
+    pub fn decompile(
+        &mut self,
+        document: &CompiledDocument,
+        relationships: &ContentRelationships,
+    ) -> &DocumentView {
+        let nodes = std::mem::replace(&mut self.view, DocumentView::from_nodes([]));
+        let mut previous = Previous::new(
+            std::mem::take(&mut self.decompiled)
+                .into_iter()
+                .zip(nodes.into_nodes())
+                .map(|(block, node)| (block.hash, (block, node))),
+        );
+
+        let mut nodes = Vec::with_capacity(document.nodes().len());
+        for node in document.nodes() {
+            let hash = BlockHash::of(node);
+            let (block, node) = match previous.take(hash) {
+                Some((block, view)) if block.source == *node && block.is_current(relationships) => {
+                    self.stats.hits += 1;
+                    (block, view)
+                }
+                _ => {
+                    self.stats.misses += 1;
+                    decompile_block(hash, node, relationships)
+                }
+            };
+            self.decompiled.push(block);
+            nodes.push(node);
+        }
+
+        self.view = DocumentView::from_nodes(nodes);
+        &self.view
+    }
+}
+
+/// Blocks from the previous call, keyed by hash. Identical blocks are handed out in order.
+struct Previous<T> {
+    slots: Vec<Option<T>>,
+    next: Vec<Option<usize>>,
+    first: HashMap<BlockHash, usize>,
+}
+
+impl<T> Previous<T> {
+    fn new(blocks: impl Iterator<Item = (BlockHash, T)>) -> Self {
+        let (hashes, slots): (Vec<_>, Vec<_>) =
+            blocks.map(|(hash, block)| (hash, Some(block))).unzip();
+        let mut first = HashMap::with_capacity_and_hasher(hashes.len(), RandomState::new());
+        let mut next = vec![None; hashes.len()];
+        for (index, hash) in hashes.into_iter().enumerate().rev() {
+            next[index] = first.insert(hash, index);
+        }
+        Self { slots, next, first }
+    }
+
+    fn take(&mut self, hash: BlockHash) -> Option<T> {
+        let index = self.first.remove(&hash)?;
+        if let Some(next) = self.next[index] {
+            self.first.insert(hash, next);
+        }
+        self.slots[index].take()
+    }
+}
+
+fn compile_block(hash: BlockHash, source: &Node<View>) -> (CompiledBlock, Node<Compiled>) {
+    let mut carriage = CompileCarriage::default();
+    let node = source.clone().process(&mut carriage);
+    let result = carriage.finish(CompiledDocument::from_nodes([]));
+    let block = CompiledBlock {
+        hash,
+        source: source.clone(),
+        searchable_text: result.searchable_text,
+        relationships: result.relationships,
+    };
+    (block, node)
+}
+
+fn decompile_block(
+    hash: BlockHash,
+    source: &Node<Compiled>,
+    relationships: &ContentRelationships,
+) -> (DecompiledBlock, Node<View>) {
+    let mut recorder = LabelRecorder {
+        relationships,
+        labels: Vec::new(),
+    };
+    let node = source.clone().process(&mut recorder);
+    let block = DecompiledBlock {
+        hash,
+        source: source.clone(),
+        labels: recorder.labels,
+    };
+    (block, node)
+}
+
+impl DecompiledBlock {
+    fn is_current(&self, relationships: &ContentRelationships) -> bool {
+        self.labels
+            .iter()
+            .all(|(node, label)| relationships.mention_label(node).as_ref() == Some(label))
+    }
+}
+
+/// Decompiles like [`ContentRelationships`] while remembering which labels were used.
+struct LabelRecorder<'r> {
+    relationships: &'r ContentRelationships,
+    labels: Vec<(CompiledTextNode, String)>,
+}
+
+impl NodeVisitor<Compiled> for LabelRecorder<'_> {
+    type OutputState = View;
+    fn visit_text_node(&mut self, node: CompiledTextNode) -> TextNodeView {
+        let Some(label) = self.relationships.mention_label(&node) else {
+            let CompiledTextNode::Text(text) = node else {
+                unreachable!("only text has no label");
+            };
+            return TextNodeView::Text(text);
+        };
+        self.labels.push((node.clone(), label.clone()));
+        match node {
+            CompiledTextNode::LegislationMention(id) => TextNodeView::LegislationMention {
+                attrs: Mention { id, label },
+            },
+            CompiledTextNode::MemberMention(id) => TextNodeView::MemberMention {
+                attrs: Mention { id, label },
+            },
+            CompiledTextNode::PostMention(id) => TextNodeView::PostMention {
+                attrs: Mention { id, label },
+            },
+            CompiledTextNode::Text(_) => unreachable!("text has no label"),
+        }
+    }
+}
+
+#[cfg(test)]
+fn sample_document(edited: &str) -> DocumentView {
+    DocumentView::builder()
+        .heading(1, |h| h.text("An act relating to roads"))
+        .paragraph(|p| p.text("Sponsored by ").member(7, "@alice"))
+        .paragraph(|p| p.text(edited).legislation(12, "HB 12"))
+        .bullet_list(|l| l.item_text("one").item_text("two"))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn incremental_compile_matches_full_compile() {
+    let mut compiler = IncrementalCompiler::new();
+    for edit in ["first draft ", "second draft "] {
+        let document = sample_document(edit);
+        let full = document.clone().compile();
+        let incremental = compiler.compile(&document);
+        assert_eq!(incremental.document, full.document);
+        assert_eq!(incremental.searchable_text, full.searchable_text);
+        assert_eq!(incremental.relationships, full.relationships);
+    }
+    // the second compile only reprocessed the edited paragraph.
+    assert_eq!(compiler.stats(), CacheStats { hits: 3, misses: 5 });
+}
+
+#[test]
+fn incremental_decompile_refreshes_changed_labels() {
+    let mut compiler = IncrementalCompiler::new();
+    let compiled = sample_document("text ").compile();
+    let mut relationships = compiled.relationships;
+
+    let view = compiler.decompile(&compiled.document, &relationships);
+    assert_eq!(view, &sample_document("text "));
+    assert_eq!(compiler.stats().misses, 4);
+
+    relationships.extend([], [(7, "@alice2".to_string())], []);
+    let view = compiler
+        .decompile(&compiled.document, &relationships)
+        .clone();
+    assert_eq!(
+        view,
+        compiled
+            .document
+            .clone()
+            .visit_and_decompile(&mut relationships)
+    );
+    // only the paragraph mentioning the member was decompiled again.
+    assert_eq!(compiler.stats(), CacheStats { hits: 3, misses: 5 });
+}
+
+#[test]
+fn hash_collisions_are_not_reused() {
+    let mut compiler = IncrementalCompiler::new();
+    compiler.compile(&sample_document("first draft "));
+
+    // make every previous block collide with the block at its position in the edit
+    let edited = sample_document("second draft ");
+    for (block, node) in compiler.compiled.iter_mut().zip(edited.nodes()) {
+        block.hash = BlockHash::of(node);
+    }
+    let incremental = compiler.compile(&edited);
+    let full = edited.clone().compile();
+    assert_eq!(incremental.document, full.document);
+    assert_eq!(incremental.searchable_text, full.searchable_text);
+    assert_eq!(compiler.stats(), CacheStats { hits: 3, misses: 5 });
+}
@@ -10,6 +10,9 @@ pub use node::*;
 mod list;
 pub use list::*;
 
+mod table;
+pub use table::*;
+
 mod text_node;
 pub use text_node::*;
 
@@ -19,6 +22,20 @@ pub use document_view::*;
 mod compiled_document;
 pub use compiled_document::*;
 
+mod policy;
+pub use policy::*;
+
+mod builder;
+pub use builder::*;
+
+mod bill_text;
+
+mod incremental;
+pub use incremental::*;
+
+#[cfg(feature = "html")]
+mod html;
+
 pub mod node_kind;
 
 use crate::tippytappy::node_kind::NodeKind;
@@ -32,6 +49,7 @@ pub trait State: Default {
         + std::fmt::Debug
         + PartialEq
         + Eq
+        + std::hash::Hash
         + Clone
         + utoipa::ToSchema;
     #[cfg(not(feature = "utoipa"))]
@@ -41,5 +59,6 @@ pub trait State: Default {
         + std::fmt::Debug
         + PartialEq
         + Eq
+        + std::hash::Hash
         + Clone;
 }
@@ -3,7 +3,7 @@ use markdown::mdast::Node as MdNode;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[cfg_attr(feature = "utoipa", schema(no_recursion))]
 #[serde(tag = "type", rename_all = "camelCase")]
@@ -29,6 +29,7 @@ pub enum Node<S: State> {
         content: Vec<DetailNode<S>>,
     },
     HorizontalRule,
+    Table(TableNode<S>),
 }
 
 impl Node<View> {
@@ -204,6 +205,7 @@ impl<S: State> Node<S> {
                 content: content.into_iter().map(|c| c.process(visitor)).collect(),
             },
             Node::HorizontalRule => Node::HorizontalRule,
+            Node::Table(table) => Node::Table(table.process(visitor)),
         }
     }
 }
@@ -310,11 +312,12 @@ impl<S: State> NodeKind for Node<S> {
             }
             Node::OrderedList(list) => list.iter_text(func),
             Node::Paragraph { content } => iter_node_children_text(content.iter(), func),
+            Node::Table(table) => table.iter_text(func),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum DetailNode<S: State> {
@@ -350,33 +353,41 @@ impl<S: State> DetailNode<S> {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct DetailAttributes {
     open: bool,
 }
+impl DetailAttributes {
+    pub fn new(open: bool) -> Self {
+        Self { open }
+    }
+    pub fn open(&self) -> bool {
+        self.open
+    }
+}
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct HeadingAttributes {
     pub level: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(tag = "type", content = "content")]
 pub enum ContentNode<S: State> {
     Doc(Vec<Node<S>>),
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(tag = "type", content = "attrs")]
 pub enum OtherNode {
     Image(ImageAttributes),
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct ImageAttributes {
     alt: Option<String>,
@@ -386,6 +397,37 @@ pub struct ImageAttributes {
     title: Option<String>,
 }
 impl ImageAttributes {
+    pub fn new(src: Url) -> Self {
+        Self {
+            alt: None,
+            height: None,
+            width: None,
+            src: Some(src),
+            title: None,
+        }
+    }
+    pub fn with_alt(mut self, alt: impl Into<String>) -> Self {
+        self.alt = Some(alt.into());
+        self
+    }
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+    pub fn with_size(mut self, width: i32, height: i32) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+    pub fn alt(&self) -> Option<&str> {
+        self.alt.as_deref()
+    }
+    pub fn src(&self) -> Option<&Url> {
+        self.src.as_ref()
+    }
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
     pub fn process_compile(self, visitor: &mut CompileCarriage) -> Self {
         if let Some(alt) = &self.alt {
             visitor.push_str(alt);
@@ -484,20 +526,23 @@ fn parse_nodes(md: &str) -> Vec<Node<View>> {
     helper.content
 }
 
+/// Text node test fixture, shared by the tippytappy modules.
 #[cfg(test)]
-fn plain(s: &str) -> TextNodeView {
+pub(super) fn text(s: &str, marks: Vec<Mark>) -> TextNodeView {
     TextNodeView::Text(Text {
         text: s.to_string(),
-        marks: vec![],
+        marks,
     })
 }
 
+#[cfg(test)]
+pub(super) fn plain(s: &str) -> TextNodeView {
+    text(s, vec![])
+}
+
 #[cfg(test)]
 fn coded(s: &str) -> TextNodeView {
-    TextNodeView::Text(Text {
-        text: s.to_string(),
-        marks: vec![Mark::Code],
-    })
+    text(s, vec![Mark::Code])
 }
 
 #[cfg(test)]
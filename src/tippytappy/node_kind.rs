@@ -8,6 +8,11 @@ pub trait NodeKind {
     where
         F: FnMut(&'slf str) -> bool;
 
+    /// Whether this is a mention of another entity rather than written text.
+    fn is_mention(&self) -> bool {
+        false
+    }
+
     /// Determined if this contains a string. Note that the `Pattern` trait is unsable, so we use a string here.
     fn contains(&self, pattern: &str) -> bool {
         let mut closure = |text: &str| text.contains(pattern);
@@ -0,0 +1,787 @@
+use std::fmt;
+
+use markdown::mdast::Node as MdNode;
+use thiserror::Error;
+use url::Url;
+
+use crate::tippytappy::*;
+
+/// Rules a [`DocumentView`] must follow before it is stored or rendered.
+///
+/// Use [`DocumentView::validate`] to reject documents that break the policy, or
+/// [`DocumentView::sanitize`] to rewrite them so that they comply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentPolicy {
+    allowed_schemes: Vec<String>,
+    allowed_hosts: Option<Vec<String>>,
+    allow_relative_urls: bool,
+    max_depth: usize,
+    max_heading_level: u32,
+    max_text_length: Option<usize>,
+    max_images: Option<usize>,
+    link_rel: Option<String>,
+    html: HtmlPolicy,
+}
+
+impl Default for DocumentPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_schemes: vec!["http".into(), "https".into(), "mailto".into()],
+            allowed_hosts: None,
+            allow_relative_urls: true,
+            max_depth: 6,
+            max_heading_level: 6,
+            max_text_length: None,
+            max_images: None,
+            link_rel: Some("noopener noreferrer nofollow".into()),
+            html: HtmlPolicy::Strip,
+        }
+    }
+}
+
+impl DocumentPolicy {
+    /// URL schemes allowed in link `href`s and image `src`s. Defaults to `http`, `https` and `mailto`.
+    pub fn with_allowed_schemes(
+        mut self,
+        schemes: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_schemes = schemes.into_iter().map(Into::into).collect();
+        self
+    }
+    /// Restricts URLs to these hosts and their subdomains. By default any host is allowed.
+    pub fn with_allowed_hosts(
+        mut self,
+        hosts: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_hosts = Some(hosts.into_iter().map(Into::into).collect());
+        self
+    }
+    /// Whether relative links such as `/legislation/12` are allowed.
+    pub fn with_relative_urls(mut self, allow: bool) -> Self {
+        self.allow_relative_urls = allow;
+        self
+    }
+    /// Maximum number of nested blockquotes, lists and details.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+    pub fn with_max_heading_level(mut self, level: u32) -> Self {
+        self.max_heading_level = level.max(1);
+        self
+    }
+    /// Maximum number of characters of text, including mention labels.
+    pub fn with_max_text_length(mut self, length: usize) -> Self {
+        self.max_text_length = Some(length);
+        self
+    }
+    pub fn with_max_images(mut self, images: usize) -> Self {
+        self.max_images = Some(images);
+        self
+    }
+    /// Tokens every link's `rel` must contain. `None` leaves `rel` untouched.
+    pub fn with_link_rel(mut self, rel: Option<impl Into<String>>) -> Self {
+        self.link_rel = rel.map(Into::into);
+        self
+    }
+    pub fn with_html(mut self, html: HtmlPolicy) -> Self {
+        self.html = html;
+        self
+    }
+
+    pub fn html(&self) -> HtmlPolicy {
+        self.html
+    }
+
+    /// Checks a URL against the allowed schemes and hosts.
+    pub fn check_url(&self, raw: &str) -> Result<(), PolicyViolation> {
+        let url = match Url::parse(raw) {
+            Ok(url) => url,
+            Err(url::ParseError::RelativeUrlWithoutBase) if self.allow_relative_urls => {
+                // protocol-relative urls (`//example.com`) still leave the site.
+                let base = Url::parse("https://relative.invalid").unwrap();
+                match base.join(raw) {
+                    Ok(url) if url.host_str() == base.host_str() => return Ok(()),
+                    Ok(url) => url,
+                    Err(_) => {
+                        return Err(PolicyViolation::InvalidUrl {
+                            url: raw.to_string(),
+                        });
+                    }
+                }
+            }
+            Err(_) => {
+                return Err(PolicyViolation::InvalidUrl {
+                    url: raw.to_string(),
+                });
+            }
+        };
+
+        if !self
+            .allowed_schemes
+            .iter()
+            .any(|scheme| scheme.eq_ignore_ascii_case(url.scheme()))
+        {
+            return Err(PolicyViolation::DisallowedScheme {
+                url: raw.to_string(),
+                scheme: url.scheme().to_string(),
+            });
+        }
+
+        if let (Some(hosts), Some(host)) = (&self.allowed_hosts, url.host_str())
+            && !hosts.iter().any(|allowed| host_matches(host, allowed))
+        {
+            return Err(PolicyViolation::DisallowedHost {
+                url: raw.to_string(),
+                host: host.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Lists every rule the document breaks. An empty list means the document is valid.
+    pub fn violations(&self, document: &DocumentView) -> Vec<PolicyViolation> {
+        let mut check = Check {
+            policy: self,
+            violations: Vec::new(),
+            images: 0,
+            text_length: 0,
+            too_deep: false,
+        };
+        check.nodes(document.nodes(), 0);
+
+        let mut violations = check.violations;
+        if let Some(max) = self.max_images
+            && check.images > max
+        {
+            violations.push(PolicyViolation::TooManyImages {
+                max,
+                count: check.images,
+            });
+        }
+        if let Some(max) = self.max_text_length
+            && check.text_length > max
+        {
+            violations.push(PolicyViolation::TooLong {
+                max,
+                length: check.text_length,
+            });
+        }
+        violations
+    }
+
+    /// Rewrites the document to comply with this policy.
+    ///
+    /// - links with disallowed URLs lose their link mark but keep their text
+    /// - images with disallowed sources, or past the image limit, are removed
+    /// - heading levels are clamped
+    /// - containers nested too deeply are unwrapped into their parent
+    /// - text past the length limit is truncated
+    /// - link `rel`s are extended with the required tokens
+    pub fn sanitize(&self, document: DocumentView) -> DocumentView {
+        let mut sanitizer = Sanitizer {
+            policy: self,
+            images: 0,
+            remaining: self.max_text_length,
+        };
+        let mut out = Vec::new();
+        sanitizer.nodes(document.into_nodes(), 0, &mut out);
+        DocumentView::from_nodes(out)
+    }
+
+    fn allows_heading(&self, level: u32) -> bool {
+        (1..=self.max_heading_level).contains(&level)
+    }
+
+    fn has_link_rel(&self, rel: Option<&str>) -> bool {
+        let Some(required) = &self.link_rel else {
+            return true;
+        };
+        let rel = rel.unwrap_or_default();
+        required.split_whitespace().all(|token| {
+            rel.split_whitespace()
+                .any(|t| t.eq_ignore_ascii_case(token))
+        })
+    }
+
+    fn merge_link_rel(&self, rel: Option<String>) -> Option<String> {
+        let Some(required) = &self.link_rel else {
+            return rel;
+        };
+        let mut merged = rel.unwrap_or_default();
+        for token in required.split_whitespace() {
+            if !merged
+                .split_whitespace()
+                .any(|t| t.eq_ignore_ascii_case(token))
+            {
+                if !merged.is_empty() {
+                    merged.push(' ');
+                }
+                merged.push_str(token);
+            }
+        }
+        Some(merged)
+    }
+}
+
+fn host_matches(host: &str, allowed: &str) -> bool {
+    host.eq_ignore_ascii_case(allowed)
+        || host
+            .len()
+            .checked_sub(allowed.len() + 1)
+            .is_some_and(|split| {
+                host.as_bytes()[split] == b'.' && host[split + 1..].eq_ignore_ascii_case(allowed)
+            })
+}
+
+/// What to do with raw HTML blocks found while parsing markdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HtmlPolicy {
+    /// Keep the HTML source as plain paragraph text. This is what [`DocumentView::parse_markdown`] does.
+    Text,
+    /// Drop HTML blocks entirely.
+    #[default]
+    Strip,
+    /// Fail parsing with [`PolicyViolation::Html`].
+    Reject,
+}
+
+impl HtmlPolicy {
+    /// Applies this policy to every HTML node in a markdown tree.
+    pub fn apply(self, node: &mut MdNode) -> Result<(), ParseError> {
+        let Some(children) = node.children_mut() else {
+            return Ok(());
+        };
+        match self {
+            HtmlPolicy::Text => return Ok(()),
+            HtmlPolicy::Strip => children.retain(|child| !matches!(child, MdNode::Html(_))),
+            HtmlPolicy::Reject => {
+                if children
+                    .iter()
+                    .any(|child| matches!(child, MdNode::Html(_)))
+                {
+                    return Err(ParseError::Policy(vec![PolicyViolation::Html]));
+                }
+            }
+        }
+        for child in children {
+            self.apply(child)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PolicyViolation {
+    #[error("`{url}` is not a valid url")]
+    InvalidUrl { url: String },
+    #[error("`{url}` uses the disallowed scheme `{scheme}`")]
+    DisallowedScheme { url: String, scheme: String },
+    #[error("`{url}` points to the disallowed host `{host}`")]
+    DisallowedHost { url: String, host: String },
+    #[error("link to `{href}` is missing the required rel")]
+    MissingLinkRel { href: String },
+    #[error("heading level {level} is not allowed")]
+    HeadingLevel { level: u32 },
+    #[error("content is nested more than {max} levels deep")]
+    TooDeep { max: usize },
+    #[error("document has {length} characters, the maximum is {max}")]
+    TooLong { max: usize, length: usize },
+    #[error("document has {count} images, the maximum is {max}")]
+    TooManyImages { max: usize, count: usize },
+    #[error("raw html is not allowed")]
+    Html,
+}
+
+impl PolicyViolation {
+    pub(crate) fn join(violations: &[PolicyViolation]) -> Joined<'_> {
+        Joined(violations)
+    }
+}
+
+pub(crate) struct Joined<'a>(&'a [PolicyViolation]);
+
+impl fmt::Display for Joined<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, violation) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{violation}")?;
+        }
+        Ok(())
+    }
+}
+
+struct Check<'p> {
+    policy: &'p DocumentPolicy,
+    violations: Vec<PolicyViolation>,
+    images: usize,
+    text_length: usize,
+    too_deep: bool,
+}
+
+impl Check<'_> {
+    fn nodes<'n>(&mut self, nodes: impl IntoIterator<Item = &'n Node<View>>, depth: usize) {
+        for node in nodes {
+            self.node(node, depth);
+        }
+    }
+
+    fn enter(&mut self, depth: usize) -> usize {
+        if depth >= self.policy.max_depth && !self.too_deep {
+            self.too_deep = true;
+            self.violations.push(PolicyViolation::TooDeep {
+                max: self.policy.max_depth,
+            });
+        }
+        depth + 1
+    }
+
+    fn node(&mut self, node: &Node<View>, depth: usize) {
+        match node {
+            Node::Image { attrs } => {
+                self.images += 1;
+                if let Some(src) = attrs.src()
+                    && let Err(violation) = self.policy.check_url(src.as_str())
+                {
+                    self.violations.push(violation);
+                }
+            }
+            Node::Heading { attrs, content } => {
+                if !self.policy.allows_heading(attrs.level) {
+                    self.violations
+                        .push(PolicyViolation::HeadingLevel { level: attrs.level });
+                }
+                self.text_nodes(content);
+            }
+            Node::Paragraph { content } => self.text_nodes(content),
+            Node::OrderedList(OrderedList { content, .. })
+            | Node::BulletList(BulletListNode { content }) => {
+                let depth = self.enter(depth);
+                for ListChild::ListItem { content } in content {
+                    self.nodes(content, depth);
+                }
+            }
+            Node::Blockquote { content } => {
+                let depth = self.enter(depth);
+                self.nodes(content, depth);
+            }
+            Node::Details { content, .. } => {
+                let depth = self.enter(depth);
+                for child in content {
+                    match child {
+                        DetailNode::DetailsSummary { content } => self.text_nodes(content),
+                        DetailNode::DetailsContent { content } => self.nodes(content, depth),
+                    }
+                }
+            }
+            Node::Table(TableNode { content }) => {
+                let depth = self.enter(depth);
+                for row in content {
+                    for cell in row.cells() {
+                        self.nodes(cell.content(), depth);
+                    }
+                }
+            }
+            Node::HorizontalRule => {}
+        }
+    }
+
+    fn text_nodes(&mut self, content: &[TextNodeView]) {
+        for node in content {
+            self.text_length += node.text().chars().count();
+            let TextNodeView::Text(text) = node else {
+                continue;
+            };
+            for mark in &text.marks {
+                let Mark::Link { attrs } = mark else {
+                    continue;
+                };
+                if let Err(violation) = self.policy.check_url(&attrs.href) {
+                    self.violations.push(violation);
+                } else if !self.policy.has_link_rel(attrs.rel.as_deref()) {
+                    self.violations.push(PolicyViolation::MissingLinkRel {
+                        href: attrs.href.clone(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+struct Sanitizer<'p> {
+    policy: &'p DocumentPolicy,
+    images: usize,
+    remaining: Option<usize>,
+}
+
+impl Sanitizer<'_> {
+    fn exhausted(&self) -> bool {
+        self.remaining == Some(0)
+    }
+
+    fn nodes(&mut self, nodes: Vec<Node<View>>, depth: usize, out: &mut Vec<Node<View>>) {
+        for node in nodes {
+            self.node(node, depth, out);
+        }
+    }
+
+    fn node(&mut self, node: Node<View>, depth: usize, out: &mut Vec<Node<View>>) {
+        match node {
+            Node::Image { attrs } => {
+                let allowed = attrs
+                    .src()
+                    .is_none_or(|src| self.policy.check_url(src.as_str()).is_ok());
+                let has_room = self.policy.max_images.is_none_or(|max| self.images < max);
+                if allowed && has_room {
+                    self.images += 1;
+                    out.push(Node::Image { attrs });
+                }
+            }
+            Node::Heading { mut attrs, content } => {
+                attrs.level = attrs.level.clamp(1, self.policy.max_heading_level);
+                let content = self.text_nodes(content);
+                if !content.is_empty() || !self.exhausted() {
+                    out.push(Node::Heading { attrs, content });
+                }
+            }
+            Node::Paragraph { content } => {
+                let content = self.text_nodes(content);
+                if !content.is_empty() || !self.exhausted() {
+                    out.push(Node::Paragraph { content });
+                }
+            }
+            Node::OrderedList(OrderedList { attrs, content }) => {
+                if let Some(content) = self.list_items(content, depth, out) {
+                    out.push(Node::OrderedList(OrderedList { attrs, content }));
+                }
+            }
+            Node::BulletList(BulletListNode { content }) => {
+                if let Some(content) = self.list_items(content, depth, out) {
+                    out.push(Node::BulletList(BulletListNode { content }));
+                }
+            }
+            Node::Blockquote { content } => {
+                if depth >= self.policy.max_depth {
+                    self.nodes(content, depth, out);
+                    return;
+                }
+                let mut nodes = Vec::new();
+                self.nodes(content, depth + 1, &mut nodes);
+                if !nodes.is_empty() {
+                    out.push(Node::Blockquote { content: nodes });
+                }
+            }
+            Node::Details { attrs, content } => {
+                if depth >= self.policy.max_depth {
+                    for child in content {
+                        match child {
+                            DetailNode::DetailsSummary { content } => {
+                                self.node(Node::Paragraph { content }, depth, out)
+                            }
+                            DetailNode::DetailsContent { content } => {
+                                self.nodes(content, depth, out)
+                            }
+                        }
+                    }
+                    return;
+                }
+                let content = content
+                    .into_iter()
+                    .map(|child| match child {
+                        DetailNode::DetailsSummary { content } => DetailNode::DetailsSummary {
+                            content: self.text_nodes(content),
+                        },
+                        DetailNode::DetailsContent { content } => {
+                            let mut nodes = Vec::new();
+                            self.nodes(content, depth + 1, &mut nodes);
+                            DetailNode::DetailsContent { content: nodes }
+                        }
+                    })
+                    .collect();
+                out.push(Node::Details { attrs, content });
+            }
+            Node::Table(TableNode { content }) => {
+                if depth >= self.policy.max_depth {
+                    for TableRow::TableRow { content } in content {
+                        for cell in content {
+                            let (_, content, _) = cell.into_parts();
+                            self.nodes(content, depth, out);
+                        }
+                    }
+                    return;
+                }
+                let rows = content
+                    .into_iter()
+                    .map(|TableRow::TableRow { content }| {
+                        let cells = content
+                            .into_iter()
+                            .map(|cell| {
+                                let (attrs, content, header) = cell.into_parts();
+                                let mut nodes = Vec::new();
+                                self.nodes(content, depth + 1, &mut nodes);
+                                // cells always hold at least one block.
+                                if nodes.is_empty() {
+                                    nodes.push(Node::Paragraph { content: vec![] });
+                                }
+                                TableCell::from_parts(attrs, nodes, header)
+                            })
+                            .collect();
+                        TableRow::new(cells)
+                    })
+                    .collect();
+                out.push(Node::Table(TableNode::new(rows)));
+            }
+            Node::HorizontalRule => out.push(Node::HorizontalRule),
+        }
+    }
+
+    /// Returns `None` if the list was unwrapped into `out` or ended up empty.
+    fn list_items(
+        &mut self,
+        items: Vec<ListChild<View>>,
+        depth: usize,
+        out: &mut Vec<Node<View>>,
+    ) -> Option<Vec<ListChild<View>>> {
+        if depth >= self.policy.max_depth {
+            for ListChild::ListItem { content } in items {
+                self.nodes(content, depth, out);
+            }
+            return None;
+        }
+        let items: Vec<_> = items
+            .into_iter()
+            .filter_map(|ListChild::ListItem { content }| {
+                let mut nodes = Vec::new();
+                self.nodes(content, depth + 1, &mut nodes);
+                (!nodes.is_empty()).then(|| ListChild::new(nodes))
+            })
+            .collect();
+        (!items.is_empty()).then_some(items)
+    }
+
+    fn text_nodes(&mut self, content: Vec<TextNodeView>) -> Vec<TextNodeView> {
+        content
+            .into_iter()
+            .filter_map(|node| self.text_node(node))
+            .collect()
+    }
+
+    fn text_node(&mut self, node: TextNodeView) -> Option<TextNodeView> {
+        match node {
+            TextNodeView::Text(mut text) => {
+                text.text = self.take_text(text.text)?;
+                text.marks = text
+                    .marks
+                    .into_iter()
+                    .filter_map(|mark| self.mark(mark))
+                    .collect();
+                Some(TextNodeView::Text(text))
+            }
+            // mentions can't be cut in half, so they are dropped if they don't fit.
+            mention => {
+                let length = mention.text().chars().count();
+                match &mut self.remaining {
+                    Some(remaining) if *remaining < length => {
+                        *remaining = 0;
+                        None
+                    }
+                    Some(remaining) => {
+                        *remaining -= length;
+                        Some(mention)
+                    }
+                    None => Some(mention),
+                }
+            }
+        }
+    }
+
+    fn take_text(&mut self, mut text: String) -> Option<String> {
+        let Some(remaining) = &mut self.remaining else {
+            return Some(text);
+        };
+        if *remaining == 0 {
+            return None;
+        }
+        match text.char_indices().nth(*remaining) {
+            Some((end, _)) => {
+                *remaining = 0;
+                text.truncate(end);
+            }
+            None => *remaining -= text.chars().count(),
+        }
+        Some(text)
+    }
+
+    fn mark(&self, mark: Mark) -> Option<Mark> {
+        match mark {
+            Mark::Link { mut attrs } => {
+                self.policy.check_url(&attrs.href).ok()?;
+                attrs.rel = self.policy.merge_link_rel(attrs.rel);
+                Some(Mark::Link { attrs })
+            }
+            mark => Some(mark),
+        }
+    }
+}
+
+#[cfg(test)]
+fn link(text: &str, href: &str, rel: Option<&str>) -> TextNodeView {
+    TextNodeView::Text(Text {
+        text: text.to_string(),
+        marks: vec![Mark::Link {
+            attrs: LinkAttributes {
+                href: href.to_string(),
+                target: None,
+                rel: rel.map(str::to_string),
+                class: None,
+                title: None,
+            },
+        }],
+    })
+}
+
+#[cfg(test)]
+fn paragraph(content: Vec<TextNodeView>) -> Node<View> {
+    Node::Paragraph { content }
+}
+
+#[test]
+fn rejects_javascript_links() {
+    let doc =
+        DocumentView::from_nodes([paragraph(vec![link("click", "javascript:alert(1)", None)])]);
+    let violations = DocumentPolicy::default().violations(&doc);
+    assert_eq!(
+        violations,
+        vec![PolicyViolation::DisallowedScheme {
+            url: "javascript:alert(1)".into(),
+            scheme: "javascript".into(),
+        }]
+    );
+    assert!(matches!(
+        doc.validate(&DocumentPolicy::default()),
+        Err(ParseError::Policy(_))
+    ));
+}
+
+#[test]
+fn sanitize_strips_bad_links_and_adds_rel() {
+    let doc = DocumentView::from_nodes([paragraph(vec![
+        link("bad", "javascript:alert(1)", None),
+        link("good", "https://peacher.app", Some("external")),
+    ])]);
+    let policy = DocumentPolicy::default();
+    let doc = doc.sanitize(&policy);
+
+    assert_eq!(
+        doc.nodes(),
+        &[paragraph(vec![
+            TextNodeView::Text(Text {
+                text: "bad".into(),
+                marks: vec![],
+            }),
+            link(
+                "good",
+                "https://peacher.app",
+                Some("external noopener noreferrer nofollow")
+            ),
+        ])]
+    );
+    assert!(doc.validate(&policy).is_ok());
+}
+
+#[test]
+fn allowed_hosts_include_subdomains() {
+    let policy = DocumentPolicy::default().with_allowed_hosts(["peacher.app"]);
+    assert!(policy.check_url("https://peacher.app/a").is_ok());
+    assert!(policy.check_url("https://cdn.peacher.app/a.png").is_ok());
+    assert!(policy.check_url("/legislation/12").is_ok());
+    assert!(matches!(
+        policy.check_url("https://notpeacher.app"),
+        Err(PolicyViolation::DisallowedHost { .. })
+    ));
+    assert!(matches!(
+        policy.check_url("//evil.com/x"),
+        Err(PolicyViolation::DisallowedHost { .. })
+    ));
+}
+
+#[test]
+fn sanitize_unwraps_deep_nesting() {
+    let doc = DocumentView::parse_markdown("> > > deep").unwrap();
+    let policy = DocumentPolicy::default().with_max_depth(1);
+    assert_eq!(
+        policy.violations(&doc),
+        vec![PolicyViolation::TooDeep { max: 1 }]
+    );
+
+    let doc = doc.sanitize(&policy);
+    assert_eq!(
+        doc.nodes(),
+        &[Node::Blockquote {
+            content: vec![paragraph(vec![TextNodeView::Text(Text {
+                text: "deep".into(),
+                marks: vec![],
+            })])],
+        }]
+    );
+}
+
+#[test]
+fn sanitize_clamps_headings_and_truncates_text() {
+    let doc = DocumentView::from_nodes([
+        Node::Heading {
+            attrs: HeadingAttributes { level: 9 },
+            content: vec![TextNodeView::Text(Text {
+                text: "Title".into(),
+                marks: vec![],
+            })],
+        },
+        paragraph(vec![TextNodeView::Text(Text {
+            text: "Some long body".into(),
+            marks: vec![],
+        })]),
+        paragraph(vec![TextNodeView::Text(Text {
+            text: "dropped".into(),
+            marks: vec![],
+        })]),
+    ]);
+    let policy = DocumentPolicy::default()
+        .with_max_heading_level(3)
+        .with_max_text_length(9);
+    assert_eq!(policy.violations(&doc).len(), 2);
+
+    let doc = doc.sanitize(&policy);
+    assert_eq!(
+        doc.nodes(),
+        &[
+            Node::Heading {
+                attrs: HeadingAttributes { level: 3 },
+                content: vec![TextNodeView::Text(Text {
+                    text: "Title".into(),
+                    marks: vec![],
+                })],
+            },
+            paragraph(vec![TextNodeView::Text(Text {
+                text: "Some".into(),
+                marks: vec![],
+            })]),
+        ]
+    );
+}
+
+#[test]
+fn html_policy_on_parse() {
+    let md = "before\n\n<script>alert(1)</script>\n\nafter";
+
+    let doc = DocumentView::parse_markdown_with_policy(md, &DocumentPolicy::default()).unwrap();
+    assert_eq!(doc.nodes().len(), 2);
+
+    let reject = DocumentPolicy::default().with_html(HtmlPolicy::Reject);
+    assert!(matches!(
+        DocumentView::parse_markdown_with_policy(md, &reject),
+        Err(ParseError::Policy(v)) if v == vec![PolicyViolation::Html]
+    ));
+}
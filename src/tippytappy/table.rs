@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+use crate::tippytappy::{node_kind::iter_node_children_text, *};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TableNode<S: State> {
+    pub content: Vec<TableRow<S>>,
+}
+
+impl<S: State> NodeKind for TableNode<S> {
+    fn iter_text<'slf, F>(&'slf self, func: &mut F) -> bool
+    where
+        F: FnMut(&'slf str) -> bool,
+    {
+        iter_node_children_text(self.content.iter(), func)
+    }
+}
+
+impl<S: State> TableNode<S> {
+    pub fn process<V: NodeVisitor<S>>(self, visitor: &mut V) -> TableNode<V::OutputState> {
+        TableNode {
+            content: self
+                .content
+                .into_iter()
+                .map(|row| row.process(visitor))
+                .collect(),
+        }
+    }
+
+    pub fn new(content: Vec<TableRow<S>>) -> Self {
+        Self { content }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TableRow<S: State> {
+    TableRow { content: Vec<TableCell<S>> },
+}
+
+impl<S: State> NodeKind for TableRow<S> {
+    fn iter_text<'slf, F>(&'slf self, func: &mut F) -> bool
+    where
+        F: FnMut(&'slf str) -> bool,
+    {
+        match self {
+            TableRow::TableRow { content } => iter_node_children_text(content.iter(), func),
+        }
+    }
+}
+
+impl<S: State> TableRow<S> {
+    pub fn process<V: NodeVisitor<S>>(self, visitor: &mut V) -> TableRow<V::OutputState> {
+        match self {
+            TableRow::TableRow { content } => TableRow::TableRow {
+                content: content.into_iter().map(|c| c.process(visitor)).collect(),
+            },
+        }
+    }
+
+    pub fn new(content: Vec<TableCell<S>>) -> Self {
+        Self::TableRow { content }
+    }
+
+    pub fn cells(&self) -> &[TableCell<S>] {
+        match self {
+            TableRow::TableRow { content } => content,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TableCell<S: State> {
+    TableHeader {
+        attrs: CellAttributes,
+        content: Vec<Node<S>>,
+    },
+    TableCell {
+        attrs: CellAttributes,
+        content: Vec<Node<S>>,
+    },
+}
+
+impl<S: State> NodeKind for TableCell<S> {
+    fn iter_text<'slf, F>(&'slf self, func: &mut F) -> bool
+    where
+        F: FnMut(&'slf str) -> bool,
+    {
+        iter_node_children_text(self.content().iter(), func)
+    }
+}
+
+impl<S: State> TableCell<S> {
+    pub fn process<V: NodeVisitor<S>>(self, visitor: &mut V) -> TableCell<V::OutputState> {
+        match self {
+            TableCell::TableHeader { attrs, content } => TableCell::TableHeader {
+                attrs,
+                content: content.into_iter().map(|c| c.process(visitor)).collect(),
+            },
+            TableCell::TableCell { attrs, content } => TableCell::TableCell {
+                attrs,
+                content: content.into_iter().map(|c| c.process(visitor)).collect(),
+            },
+        }
+    }
+
+    pub fn content(&self) -> &[Node<S>] {
+        match self {
+            TableCell::TableHeader { content, .. } | TableCell::TableCell { content, .. } => {
+                content
+            }
+        }
+    }
+
+    /// Splits the cell into its attributes, content and whether it is a header cell.
+    pub fn into_parts(self) -> (CellAttributes, Vec<Node<S>>, bool) {
+        match self {
+            TableCell::TableHeader { attrs, content } => (attrs, content, true),
+            TableCell::TableCell { attrs, content } => (attrs, content, false),
+        }
+    }
+
+    pub fn from_parts(attrs: CellAttributes, content: Vec<Node<S>>, header: bool) -> Self {
+        if header {
+            TableCell::TableHeader { attrs, content }
+        } else {
+            TableCell::TableCell { attrs, content }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CellAttributes {
+    pub colspan: u32,
+    pub rowspan: u32,
+    #[serde(default)]
+    pub colwidth: Option<Vec<u32>>,
+}
+
+impl Default for CellAttributes {
+    fn default() -> Self {
+        Self {
+            colspan: 1,
+            rowspan: 1,
+            colwidth: None,
+        }
+    }
+}
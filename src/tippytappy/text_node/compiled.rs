@@ -3,7 +3,7 @@ use uuid::Uuid;
 
 use crate::tippytappy::{node_kind::NodeKind, *};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[serde(tag = "type", content = "data", rename_all = "camelCase")]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub enum CompiledTextNode {
@@ -24,4 +24,8 @@ impl NodeKind for CompiledTextNode {
             true
         }
     }
+
+    fn is_mention(&self) -> bool {
+        !matches!(self, CompiledTextNode::Text(_))
+    }
 }
@@ -3,7 +3,7 @@ use uuid::Uuid;
 
 use crate::tippytappy::{Text, node_kind::NodeKind};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[serde(tag = "type", rename_all = "camelCase")]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub enum TextNodeView {
@@ -26,6 +26,10 @@ impl NodeKind for TextNodeView {
             TextNodeView::PostMention { attrs } => func(&attrs.label),
         }
     }
+
+    fn is_mention(&self) -> bool {
+        !matches!(self, TextNodeView::Text(_))
+    }
 }
 
 impl TextNodeView {
@@ -39,7 +43,7 @@ impl TextNodeView {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Mention<Id> {
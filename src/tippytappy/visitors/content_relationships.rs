@@ -11,7 +11,7 @@ use crate::tippytappy::*;
 /// it can be used to turn content within a [`CompiledDocument`](crate::tippytappy::CompiledView) to
 /// turn into a [`DocumentView`](crate::tippytappy::DocumentView).
 ///
-#[derive(Default)]
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct ContentRelationships {
     pub(super) legislation_nameids: HashMap<i32, String>,
     pub(super) member_handles: HashMap<i32, String>,
@@ -52,6 +52,24 @@ impl ContentRelationships {
         self.sibling_labels.extend(siblings);
     }
 
+    /// Adds every relationship in `other`, replacing labels for ids that are already present.
+    pub fn merge(&mut self, other: &ContentRelationships) {
+        self.extend(
+            other
+                .legislation_nameids
+                .iter()
+                .map(|(id, label)| (*id, label.clone())),
+            other
+                .member_handles
+                .iter()
+                .map(|(id, label)| (*id, label.clone())),
+            other
+                .sibling_labels
+                .iter()
+                .map(|(id, label)| (*id, label.clone())),
+        );
+    }
+
     pub fn legislation_ids(&self) -> impl Iterator<Item = i32> {
         self.legislation_nameids.keys().copied()
     }
@@ -76,40 +94,44 @@ impl ContentRelationships {
     }
 }
 
+impl ContentRelationships {
+    /// The label shown for a mention when decompiling, falling back to a placeholder for unknown ids.
+    pub fn mention_label(&self, node: &CompiledTextNode) -> Option<String> {
+        match node {
+            CompiledTextNode::Text(_) => None,
+            CompiledTextNode::LegislationMention(id) => Some(
+                self.get_legislation_nameid(*id)
+                    .unwrap_or(format!("Legislation #{id}")),
+            ),
+            CompiledTextNode::MemberMention(member_id) => Some(
+                self.get_member_handle(*member_id)
+                    .unwrap_or("@{UNKNOWN}".to_string()),
+            ),
+            CompiledTextNode::PostMention(id) => {
+                Some(self.get_content_label(*id).unwrap_or("UNKNOWN".to_string()))
+            }
+        }
+    }
+}
+
 impl NodeVisitor<Compiled> for ContentRelationships {
     type OutputState = View;
     fn visit_text_node(&mut self, node: CompiledTextNode) -> TextNodeView {
+        let label = self.mention_label(&node).unwrap_or_default();
         match node {
             CompiledTextNode::Text(text) => TextNodeView::Text(text),
-            CompiledTextNode::LegislationMention(id) => {
-                let label = self.get_legislation_nameid(id);
-
-                TextNodeView::LegislationMention {
-                    attrs: Mention {
-                        id,
-                        label: label.unwrap_or(format!("Legislation #{id}")),
-                    },
-                }
-            }
-            CompiledTextNode::MemberMention(member_id) => {
-                let label = self.get_member_handle(member_id);
-
-                TextNodeView::MemberMention {
-                    attrs: Mention {
-                        id: member_id,
-                        label: label.unwrap_or("@{UNKNOWN}".to_string()),
-                    },
-                }
-            }
-            CompiledTextNode::PostMention(id) => {
-                let label = self.get_content_label(id);
-                TextNodeView::PostMention {
-                    attrs: Mention {
-                        id,
-                        label: label.unwrap_or("UNKNOWN".to_string()),
-                    },
-                }
-            }
+            CompiledTextNode::LegislationMention(id) => TextNodeView::LegislationMention {
+                attrs: Mention { id, label },
+            },
+            CompiledTextNode::MemberMention(member_id) => TextNodeView::MemberMention {
+                attrs: Mention {
+                    id: member_id,
+                    label,
+                },
+            },
+            CompiledTextNode::PostMention(id) => TextNodeView::PostMention {
+                attrs: Mention { id, label },
+            },
         }
     }
 }
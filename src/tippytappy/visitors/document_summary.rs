@@ -0,0 +1,311 @@
+use serde::{Deserialize, Serialize};
+
+use crate::tippytappy::{
+    node_kind::{NodeKind, iter_node_children_text},
+    *,
+};
+
+/// Controls how a [`DocumentSummary`] is produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SummaryOptions {
+    excerpt_length: usize,
+    words_per_minute: u32,
+}
+
+impl Default for SummaryOptions {
+    fn default() -> Self {
+        Self {
+            excerpt_length: 280,
+            words_per_minute: 238,
+        }
+    }
+}
+
+impl SummaryOptions {
+    /// Maximum number of characters in the excerpt, including a trailing ellipsis.
+    pub fn with_excerpt_length(mut self, excerpt_length: usize) -> Self {
+        self.excerpt_length = excerpt_length;
+        self
+    }
+    pub fn with_words_per_minute(mut self, words_per_minute: u32) -> Self {
+        self.words_per_minute = words_per_minute.max(1);
+        self
+    }
+}
+
+/// Reading metadata for a document, used by feeds and notifications.
+///
+/// Produced by [`DocumentView::summarize`] and [`CompiledDocument::summarize`] in a single pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DocumentSummary {
+    /// Body text cut at a sentence or word boundary. Headings and mentions are skipped.
+    pub excerpt: String,
+    pub first_image: Option<ImageAttributes>,
+    pub outline: Vec<OutlineEntry>,
+    pub word_count: usize,
+    pub reading_time_minutes: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct OutlineEntry {
+    pub level: u32,
+    pub text: String,
+}
+
+impl DocumentSummary {
+    pub fn from_nodes<S: State>(nodes: &[Node<S>], options: &SummaryOptions) -> Self {
+        let mut visitor = SummaryVisitor {
+            limit: options.excerpt_length,
+            ..Default::default()
+        };
+        visitor.nodes(nodes);
+
+        let word_count = visitor.word_count;
+        let reading_time_minutes = word_count.div_ceil(options.words_per_minute as usize) as u32;
+
+        DocumentSummary {
+            excerpt: visitor.excerpt(),
+            first_image: visitor.first_image,
+            outline: visitor.outline,
+            word_count,
+            reading_time_minutes,
+        }
+    }
+}
+
+#[derive(Default)]
+struct SummaryVisitor {
+    limit: usize,
+    excerpt: String,
+    excerpt_chars: usize,
+    truncated: bool,
+    first_image: Option<ImageAttributes>,
+    outline: Vec<OutlineEntry>,
+    word_count: usize,
+    in_word: bool,
+}
+
+impl SummaryVisitor {
+    fn nodes<S: State>(&mut self, nodes: &[Node<S>]) {
+        for node in nodes {
+            self.node(node);
+        }
+    }
+
+    fn node<S: State>(&mut self, node: &Node<S>) {
+        match node {
+            Node::Image { attrs } => {
+                if self.first_image.is_none() && attrs.src().is_some() {
+                    self.first_image = Some(attrs.clone());
+                }
+            }
+            Node::Heading { attrs, content } => {
+                let mut text = String::new();
+                iter_node_children_text(content.iter(), &mut |t| {
+                    text.push_str(t);
+                    true
+                });
+                self.in_word = false;
+                self.count_words(&text);
+                self.outline.push(OutlineEntry {
+                    level: attrs.level,
+                    text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+                });
+            }
+            Node::Paragraph { content } => self.text_block(content),
+            Node::OrderedList(OrderedList { content, .. })
+            | Node::BulletList(BulletListNode { content }) => {
+                for ListChild::ListItem { content } in content {
+                    self.nodes(content);
+                }
+            }
+            Node::Blockquote { content } => self.nodes(content),
+            Node::Details { content, .. } => {
+                for child in content {
+                    match child {
+                        DetailNode::DetailsSummary { content } => self.text_block(content),
+                        DetailNode::DetailsContent { content } => self.nodes(content),
+                    }
+                }
+            }
+            Node::Table(TableNode { content }) => {
+                for row in content {
+                    for cell in row.cells() {
+                        self.nodes(cell.content());
+                    }
+                }
+            }
+            Node::HorizontalRule => {}
+        }
+    }
+
+    fn text_block<T: NodeKind>(&mut self, content: &[T]) {
+        self.in_word = false;
+        self.push_excerpt(" ");
+        for node in content {
+            let mention = node.is_mention();
+            let mut has_text = false;
+            node.iter_text(&mut |text| {
+                has_text = true;
+                self.count_words(text);
+                if !mention {
+                    self.push_excerpt(text);
+                }
+                true
+            });
+            // compiled mentions carry only an id, but still read as a word.
+            if mention && !has_text {
+                self.word_count += 1;
+                self.in_word = false;
+            }
+        }
+    }
+
+    fn count_words(&mut self, text: &str) {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                self.in_word = false;
+            } else if c.is_alphanumeric() && !self.in_word {
+                self.in_word = true;
+                self.word_count += 1;
+            }
+        }
+    }
+
+    /// Appends text with collapsed whitespace, stopping one character past the limit
+    /// so that [`Self::excerpt`] knows whether anything was cut.
+    fn push_excerpt(&mut self, text: &str) {
+        for c in text.chars() {
+            if self.truncated {
+                return;
+            }
+            if c.is_whitespace() {
+                if self.excerpt.is_empty() || self.excerpt.ends_with(' ') {
+                    continue;
+                }
+                self.excerpt.push(' ');
+            } else {
+                self.excerpt.push(c);
+            }
+            self.excerpt_chars += 1;
+            self.truncated = self.excerpt_chars > self.limit;
+        }
+    }
+
+    fn excerpt(&self) -> String {
+        let text = self.excerpt.trim_end();
+        if !self.truncated {
+            return text.to_string();
+        }
+
+        let window_end = text
+            .char_indices()
+            .nth(self.limit.saturating_sub(1))
+            .map_or(text.len(), |(i, _)| i);
+        let window = &text[..window_end];
+
+        // prefer ending on a full sentence if it keeps at least a third of the excerpt.
+        let sentence_end = window
+            .char_indices()
+            .filter(|(i, c)| {
+                matches!(c, '.' | '!' | '?') && text[i + c.len_utf8()..].starts_with(' ')
+            })
+            .map(|(i, c)| i + c.len_utf8())
+            .next_back();
+        if let Some(end) = sentence_end
+            && window[..end].chars().count() >= self.limit / 3
+        {
+            return window[..end].to_string();
+        }
+
+        let cut = match window.rfind(' ') {
+            Some(space) if !text[window_end..].starts_with(' ') => &window[..space],
+            _ => window,
+        };
+        let cut = cut.trim_end_matches(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | ':'));
+        format!("{cut}…")
+    }
+}
+
+#[cfg(test)]
+fn summarize(md: &str, options: SummaryOptions) -> DocumentSummary {
+    DocumentView::parse_markdown(md)
+        .unwrap()
+        .summarize(&options)
+}
+
+#[test]
+fn summary_counts_words_and_outline() {
+    let summary = summarize(
+        "# Intro\n\nThis bill does **three** things.\n\n## Details\n\n- funds roads\n- funds schools",
+        SummaryOptions::default(),
+    );
+    assert_eq!(summary.word_count, 11);
+    assert_eq!(summary.reading_time_minutes, 1);
+    assert_eq!(
+        summary.outline,
+        vec![
+            OutlineEntry {
+                level: 1,
+                text: "Intro".into()
+            },
+            OutlineEntry {
+                level: 2,
+                text: "Details".into()
+            },
+        ]
+    );
+    assert_eq!(
+        summary.excerpt,
+        "This bill does three things. funds roads funds schools"
+    );
+}
+
+#[test]
+fn excerpt_prefers_sentence_boundary() {
+    let summary = summarize(
+        "The committee met today. It discussed the budget at length and voted.",
+        SummaryOptions::default().with_excerpt_length(40),
+    );
+    assert_eq!(summary.excerpt, "The committee met today.");
+}
+
+#[test]
+fn excerpt_falls_back_to_word_boundary() {
+    let summary = summarize(
+        "An act relating to transportation, infrastructure and funding",
+        SummaryOptions::default().with_excerpt_length(36),
+    );
+    assert_eq!(summary.excerpt, "An act relating to transportation…");
+    assert!(summary.excerpt.chars().count() <= 36);
+}
+
+#[test]
+fn excerpt_skips_mentions() {
+    let doc = DocumentView::from_nodes([Node::Paragraph {
+        content: vec![
+            TextNodeView::Text(Text {
+                text: "Thanks ".into(),
+                marks: vec![],
+            }),
+            TextNodeView::MemberMention {
+                attrs: Mention {
+                    id: 1,
+                    label: "@alice".into(),
+                },
+            },
+            TextNodeView::Text(Text {
+                text: " for sponsoring".into(),
+                marks: vec![],
+            }),
+        ],
+    }]);
+    let summary = doc.summarize(&SummaryOptions::default());
+    assert_eq!(summary.excerpt, "Thanks for sponsoring");
+    assert_eq!(summary.word_count, 4);
+
+    let compiled = doc.compile().document.summarize(&SummaryOptions::default());
+    assert_eq!(compiled, summary);
+}
@@ -7,6 +7,9 @@ pub use compile_carriage::*;
 mod content_dependencies;
 pub use content_dependencies::*;
 
+mod document_summary;
+pub use document_summary::*;
+
 use crate::tippytappy::State;
 
 pub trait NodeVisitor<S: State> {